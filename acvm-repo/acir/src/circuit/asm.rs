@@ -0,0 +1,1067 @@
+//! A stable, human-readable textual assembly format for ACIR circuits.
+//!
+//! Unlike the `Display` implementation of [Circuit], which is intended for debugging, this format can be
+//! parsed back into an identical [Circuit] so that it can be used for round-trips and external analysis.
+//!
+//! A circuit is written as a list of header lines followed by one opcode per line.
+//! Blank lines and anything following a `#` are ignored.
+//!
+//! ```text
+//! circuit     := line*
+//! line        := header | opcode
+//! header      := "current_witness_index" u32
+//!              | "expression_width" ("unbounded" | usize)
+//!              | "private_parameters" witnesses
+//!              | "public_parameters" witnesses
+//!              | "return_values" witnesses
+//!              | "assert" location u64 "[" (payload ("," payload)*)? "]"
+//! opcode      := "ASSERT_ZERO" expression
+//!              | "BLACKBOX" name (field "=" value)*
+//!              | "MEMORY_INIT" block block_type witnesses
+//!              | "MEMORY_OP" block expression expression expression predicate?
+//!              | "BRILLIG_CALL" u32 "[" (brillig_input ("," brillig_input)*)? "]"
+//!                               "[" (brillig_output ("," brillig_output)*)? "]" predicate?
+//!              | "CALL" u32 witnesses witnesses predicate?
+//!
+//! expression  := "{" (term ("+" term)*)? "}"
+//! term        := field "*" witness "*" witness | field "*" witness | field
+//! predicate   := "if" expression
+//! payload     := expression | block
+//! block_type  := "memory" | "calldata" u32 | "returndata"
+//! brillig_input  := expression | "[" (expression ("," expression)*)? "]" | block
+//! brillig_output := witness | witnesses
+//!
+//! value       := witness | input | u32 | "[" (value ("," value)*)? "]"
+//! input       := (witness | field) ":" u32
+//! witnesses   := "[" (witness ("," witness)*)? "]"
+//! witness     := "w" u32
+//! block       := "b" u32
+//! location    := usize | usize "." usize
+//! field       := "-"? decimal | "0x" hex
+//! ```
+//!
+//! The operands of a `BLACKBOX` opcode are named after the fields of the corresponding [BlackBoxFuncCall] variant,
+//! with the inputs and outputs of an `EmbeddedCurveAdd` or `MultiScalarMul` written as lists.
+//! The operands of a `MEMORY_OP` are its operation, index and value in that order.
+
+use std::collections::{BTreeSet, HashMap};
+
+use acir_field::AcirField;
+use thiserror::Error;
+
+use super::brillig::{BrilligFunctionId, BrilligInputs, BrilligOutputs};
+use super::opcodes::{
+    AcirFunctionId, BlackBoxFuncCall, BlockId, BlockType, ConstantOrWitnessEnum, FunctionInput,
+    MemOp,
+};
+use super::{
+    AssertionPayload, Circuit, ExpressionOrMemory, ExpressionWidth, Opcode, OpcodeLocation,
+    PublicInputs,
+};
+use crate::native_types::{Expression, Witness};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Error on line {line}: {message}")]
+pub struct AsmParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Writes the circuit in the textual assembly format.
+pub fn print_circuit<F: AcirField>(circuit: &Circuit<F>) -> String {
+    let mut lines = vec![format!("current_witness_index {}", circuit.current_witness_index)];
+    lines.push(match circuit.expression_width {
+        ExpressionWidth::Unbounded => "expression_width unbounded".to_string(),
+        ExpressionWidth::Bounded { width } => format!("expression_width {width}"),
+    });
+    lines.push(format!("private_parameters {}", print_witnesses(&circuit.private_parameters)));
+    lines.push(format!("public_parameters {}", print_witnesses(&circuit.public_parameters.0)));
+    lines.push(format!("return_values {}", print_witnesses(&circuit.return_values.0)));
+    for (location, payload) in &circuit.assert_messages {
+        let items: Vec<String> = payload
+            .payload
+            .iter()
+            .map(|item| match item {
+                ExpressionOrMemory::Expression(expr) => print_expression(expr),
+                ExpressionOrMemory::Memory(block_id) => format!("b{}", block_id.0),
+            })
+            .collect();
+        lines.push(format!("assert {location} {} [{}]", payload.error_selector, items.join(", ")));
+    }
+    lines.extend(circuit.opcodes.iter().map(print_opcode));
+
+    let mut asm = lines.join("\n");
+    asm.push('\n');
+    asm
+}
+
+/// Parses a circuit written in the textual assembly format.
+pub fn parse_circuit<F: AcirField>(asm: &str) -> Result<Circuit<F>, AsmParseError> {
+    let mut circuit = Circuit::default();
+    for (index, line) in asm.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.split('#').next().unwrap_or_default();
+        let mut parser = Parser::new(line, line_number);
+        if parser.is_empty() {
+            continue;
+        }
+        parser.parse_line(&mut circuit)?;
+        parser.expect_end()?;
+    }
+    Ok(circuit)
+}
+
+fn print_witnesses<'a>(witnesses: impl IntoIterator<Item = &'a Witness>) -> String {
+    let witnesses: Vec<String> =
+        witnesses.into_iter().map(|witness| format!("w{}", witness.0)).collect();
+    format!("[{}]", witnesses.join(", "))
+}
+
+fn print_expression<F: AcirField>(expr: &Expression<F>) -> String {
+    let mut terms: Vec<String> = expr
+        .mul_terms
+        .iter()
+        .map(|(coefficient, lhs, rhs)| format!("{coefficient}*w{}*w{}", lhs.0, rhs.0))
+        .collect();
+    terms.extend(
+        expr.linear_combinations
+            .iter()
+            .map(|(coefficient, witness)| format!("{coefficient}*w{}", witness.0)),
+    );
+    if !expr.q_c.is_zero() {
+        terms.push(expr.q_c.to_string());
+    }
+    format!("{{{}}}", terms.join(" + "))
+}
+
+fn print_predicate<F: AcirField>(predicate: &Option<Expression<F>>) -> String {
+    match predicate {
+        Some(predicate) => format!(" if {}", print_expression(predicate)),
+        None => String::new(),
+    }
+}
+
+fn print_opcode<F: AcirField>(opcode: &Opcode<F>) -> String {
+    match opcode {
+        Opcode::AssertZero(expr) => format!("ASSERT_ZERO {}", print_expression(expr)),
+        Opcode::BlackBoxFuncCall(call) => {
+            let (name, fields) = black_box_fields(call);
+            let mut line = format!("BLACKBOX {name}");
+            for (field, value) in fields {
+                line.push_str(&format!(" {field}={}", value.print()));
+            }
+            line
+        }
+        Opcode::MemoryInit { block_id, init, block_type } => {
+            let block_type = match block_type {
+                BlockType::Memory => "memory".to_string(),
+                BlockType::CallData(id) => format!("calldata {id}"),
+                BlockType::ReturnData => "returndata".to_string(),
+            };
+            format!("MEMORY_INIT b{} {block_type} {}", block_id.0, print_witnesses(init))
+        }
+        Opcode::MemoryOp { block_id, op, predicate } => format!(
+            "MEMORY_OP b{} {} {} {}{}",
+            block_id.0,
+            print_expression(&op.operation),
+            print_expression(&op.index),
+            print_expression(&op.value),
+            print_predicate(predicate)
+        ),
+        Opcode::BrilligCall { id, inputs, outputs, predicate } => {
+            let inputs: Vec<String> = inputs
+                .iter()
+                .map(|input| match input {
+                    BrilligInputs::Single(expr) => print_expression(expr),
+                    BrilligInputs::Array(exprs) => {
+                        let exprs: Vec<String> = exprs.iter().map(print_expression).collect();
+                        format!("[{}]", exprs.join(", "))
+                    }
+                    BrilligInputs::MemoryArray(block_id) => format!("b{}", block_id.0),
+                })
+                .collect();
+            let outputs: Vec<String> = outputs
+                .iter()
+                .map(|output| match output {
+                    BrilligOutputs::Simple(witness) => format!("w{}", witness.0),
+                    BrilligOutputs::Array(witnesses) => print_witnesses(witnesses),
+                })
+                .collect();
+            format!(
+                "BRILLIG_CALL {} [{}] [{}]{}",
+                id.0,
+                inputs.join(", "),
+                outputs.join(", "),
+                print_predicate(predicate)
+            )
+        }
+        Opcode::Call { id, inputs, outputs, predicate } => format!(
+            "CALL {} {} {}{}",
+            id.0,
+            print_witnesses(inputs),
+            print_witnesses(outputs),
+            print_predicate(predicate)
+        ),
+    }
+}
+
+/// An operand of a `BLACKBOX` opcode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value<F> {
+    Witness(Witness),
+    Input(FunctionInput<F>),
+    Integer(u32),
+    List(Vec<Value<F>>),
+}
+
+impl<F: AcirField> Value<F> {
+    fn print(&self) -> String {
+        match self {
+            Value::Witness(witness) => format!("w{}", witness.0),
+            Value::Input(input) => match input.input_ref() {
+                ConstantOrWitnessEnum::Constant(constant) => {
+                    format!("{constant}:{}", input.num_bits())
+                }
+                ConstantOrWitnessEnum::Witness(witness) => {
+                    format!("w{}:{}", witness.0, input.num_bits())
+                }
+            },
+            Value::Integer(integer) => integer.to_string(),
+            Value::List(values) => {
+                let values: Vec<String> = values.iter().map(Value::print).collect();
+                format!("[{}]", values.join(", "))
+            }
+        }
+    }
+}
+
+fn inputs<F: Copy>(inputs: &[FunctionInput<F>]) -> Value<F> {
+    Value::List(inputs.iter().copied().map(Value::Input).collect())
+}
+
+fn witnesses<F>(witnesses: &[Witness]) -> Value<F> {
+    Value::List(witnesses.iter().copied().map(Value::Witness).collect())
+}
+
+/// Returns the name of the black box function called along with its named operands.
+fn black_box_fields<F: AcirField>(
+    call: &BlackBoxFuncCall<F>,
+) -> (&'static str, Vec<(&'static str, Value<F>)>) {
+    let fields = match call {
+        BlackBoxFuncCall::AES128Encrypt { inputs: call_inputs, iv, key, outputs } => vec![
+            ("inputs", inputs(call_inputs)),
+            ("iv", inputs(iv.as_slice())),
+            ("key", inputs(key.as_slice())),
+            ("outputs", witnesses(outputs)),
+        ],
+        BlackBoxFuncCall::AND { lhs, rhs, output } | BlackBoxFuncCall::XOR { lhs, rhs, output } => {
+            vec![
+                ("lhs", Value::Input(*lhs)),
+                ("rhs", Value::Input(*rhs)),
+                ("output", Value::Witness(*output)),
+            ]
+        }
+        BlackBoxFuncCall::RANGE { input } => vec![("input", Value::Input(*input))],
+        BlackBoxFuncCall::Blake2s { inputs: call_inputs, outputs }
+        | BlackBoxFuncCall::Blake3 { inputs: call_inputs, outputs } => {
+            vec![("inputs", inputs(call_inputs)), ("outputs", witnesses(outputs.as_slice()))]
+        }
+        BlackBoxFuncCall::SchnorrVerify {
+            public_key_x,
+            public_key_y,
+            signature,
+            message,
+            output,
+        } => vec![
+            ("public_key_x", Value::Input(*public_key_x)),
+            ("public_key_y", Value::Input(*public_key_y)),
+            ("signature", inputs(signature.as_slice())),
+            ("message", inputs(message)),
+            ("output", Value::Witness(*output)),
+        ],
+        BlackBoxFuncCall::EcdsaSecp256k1 {
+            public_key_x,
+            public_key_y,
+            signature,
+            hashed_message,
+            output,
+        }
+        | BlackBoxFuncCall::EcdsaSecp256r1 {
+            public_key_x,
+            public_key_y,
+            signature,
+            hashed_message,
+            output,
+        } => vec![
+            ("public_key_x", inputs(public_key_x.as_slice())),
+            ("public_key_y", inputs(public_key_y.as_slice())),
+            ("signature", inputs(signature.as_slice())),
+            ("hashed_message", inputs(hashed_message.as_slice())),
+            ("output", Value::Witness(*output)),
+        ],
+        BlackBoxFuncCall::MultiScalarMul { points, scalars, outputs } => vec![
+            ("points", inputs(points)),
+            ("scalars", inputs(scalars)),
+            ("outputs", witnesses(&[outputs.0, outputs.1, outputs.2])),
+        ],
+        BlackBoxFuncCall::EmbeddedCurveAdd { input1, input2, outputs } => vec![
+            ("input1", inputs(input1.as_slice())),
+            ("input2", inputs(input2.as_slice())),
+            ("outputs", witnesses(&[outputs.0, outputs.1, outputs.2])),
+        ],
+        BlackBoxFuncCall::Keccakf1600 { inputs: call_inputs, outputs } => vec![
+            ("inputs", inputs(call_inputs.as_slice())),
+            ("outputs", witnesses(outputs.as_slice())),
+        ],
+        BlackBoxFuncCall::RecursiveAggregation {
+            verification_key,
+            proof,
+            public_inputs,
+            key_hash,
+            proof_type,
+        } => vec![
+            ("verification_key", inputs(verification_key)),
+            ("proof", inputs(proof)),
+            ("public_inputs", inputs(public_inputs)),
+            ("key_hash", Value::Input(*key_hash)),
+            ("proof_type", Value::Integer(*proof_type)),
+        ],
+        BlackBoxFuncCall::BigIntAdd { lhs, rhs, output }
+        | BlackBoxFuncCall::BigIntSub { lhs, rhs, output }
+        | BlackBoxFuncCall::BigIntMul { lhs, rhs, output }
+        | BlackBoxFuncCall::BigIntDiv { lhs, rhs, output } => vec![
+            ("lhs", Value::Integer(*lhs)),
+            ("rhs", Value::Integer(*rhs)),
+            ("output", Value::Integer(*output)),
+        ],
+        BlackBoxFuncCall::BigIntFromLeBytes { inputs: call_inputs, modulus, output } => vec![
+            ("inputs", inputs(call_inputs)),
+            (
+                "modulus",
+                Value::List(modulus.iter().map(|byte| Value::Integer(u32::from(*byte))).collect()),
+            ),
+            ("output", Value::Integer(*output)),
+        ],
+        BlackBoxFuncCall::BigIntToLeBytes { input, outputs } => {
+            vec![("input", Value::Integer(*input)), ("outputs", witnesses(outputs))]
+        }
+        BlackBoxFuncCall::Poseidon2Permutation { inputs: call_inputs, outputs, len } => vec![
+            ("inputs", inputs(call_inputs)),
+            ("outputs", witnesses(outputs)),
+            ("len", Value::Integer(*len)),
+        ],
+        BlackBoxFuncCall::Sha256Compression { inputs: call_inputs, hash_values, outputs } => vec![
+            ("inputs", inputs(call_inputs.as_slice())),
+            ("hash_values", inputs(hash_values.as_slice())),
+            ("outputs", witnesses(outputs.as_slice())),
+        ],
+    };
+    (call.get_black_box_func().name(), fields)
+}
+
+/// The named operands of a `BLACKBOX` opcode, which are taken as the black box call is rebuilt.
+struct Fields<F> {
+    values: HashMap<String, Value<F>>,
+    line: usize,
+}
+
+impl<F: AcirField> Fields<F> {
+    fn error(&self, message: String) -> AsmParseError {
+        AsmParseError { line: self.line, message }
+    }
+
+    fn take(&mut self, name: &str) -> Result<Value<F>, AsmParseError> {
+        self.values.remove(name).ok_or_else(|| self.error(format!("missing operand `{name}`")))
+    }
+
+    fn input(&mut self, name: &str) -> Result<FunctionInput<F>, AsmParseError> {
+        match self.take(name)? {
+            Value::Input(input) => Ok(input),
+            _ => Err(self.error(format!("expected operand `{name}` to be a function input"))),
+        }
+    }
+
+    fn inputs(&mut self, name: &str) -> Result<Vec<FunctionInput<F>>, AsmParseError> {
+        let Value::List(values) = self.take(name)? else {
+            return Err(self.error(format!("expected operand `{name}` to be a list")));
+        };
+        values
+            .into_iter()
+            .map(|value| match value {
+                Value::Input(input) => Ok(input),
+                _ => Err(self.error(format!("expected operand `{name}` to hold function inputs"))),
+            })
+            .collect()
+    }
+
+    fn input_array<const N: usize>(
+        &mut self,
+        name: &str,
+    ) -> Result<Box<[FunctionInput<F>; N]>, AsmParseError> {
+        let inputs = self.inputs(name)?;
+        let length = inputs.len();
+        inputs.try_into().map_err(|_| {
+            self.error(format!("expected operand `{name}` to hold {N} inputs but found {length}"))
+        })
+    }
+
+    fn witness(&mut self, name: &str) -> Result<Witness, AsmParseError> {
+        match self.take(name)? {
+            Value::Witness(witness) => Ok(witness),
+            _ => Err(self.error(format!("expected operand `{name}` to be a witness"))),
+        }
+    }
+
+    fn witnesses(&mut self, name: &str) -> Result<Vec<Witness>, AsmParseError> {
+        let Value::List(values) = self.take(name)? else {
+            return Err(self.error(format!("expected operand `{name}` to be a list")));
+        };
+        values
+            .into_iter()
+            .map(|value| match value {
+                Value::Witness(witness) => Ok(witness),
+                _ => Err(self.error(format!("expected operand `{name}` to hold witnesses"))),
+            })
+            .collect()
+    }
+
+    fn witness_array<const N: usize>(
+        &mut self,
+        name: &str,
+    ) -> Result<Box<[Witness; N]>, AsmParseError> {
+        let witnesses = self.witnesses(name)?;
+        let length = witnesses.len();
+        witnesses.try_into().map_err(|_| {
+            self.error(format!(
+                "expected operand `{name}` to hold {N} witnesses but found {length}"
+            ))
+        })
+    }
+
+    fn witness_triple(&mut self, name: &str) -> Result<(Witness, Witness, Witness), AsmParseError> {
+        let [first, second, third] = *self.witness_array::<3>(name)?;
+        Ok((first, second, third))
+    }
+
+    fn integer(&mut self, name: &str) -> Result<u32, AsmParseError> {
+        match self.take(name)? {
+            Value::Integer(integer) => Ok(integer),
+            _ => Err(self.error(format!("expected operand `{name}` to be an integer"))),
+        }
+    }
+
+    fn bytes(&mut self, name: &str) -> Result<Vec<u8>, AsmParseError> {
+        let Value::List(values) = self.take(name)? else {
+            return Err(self.error(format!("expected operand `{name}` to be a list")));
+        };
+        values
+            .into_iter()
+            .map(|value| match value {
+                Value::Integer(integer) => u8::try_from(integer).ok(),
+                _ => None,
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(|| self.error(format!("expected operand `{name}` to hold bytes")))
+    }
+
+    /// Rebuilds the call to the black box function named `name`, erroring if any operands are left over.
+    fn into_black_box_call(mut self, name: &str) -> Result<BlackBoxFuncCall<F>, AsmParseError> {
+        let call = match name {
+            "aes128_encrypt" => BlackBoxFuncCall::AES128Encrypt {
+                inputs: self.inputs("inputs")?,
+                iv: self.input_array("iv")?,
+                key: self.input_array("key")?,
+                outputs: self.witnesses("outputs")?,
+            },
+            "and" => BlackBoxFuncCall::AND {
+                lhs: self.input("lhs")?,
+                rhs: self.input("rhs")?,
+                output: self.witness("output")?,
+            },
+            "xor" => BlackBoxFuncCall::XOR {
+                lhs: self.input("lhs")?,
+                rhs: self.input("rhs")?,
+                output: self.witness("output")?,
+            },
+            "range" => BlackBoxFuncCall::RANGE { input: self.input("input")? },
+            "blake2s" => BlackBoxFuncCall::Blake2s {
+                inputs: self.inputs("inputs")?,
+                outputs: self.witness_array("outputs")?,
+            },
+            "blake3" => BlackBoxFuncCall::Blake3 {
+                inputs: self.inputs("inputs")?,
+                outputs: self.witness_array("outputs")?,
+            },
+            "schnorr_verify" => BlackBoxFuncCall::SchnorrVerify {
+                public_key_x: self.input("public_key_x")?,
+                public_key_y: self.input("public_key_y")?,
+                signature: self.input_array("signature")?,
+                message: self.inputs("message")?,
+                output: self.witness("output")?,
+            },
+            "ecdsa_secp256k1" => BlackBoxFuncCall::EcdsaSecp256k1 {
+                public_key_x: self.input_array("public_key_x")?,
+                public_key_y: self.input_array("public_key_y")?,
+                signature: self.input_array("signature")?,
+                hashed_message: self.input_array("hashed_message")?,
+                output: self.witness("output")?,
+            },
+            "ecdsa_secp256r1" => BlackBoxFuncCall::EcdsaSecp256r1 {
+                public_key_x: self.input_array("public_key_x")?,
+                public_key_y: self.input_array("public_key_y")?,
+                signature: self.input_array("signature")?,
+                hashed_message: self.input_array("hashed_message")?,
+                output: self.witness("output")?,
+            },
+            "multi_scalar_mul" => BlackBoxFuncCall::MultiScalarMul {
+                points: self.inputs("points")?,
+                scalars: self.inputs("scalars")?,
+                outputs: self.witness_triple("outputs")?,
+            },
+            "embedded_curve_add" => BlackBoxFuncCall::EmbeddedCurveAdd {
+                input1: self.input_array("input1")?,
+                input2: self.input_array("input2")?,
+                outputs: self.witness_triple("outputs")?,
+            },
+            "keccakf1600" => BlackBoxFuncCall::Keccakf1600 {
+                inputs: self.input_array("inputs")?,
+                outputs: self.witness_array("outputs")?,
+            },
+            "recursive_aggregation" => BlackBoxFuncCall::RecursiveAggregation {
+                verification_key: self.inputs("verification_key")?,
+                proof: self.inputs("proof")?,
+                public_inputs: self.inputs("public_inputs")?,
+                key_hash: self.input("key_hash")?,
+                proof_type: self.integer("proof_type")?,
+            },
+            "bigint_add" => BlackBoxFuncCall::BigIntAdd {
+                lhs: self.integer("lhs")?,
+                rhs: self.integer("rhs")?,
+                output: self.integer("output")?,
+            },
+            "bigint_sub" => BlackBoxFuncCall::BigIntSub {
+                lhs: self.integer("lhs")?,
+                rhs: self.integer("rhs")?,
+                output: self.integer("output")?,
+            },
+            "bigint_mul" => BlackBoxFuncCall::BigIntMul {
+                lhs: self.integer("lhs")?,
+                rhs: self.integer("rhs")?,
+                output: self.integer("output")?,
+            },
+            "bigint_div" => BlackBoxFuncCall::BigIntDiv {
+                lhs: self.integer("lhs")?,
+                rhs: self.integer("rhs")?,
+                output: self.integer("output")?,
+            },
+            "bigint_from_le_bytes" => BlackBoxFuncCall::BigIntFromLeBytes {
+                inputs: self.inputs("inputs")?,
+                modulus: self.bytes("modulus")?,
+                output: self.integer("output")?,
+            },
+            "bigint_to_le_bytes" => BlackBoxFuncCall::BigIntToLeBytes {
+                input: self.integer("input")?,
+                outputs: self.witnesses("outputs")?,
+            },
+            "poseidon2_permutation" => BlackBoxFuncCall::Poseidon2Permutation {
+                inputs: self.inputs("inputs")?,
+                outputs: self.witnesses("outputs")?,
+                len: self.integer("len")?,
+            },
+            "sha256_compression" => BlackBoxFuncCall::Sha256Compression {
+                inputs: self.input_array("inputs")?,
+                hash_values: self.input_array("hash_values")?,
+                outputs: self.witness_array("outputs")?,
+            },
+            _ => return Err(self.error(format!("unknown black box function `{name}`"))),
+        };
+
+        if let Some(name) = self.values.keys().next() {
+            return Err(self.error(format!("unexpected operand `{name}`")));
+        }
+        Ok(call)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Symbol(char),
+}
+
+const SYMBOLS: &[char] = &['{', '}', '[', ']', ',', ':', '=', '*', '+'];
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    line: usize,
+}
+
+impl Parser {
+    fn new(source: &str, line: usize) -> Self {
+        let mut tokens = Vec::new();
+        let mut word = String::new();
+        for character in source.chars() {
+            if character.is_whitespace() || SYMBOLS.contains(&character) {
+                if !word.is_empty() {
+                    tokens.push(Token::Word(std::mem::take(&mut word)));
+                }
+                if !character.is_whitespace() {
+                    tokens.push(Token::Symbol(character));
+                }
+            } else {
+                word.push(character);
+            }
+        }
+        if !word.is_empty() {
+            tokens.push(Token::Word(word));
+        }
+        Parser { tokens, position: 0, line }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn error(&self, message: impl Into<String>) -> AsmParseError {
+        AsmParseError { line: self.line, message: message.into() }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_symbol(&self, symbol: char) -> bool {
+        self.peek() == Some(&Token::Symbol(symbol))
+    }
+
+    fn eat_symbol(&mut self, symbol: char) -> bool {
+        let found = self.peek_symbol(symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), AsmParseError> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{symbol}`")))
+        }
+    }
+
+    fn word(&mut self) -> Result<String, AsmParseError> {
+        match self.tokens.get(self.position) {
+            Some(Token::Word(word)) => {
+                self.position += 1;
+                Ok(word.clone())
+            }
+            _ => Err(self.error("expected a word")),
+        }
+    }
+
+    fn peek_word(&self) -> Option<&str> {
+        match self.peek() {
+            Some(Token::Word(word)) => Some(word),
+            _ => None,
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), AsmParseError> {
+        if self.position == self.tokens.len() {
+            Ok(())
+        } else {
+            Err(self.error("unexpected trailing input"))
+        }
+    }
+
+    fn number<T: std::str::FromStr>(&mut self) -> Result<T, AsmParseError> {
+        let word = self.word()?;
+        word.parse().map_err(|_| self.error(format!("expected a number but found `{word}`")))
+    }
+
+    fn prefixed_index(&mut self, prefix: char) -> Result<u32, AsmParseError> {
+        let word = self.word()?;
+        word.strip_prefix(prefix)
+            .and_then(|index| index.parse().ok())
+            .ok_or_else(|| self.error(format!("expected `{prefix}<index>` but found `{word}`")))
+    }
+
+    fn witness(&mut self) -> Result<Witness, AsmParseError> {
+        self.prefixed_index('w').map(Witness)
+    }
+
+    fn block(&mut self) -> Result<BlockId, AsmParseError> {
+        self.prefixed_index('b').map(BlockId)
+    }
+
+    /// Parses a comma separated list enclosed in square brackets.
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, AsmParseError>,
+    ) -> Result<Vec<T>, AsmParseError> {
+        self.expect_symbol('[')?;
+        let mut items = Vec::new();
+        if self.eat_symbol(']') {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.eat_symbol(']') {
+                return Ok(items);
+            }
+            self.expect_symbol(',')?;
+        }
+    }
+
+    fn witnesses(&mut self) -> Result<Vec<Witness>, AsmParseError> {
+        self.list(Self::witness)
+    }
+
+    fn field<F: AcirField>(&mut self) -> Result<F, AsmParseError> {
+        let word = self.word()?;
+        parse_field(&word).ok_or_else(|| self.error(format!("invalid field element `{word}`")))
+    }
+
+    fn expression<F: AcirField>(&mut self) -> Result<Expression<F>, AsmParseError> {
+        self.expect_symbol('{')?;
+        let mut expr = Expression::default();
+        let mut has_constant = false;
+        if self.eat_symbol('}') {
+            return Ok(expr);
+        }
+        loop {
+            let coefficient = self.field()?;
+            if self.eat_symbol('*') {
+                let lhs = self.witness()?;
+                if self.eat_symbol('*') {
+                    let rhs = self.witness()?;
+                    expr.mul_terms.push((coefficient, lhs, rhs));
+                } else {
+                    expr.linear_combinations.push((coefficient, lhs));
+                }
+            } else if has_constant {
+                return Err(self.error("expression has more than one constant term"));
+            } else {
+                expr.q_c = coefficient;
+                has_constant = true;
+            }
+
+            if self.eat_symbol('}') {
+                return Ok(expr);
+            }
+            self.expect_symbol('+')?;
+        }
+    }
+
+    fn predicate<F: AcirField>(&mut self) -> Result<Option<Expression<F>>, AsmParseError> {
+        if self.peek_word() == Some("if") {
+            self.position += 1;
+            self.expression().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn value<F: AcirField>(&mut self) -> Result<Value<F>, AsmParseError> {
+        if self.peek_symbol('[') {
+            return self.list(Self::value).map(Value::List);
+        }
+
+        let word = self.word()?;
+        if self.eat_symbol(':') {
+            let num_bits = self.number()?;
+            let input = match word.strip_prefix('w').and_then(|index| index.parse().ok()) {
+                Some(index) => FunctionInput::witness(Witness(index), num_bits),
+                None => {
+                    let constant = parse_field(&word)
+                        .ok_or_else(|| self.error(format!("invalid function input `{word}`")))?;
+                    FunctionInput::constant(constant, num_bits)
+                        .map_err(|err| self.error(err.to_string()))?
+                }
+            };
+            return Ok(Value::Input(input));
+        }
+
+        if let Some(index) = word.strip_prefix('w').and_then(|index| index.parse().ok()) {
+            Ok(Value::Witness(Witness(index)))
+        } else {
+            word.parse()
+                .map(Value::Integer)
+                .map_err(|_| self.error(format!("invalid operand `{word}`")))
+        }
+    }
+
+    fn parse_line<F: AcirField>(&mut self, circuit: &mut Circuit<F>) -> Result<(), AsmParseError> {
+        let keyword = self.word()?;
+        match keyword.as_str() {
+            "current_witness_index" => circuit.current_witness_index = self.number()?,
+            "expression_width" => {
+                circuit.expression_width = if self.peek_word() == Some("unbounded") {
+                    self.position += 1;
+                    ExpressionWidth::Unbounded
+                } else {
+                    ExpressionWidth::Bounded { width: self.number()? }
+                };
+            }
+            "private_parameters" => {
+                circuit.private_parameters = self.witnesses()?.into_iter().collect();
+            }
+            "public_parameters" => {
+                circuit.public_parameters =
+                    PublicInputs(self.witnesses()?.into_iter().collect::<BTreeSet<_>>());
+            }
+            "return_values" => {
+                circuit.return_values =
+                    PublicInputs(self.witnesses()?.into_iter().collect::<BTreeSet<_>>());
+            }
+            "assert" => {
+                let location = self.word()?;
+                let location: OpcodeLocation = location
+                    .parse()
+                    .map_err(|_| self.error(format!("invalid opcode location `{location}`")))?;
+                let error_selector = self.number()?;
+                let payload = self.list(|parser| {
+                    if parser.peek_symbol('{') {
+                        parser.expression().map(ExpressionOrMemory::Expression)
+                    } else {
+                        parser.block().map(ExpressionOrMemory::Memory)
+                    }
+                })?;
+                circuit
+                    .assert_messages
+                    .push((location, AssertionPayload { error_selector, payload }));
+            }
+            _ => {
+                let opcode = self.opcode(&keyword)?;
+                circuit.opcodes.push(opcode);
+            }
+        }
+        Ok(())
+    }
+
+    fn opcode<F: AcirField>(&mut self, mnemonic: &str) -> Result<Opcode<F>, AsmParseError> {
+        let opcode = match mnemonic {
+            "ASSERT_ZERO" => Opcode::AssertZero(self.expression()?),
+            "BLACKBOX" => {
+                let name = self.word()?;
+                let mut values = HashMap::new();
+                while self.peek().is_some() {
+                    let field = self.word()?;
+                    self.expect_symbol('=')?;
+                    let value = self.value()?;
+                    if values.insert(field.clone(), value).is_some() {
+                        return Err(self.error(format!("duplicate operand `{field}`")));
+                    }
+                }
+                Fields { values, line: self.line }.into_black_box_call(&name)?
+            }
+            "MEMORY_INIT" => {
+                let block_id = self.block()?;
+                let block_type = match self.word()?.as_str() {
+                    "memory" => BlockType::Memory,
+                    "calldata" => BlockType::CallData(self.number()?),
+                    "returndata" => BlockType::ReturnData,
+                    other => return Err(self.error(format!("unknown block type `{other}`"))),
+                };
+                let init = self.witnesses()?;
+                Opcode::MemoryInit { block_id, init, block_type }
+            }
+            "MEMORY_OP" => {
+                let block_id = self.block()?;
+                let op = MemOp {
+                    operation: self.expression()?,
+                    index: self.expression()?,
+                    value: self.expression()?,
+                };
+                Opcode::MemoryOp { block_id, op, predicate: self.predicate()? }
+            }
+            "BRILLIG_CALL" => {
+                let id = BrilligFunctionId(self.number()?);
+                let inputs = self.list(|parser| {
+                    if parser.peek_symbol('{') {
+                        parser.expression().map(BrilligInputs::Single)
+                    } else if parser.peek_symbol('[') {
+                        parser.list(Self::expression).map(BrilligInputs::Array)
+                    } else {
+                        parser.block().map(BrilligInputs::MemoryArray)
+                    }
+                })?;
+                let outputs = self.list(|parser| {
+                    if parser.peek_symbol('[') {
+                        parser.witnesses().map(BrilligOutputs::Array)
+                    } else {
+                        parser.witness().map(BrilligOutputs::Simple)
+                    }
+                })?;
+                Opcode::BrilligCall { id, inputs, outputs, predicate: self.predicate()? }
+            }
+            "CALL" => {
+                let id = AcirFunctionId(self.number()?);
+                let inputs = self.witnesses()?;
+                let outputs = self.witnesses()?;
+                Opcode::Call { id, inputs, outputs, predicate: self.predicate()? }
+            }
+            _ => return Err(self.error(format!("unknown opcode `{mnemonic}`"))),
+        };
+        Ok(opcode)
+    }
+}
+
+/// Parses a field element written in decimal, optionally negated, or in hexadecimal with a `0x` prefix.
+fn parse_field<F: AcirField>(word: &str) -> Option<F> {
+    if word.starts_with("0x") {
+        return F::from_hex(word);
+    }
+
+    let (negative, digits) = match word.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, word),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    let ten = F::from(10_u128);
+    let mut value = F::zero();
+    for digit in digits.chars() {
+        value = value * ten + F::from(u128::from(digit.to_digit(10)?));
+    }
+    Some(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::{AcirField, FieldElement};
+
+    use super::{parse_circuit, print_circuit};
+    use crate::circuit::brillig::{BrilligFunctionId, BrilligInputs, BrilligOutputs};
+    use crate::circuit::opcodes::{
+        AcirFunctionId, BlackBoxFuncCall, BlockId, BlockType, FunctionInput, MemOp,
+    };
+    use crate::circuit::{
+        AssertionPayload, Circuit, ExpressionOrMemory, ExpressionWidth, Opcode, OpcodeLocation,
+        PublicInputs,
+    };
+    use crate::native_types::{Expression, Witness};
+
+    fn example_circuit() -> Circuit<FieldElement> {
+        let expression = Expression {
+            mul_terms: vec![(FieldElement::from(2_u128), Witness(1), Witness(2))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+            q_c: FieldElement::from(5_u128),
+        };
+        let opcodes = vec![
+            Opcode::AssertZero(expression.clone()),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
+                lhs: FunctionInput::witness(Witness(1), 8),
+                rhs: FunctionInput::constant(FieldElement::from(3_u128), 8).unwrap(),
+                output: Witness(4),
+            }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::MultiScalarMul {
+                points: vec![FunctionInput::witness(Witness(1), 254); 3],
+                scalars: vec![FunctionInput::witness(Witness(2), 254); 2],
+                outputs: (Witness(5), Witness(6), Witness(7)),
+            }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::BigIntFromLeBytes {
+                inputs: vec![FunctionInput::witness(Witness(1), 8)],
+                modulus: vec![1, 255],
+                output: 0,
+            }),
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Keccakf1600 {
+                inputs: Box::new([FunctionInput::witness(Witness(1), 64); 25]),
+                outputs: Box::new([Witness(8); 25]),
+            }),
+            Opcode::MemoryInit {
+                block_id: BlockId(0),
+                init: vec![Witness(1), Witness(2)],
+                block_type: BlockType::CallData(1),
+            },
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp::read_at_mem_index(Witness(1).into(), Witness(9)),
+                predicate: Some(Expression::one()),
+            },
+            Opcode::BrilligCall {
+                id: BrilligFunctionId(0),
+                inputs: vec![
+                    BrilligInputs::Single(expression),
+                    BrilligInputs::Array(vec![Witness(1).into(), Expression::zero()]),
+                    BrilligInputs::MemoryArray(BlockId(0)),
+                ],
+                outputs: vec![
+                    BrilligOutputs::Simple(Witness(10)),
+                    BrilligOutputs::Array(vec![Witness(11), Witness(12)]),
+                ],
+                predicate: None,
+            },
+            Opcode::Call {
+                id: AcirFunctionId(1),
+                inputs: vec![Witness(1)],
+                outputs: vec![],
+                predicate: Some(Witness(2).into()),
+            },
+        ];
+
+        Circuit {
+            current_witness_index: 12,
+            opcodes,
+            expression_width: ExpressionWidth::Bounded { width: 4 },
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            public_parameters: PublicInputs(BTreeSet::from([Witness(3)])),
+            return_values: PublicInputs(BTreeSet::from([Witness(4)])),
+            assert_messages: vec![(
+                OpcodeLocation::Brillig { acir_index: 7, brillig_index: 2 },
+                AssertionPayload {
+                    error_selector: 42,
+                    payload: vec![
+                        ExpressionOrMemory::Expression(Witness(1).into()),
+                        ExpressionOrMemory::Memory(BlockId(0)),
+                    ],
+                },
+            )],
+        }
+    }
+
+    #[test]
+    fn circuit_round_trips_through_asm() {
+        let circuit = example_circuit();
+        let asm = print_circuit(&circuit);
+        let parsed: Circuit<FieldElement> = parse_circuit(&asm).unwrap();
+        assert_eq!(parsed, circuit);
+        assert_eq!(print_circuit(&parsed), asm);
+    }
+
+    #[test]
+    fn parses_handwritten_asm() {
+        let asm = "
+            # Constrains w3 to be the product of w1 and w2
+            current_witness_index 3
+            private_parameters [w1, w2]
+            return_values [w3]
+            ASSERT_ZERO {1*w1*w2 + -1*w3}
+            BLACKBOX range input=w3:32
+        ";
+        let circuit: Circuit<FieldElement> = parse_circuit(asm).unwrap();
+
+        assert_eq!(circuit.current_witness_index, 3);
+        assert_eq!(circuit.expression_width, ExpressionWidth::Unbounded);
+        assert_eq!(circuit.opcodes.len(), 2);
+        assert_eq!(
+            circuit.opcodes[1],
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: FunctionInput::witness(Witness(3), 32)
+            })
+        );
+    }
+
+    #[test]
+    fn reports_line_of_parse_errors() {
+        let asm = "current_witness_index 1\nBLACKBOX and lhs=w1:8 output=w2\n";
+        let error = parse_circuit::<FieldElement>(asm).unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "missing operand `rhs`");
+    }
+}
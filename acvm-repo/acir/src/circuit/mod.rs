@@ -1,3 +1,4 @@
+pub mod asm;
 pub mod black_box_functions;
 pub mod brillig;
 pub mod opcodes;
@@ -7,11 +7,13 @@ use acir::{
 
 // The various passes that we can use over ACIR
 mod optimizers;
+mod profile;
 mod simulator;
 mod transformers;
 
 pub use optimizers::optimize;
 use optimizers::optimize_internal;
+pub use profile::{OptimizationProfile, PassProfile};
 pub use simulator::CircuitSimulator;
 use transformers::transform_internal;
 pub use transformers::{transform, MIN_EXPRESSION_WIDTH};
@@ -71,15 +73,76 @@ fn transform_assert_messages<F: Clone>(
         .collect()
 }
 
+/// How much effort [`compile_with_optimization_level`] spends optimizing a [`Circuit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OptimizationLevel {
+    /// Only split expressions so that they fit the target expression width.
+    None = 0,
+    /// Additionally run the general, unused memory and redundant range constraint optimizers.
+    Basic = 1,
+    /// Additionally merge intermediate variables back into the expressions which use them.
+    #[default]
+    Full = 2,
+}
+
+impl OptimizationLevel {
+    pub fn from_u8(level: u8) -> Option<Self> {
+        match level {
+            0 => Some(OptimizationLevel::None),
+            1 => Some(OptimizationLevel::Basic),
+            2 => Some(OptimizationLevel::Full),
+            _ => None,
+        }
+    }
+}
+
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`].
 pub fn compile<F: AcirField>(
     acir: Circuit<F>,
     expression_width: ExpressionWidth,
 ) -> (Circuit<F>, AcirTransformationMap) {
-    let (acir, acir_opcode_positions) = optimize_internal(acir);
+    compile_with_optimization_level(acir, expression_width, OptimizationLevel::Full)
+}
+
+/// Same as [`compile`], but only applies the optimizations enabled at the given [`OptimizationLevel`].
+pub fn compile_with_optimization_level<F: AcirField>(
+    acir: Circuit<F>,
+    expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+) -> (Circuit<F>, AcirTransformationMap) {
+    compile_internal(acir, expression_width, optimization_level, None)
+}
+
+/// Same as [`compile_with_optimization_level`], but records the time spent in each pass in `profile`.
+pub fn compile_with_profile<F: AcirField>(
+    acir: Circuit<F>,
+    expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+    profile: &mut OptimizationProfile,
+) -> (Circuit<F>, AcirTransformationMap) {
+    compile_internal(acir, expression_width, optimization_level, Some(profile))
+}
+
+fn compile_internal<F: AcirField>(
+    acir: Circuit<F>,
+    expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+    mut profile: Option<&mut OptimizationProfile>,
+) -> (Circuit<F>, AcirTransformationMap) {
+    let (acir, acir_opcode_positions) = if optimization_level == OptimizationLevel::None {
+        let acir_opcode_positions = acir.opcodes.iter().enumerate().map(|(i, _)| i).collect();
+        (acir, acir_opcode_positions)
+    } else {
+        optimize_internal(acir, profile.as_deref_mut())
+    };
 
-    let (mut acir, acir_opcode_positions) =
-        transform_internal(acir, expression_width, acir_opcode_positions);
+    let (mut acir, acir_opcode_positions) = transform_internal(
+        acir,
+        expression_width,
+        acir_opcode_positions,
+        optimization_level == OptimizationLevel::Full,
+        profile,
+    );
 
     let transformation_map = AcirTransformationMap::new(acir_opcode_positions);
 
@@ -17,11 +17,11 @@ use tracing::info;
 // use self::constant_backpropagation::ConstantBackpropagationOptimizer;
 use self::unused_memory::UnusedMemoryOptimizer;
 
-use super::{transform_assert_messages, AcirTransformationMap};
+use super::{transform_assert_messages, AcirTransformationMap, OptimizationProfile};
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] independent optimizations to a [`Circuit`].
 pub fn optimize<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>, AcirTransformationMap) {
-    let (mut acir, new_opcode_positions) = optimize_internal(acir);
+    let (mut acir, new_opcode_positions) = optimize_internal(acir, None);
 
     let transformation_map = AcirTransformationMap::new(new_opcode_positions);
 
@@ -31,8 +31,13 @@ pub fn optimize<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>, AcirTransformati
 }
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] independent optimizations to a [`Circuit`].
-#[tracing::instrument(level = "trace", name = "optimize_acir" skip(acir))]
-pub(super) fn optimize_internal<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>, Vec<usize>) {
+///
+/// Each pass is recorded in `profile`, if one is given.
+#[tracing::instrument(level = "trace", name = "optimize_acir" skip(acir, profile))]
+pub(super) fn optimize_internal<F: AcirField>(
+    acir: Circuit<F>,
+    mut profile: Option<&mut OptimizationProfile>,
+) -> (Circuit<F>, Vec<usize>) {
     // Track original acir opcode positions throughout the transformation passes of the compilation
     // by applying the modifications done to the circuit opcodes and also to the opcode_positions (delete and insert)
     let acir_opcode_positions = (0..acir.opcodes.len()).collect();
@@ -45,6 +50,9 @@ pub(super) fn optimize_internal<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>,
     info!("Number of opcodes before: {}", acir.opcodes.len());
 
     // General optimizer pass
+    if let Some(profile) = &mut profile {
+        profile.start_pass("general", &acir.opcodes);
+    }
     let opcodes: Vec<Opcode<F>> = acir
         .opcodes
         .into_iter()
@@ -57,19 +65,34 @@ pub(super) fn optimize_internal<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>,
         })
         .collect();
     let acir = Circuit { opcodes, ..acir };
+    if let Some(profile) = &mut profile {
+        profile.finish_pass(&acir.opcodes);
+    }
 
     // Unused memory optimization pass
+    if let Some(profile) = &mut profile {
+        profile.start_pass("unused_memory", &acir.opcodes);
+    }
     let memory_optimizer = UnusedMemoryOptimizer::new(acir);
     let (acir, acir_opcode_positions) =
         memory_optimizer.remove_unused_memory_initializations(acir_opcode_positions);
+    if let Some(profile) = &mut profile {
+        profile.finish_pass(&acir.opcodes);
+    }
 
     // let (acir, acir_opcode_positions) =
     // ConstantBackpropagationOptimizer::backpropagate_constants(acir, acir_opcode_positions);
 
     // Range optimization pass
+    if let Some(profile) = &mut profile {
+        profile.start_pass("redundant_range", &acir.opcodes);
+    }
     let range_optimizer = RangeOptimizer::new(acir);
     let (acir, acir_opcode_positions) =
         range_optimizer.replace_redundant_ranges(acir_opcode_positions);
+    if let Some(profile) = &mut profile {
+        profile.finish_pass(&acir.opcodes);
+    }
 
     // let (acir, acir_opcode_positions) =
     // ConstantBackpropagationOptimizer::backpropagate_constants(acir, acir_opcode_positions);
@@ -0,0 +1,101 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use acir::circuit::Opcode;
+
+/// The time spent in each pass of [`compile_with_profile`][super::compile_with_profile],
+/// along with how the pass changed the number of opcodes of each kind.
+///
+/// Passes are aggregated by name, so compiling several circuits with the same profile sums their passes.
+#[derive(Debug, Default, Clone)]
+pub struct OptimizationProfile {
+    passes: Vec<PassProfile>,
+    pending: Option<(&'static str, Instant, BTreeMap<&'static str, usize>)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PassProfile {
+    pub name: &'static str,
+    pub duration: Duration,
+    /// The number of opcodes of each kind before the pass ran.
+    pub opcodes_before: BTreeMap<&'static str, usize>,
+    /// The number of opcodes of each kind after the pass ran.
+    pub opcodes_after: BTreeMap<&'static str, usize>,
+}
+
+impl OptimizationProfile {
+    pub fn passes(&self) -> &[PassProfile] {
+        &self.passes
+    }
+
+    pub(super) fn start_pass<F>(&mut self, name: &'static str, opcodes: &[Opcode<F>]) {
+        self.pending = Some((name, Instant::now(), count_opcodes(opcodes)));
+    }
+
+    pub(super) fn finish_pass<F>(&mut self, opcodes: &[Opcode<F>]) {
+        let Some((name, start, opcodes_before)) = self.pending.take() else {
+            return;
+        };
+        let duration = start.elapsed();
+        let opcodes_after = count_opcodes(opcodes);
+
+        match self.passes.iter_mut().find(|pass| pass.name == name) {
+            Some(pass) => {
+                pass.duration += duration;
+                add_counts(&mut pass.opcodes_before, opcodes_before);
+                add_counts(&mut pass.opcodes_after, opcodes_after);
+            }
+            None => {
+                self.passes.push(PassProfile { name, duration, opcodes_before, opcodes_after });
+            }
+        }
+    }
+}
+
+impl fmt::Display for OptimizationProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pass in &self.passes {
+            write!(f, "{:<20} {:>12.3?}", pass.name, pass.duration)?;
+
+            let kinds: BTreeSet<&str> =
+                pass.opcodes_before.keys().chain(pass.opcodes_after.keys()).copied().collect();
+            let mut changes = Vec::new();
+            for kind in kinds {
+                let before = pass.opcodes_before.get(kind).copied().unwrap_or_default();
+                let after = pass.opcodes_after.get(kind).copied().unwrap_or_default();
+                if before != after {
+                    changes.push(format!("{kind}: {before} -> {after}"));
+                }
+            }
+            if changes.is_empty() {
+                writeln!(f, "  no opcodes changed")?;
+            } else {
+                writeln!(f, "  {}", changes.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn count_opcodes<F>(opcodes: &[Opcode<F>]) -> BTreeMap<&'static str, usize> {
+    let mut counts = BTreeMap::new();
+    for opcode in opcodes {
+        let kind = match opcode {
+            Opcode::AssertZero(_) => "AssertZero",
+            Opcode::BlackBoxFuncCall(_) => "BlackBoxFuncCall",
+            Opcode::MemoryOp { .. } => "MemoryOp",
+            Opcode::MemoryInit { .. } => "MemoryInit",
+            Opcode::BrilligCall { .. } => "BrilligCall",
+            Opcode::Call { .. } => "Call",
+        };
+        *counts.entry(kind).or_default() += 1;
+    }
+    counts
+}
+
+fn add_counts(totals: &mut BTreeMap<&'static str, usize>, counts: BTreeMap<&'static str, usize>) {
+    for (kind, count) in counts {
+        *totals.entry(kind).or_default() += count;
+    }
+}
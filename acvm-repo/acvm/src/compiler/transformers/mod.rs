@@ -12,6 +12,7 @@ pub use csat::MIN_EXPRESSION_WIDTH;
 
 use super::{
     optimizers::MergeExpressionsOptimizer, transform_assert_messages, AcirTransformationMap,
+    OptimizationProfile,
 };
 
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`].
@@ -24,7 +25,7 @@ pub fn transform<F: AcirField>(
     let acir_opcode_positions = acir.opcodes.iter().enumerate().map(|(i, _)| i).collect();
 
     let (mut acir, acir_opcode_positions) =
-        transform_internal(acir, expression_width, acir_opcode_positions);
+        transform_internal(acir, expression_width, acir_opcode_positions, true, None);
 
     let transformation_map = AcirTransformationMap::new(acir_opcode_positions);
 
@@ -36,11 +37,19 @@ pub fn transform<F: AcirField>(
 /// Applies [`ProofSystemCompiler`][crate::ProofSystemCompiler] specific optimizations to a [`Circuit`].
 ///
 /// Accepts an injected `acir_opcode_positions` to allow transformations to be applied directly after optimizations.
-#[tracing::instrument(level = "trace", name = "transform_acir", skip(acir, acir_opcode_positions))]
+/// Intermediate variables are only merged back into their users when `merge_expressions` is set.
+/// Each pass is recorded in `profile`, if one is given.
+#[tracing::instrument(
+    level = "trace",
+    name = "transform_acir",
+    skip(acir, acir_opcode_positions, profile)
+)]
 pub(super) fn transform_internal<F: AcirField>(
     acir: Circuit<F>,
     expression_width: ExpressionWidth,
     acir_opcode_positions: Vec<usize>,
+    merge_expressions: bool,
+    mut profile: Option<&mut OptimizationProfile>,
 ) -> (Circuit<F>, Vec<usize>) {
     let mut transformer = match &expression_width {
         ExpressionWidth::Unbounded => {
@@ -59,6 +68,10 @@ pub(super) fn transform_internal<F: AcirField>(
     // TODO it may be possible to refactor it in a way that we do not need to return early from the r1cs
     // TODO or at the very least, we could put all of it inside of CSatOptimizer pass
 
+    if let Some(profile) = &mut profile {
+        profile.start_pass("csat", &acir.opcodes);
+    }
+
     let mut new_acir_opcode_positions: Vec<usize> = Vec::with_capacity(acir_opcode_positions.len());
     // Optimize the assert-zero gates by reducing them into the correct width and
     // creating intermediate variables when necessary
@@ -157,6 +170,16 @@ pub(super) fn transform_internal<F: AcirField>(
         // The transformer does not add new public inputs
         ..acir
     };
+    if let Some(profile) = &mut profile {
+        profile.finish_pass(&acir.opcodes);
+    }
+    if !merge_expressions {
+        return (acir, new_acir_opcode_positions);
+    }
+
+    if let Some(profile) = &mut profile {
+        profile.start_pass("merge_expressions", &acir.opcodes);
+    }
     let mut merge_optimizer = MergeExpressionsOptimizer::new();
     let (opcodes, new_acir_opcode_positions) =
         merge_optimizer.eliminate_intermediate_variable(&acir, new_acir_opcode_positions);
@@ -168,5 +191,8 @@ pub(super) fn transform_internal<F: AcirField>(
         // The optimizer does not add new public inputs
         ..acir
     };
+    if let Some(profile) = &mut profile {
+        profile.finish_pass(&acir.opcodes);
+    }
     (acir, new_acir_opcode_positions)
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
 use acvm::{acir::circuit::Program, FieldElement};
 use fm::FileId;
@@ -8,11 +8,12 @@ use noirc_errors::debug_info::DebugInfo;
 use noirc_evaluator::errors::SsaReport;
 
 use super::debug::DebugFile;
+use super::program::CompiledProgram;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CompiledContractOutputs {
-    pub structs: HashMap<String, Vec<AbiType>>,
-    pub globals: HashMap<String, Vec<AbiValue>>,
+    pub structs: BTreeMap<String, Vec<AbiType>>,
+    pub globals: BTreeMap<String, Vec<AbiValue>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -22,7 +23,7 @@ pub struct CompiledContract {
     /// The name of the contract.
     pub name: String,
     /// Each of the contract's functions are compiled into a separate `CompiledProgram`
-    /// stored in this `Vector`.
+    /// stored in this `Vector`, sorted by name.
     pub functions: Vec<ContractFunction>,
 
     pub outputs: CompiledContractOutputs,
@@ -41,6 +42,11 @@ pub struct CompiledContract {
 pub struct ContractFunction {
     pub name: String,
 
+    /// Hash of the monomorphized function from which this [`ContractFunction`] was compiled.
+    ///
+    /// Used to skip recompiling functions whose source code hasn't changed since the last compilation.
+    pub hash: u64,
+
     pub is_unconstrained: bool,
 
     pub custom_attributes: Vec<String>,
@@ -61,3 +67,22 @@ pub struct ContractFunction {
     /// Names of the unconstrained functions in the program.
     pub brillig_names: Vec<String>,
 }
+
+impl ContractFunction {
+    /// Returns the function as a [`CompiledProgram`] so that it can be used as a compilation cache.
+    pub(crate) fn as_cached_program(&self, noir_version: &str) -> CompiledProgram {
+        CompiledProgram {
+            noir_version: noir_version.to_string(),
+            hash: self.hash,
+            program: self.bytecode.clone(),
+            abi: self.abi.clone(),
+            debug: self.debug.clone(),
+            file_map: BTreeMap::new(),
+            warnings: Vec::new(),
+            names: self.names.clone(),
+            brillig_names: self.brillig_names.clone(),
+            monomorphized_functions: BTreeMap::new(),
+            unreachable_functions: Vec::new(),
+        }
+    }
+}
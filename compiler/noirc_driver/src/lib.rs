@@ -5,16 +5,18 @@
 
 use abi_gen::{abi_type_from_hir_type, value_from_hir_expression};
 use acvm::acir::circuit::ExpressionWidth;
-use acvm::compiler::MIN_EXPRESSION_WIDTH;
-use clap::Args;
+use acvm::compiler::{OptimizationLevel, MIN_EXPRESSION_WIDTH};
+use clap::{Args, ValueEnum};
 use fm::{FileId, FileManager};
 use iter_extended::vecmap;
-use noirc_abi::{AbiParameter, AbiType, AbiValue};
-use noirc_errors::{CustomDiagnostic, FileDiagnostic};
+use noirc_abi::profile::{abi_type_to_string, AbiProfile};
+use noirc_abi::{Abi, AbiParameter, AbiType, AbiValue};
+use noirc_errors::{CustomDiagnostic, DiagnosticKind, FileDiagnostic, LintLevel};
 use noirc_evaluator::create_program;
 use noirc_evaluator::errors::RuntimeError;
 use noirc_evaluator::ssa::{SsaLogging, SsaProgramArtifact};
 use noirc_frontend::debug::build_debug_crate_file;
+use noirc_frontend::graph::{PACKAGE_CRATE_NAME, PRELUDE_CRATE_NAME};
 use noirc_frontend::hir::def_map::{Contract, CrateDefMap};
 use noirc_frontend::hir::Context;
 use noirc_frontend::monomorphization::{
@@ -22,6 +24,7 @@ use noirc_frontend::monomorphization::{
 };
 use noirc_frontend::node_interner::FuncId;
 use noirc_frontend::token::SecondaryAttribute;
+use std::collections::BTreeMap;
 use std::path::Path;
 use tracing::info;
 
@@ -50,10 +53,14 @@ pub const NOIRC_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const NOIR_ARTIFACT_VERSION_STRING: &str =
     concat!(env!("CARGO_PKG_VERSION"), "+", env!("GIT_COMMIT"));
 
+/// Options controlling how Noir programs are compiled.
+///
+/// Some options can also be set through `NARGO_*` environment variables, e.g. `NARGO_DENY_WARNINGS=true`.
+/// Flags passed on the command line take precedence over the environment and invalid values are rejected.
 #[derive(Args, Clone, Debug, Default)]
 pub struct CompileOptions {
     /// Specify the backend expression width that should be targeted
-    #[arg(long, value_parser = parse_expression_width)]
+    #[arg(long, value_parser = parse_expression_width, env = "NARGO_EXPRESSION_WIDTH")]
     pub expression_width: Option<ExpressionWidth>,
 
     /// Generate ACIR with the target backend expression width.
@@ -62,10 +69,57 @@ pub struct CompileOptions {
     #[arg(long, default_value = "false")]
     pub bounded_codegen: bool,
 
+    /// Optimization level applied to the generated ACIR:
+    /// 0 only splits expressions to fit the expression width,
+    /// 1 also removes redundant opcodes, unused memory and redundant range constraints,
+    /// 2 also merges intermediate variables back into their expressions (default).
+    /// Can also be given as `-O0`, `-O1` or `-O2`.
+    #[arg(short = 'O', long, value_name = "LEVEL", value_parser = parse_optimization_level)]
+    pub opt_level: Option<OptimizationLevel>,
+
+    /// Print the time spent in each ACIR optimization pass and how it changed the number of opcodes of each kind.
+    /// This is a tool for developers working on the optimizer itself.
+    #[arg(long)]
+    pub profile_opt: bool,
+
     /// Force a full recompilation.
-    #[arg(long = "force")]
+    #[arg(long = "force", env = "NARGO_FORCE")]
     pub force_compile: bool,
 
+    /// Only recompile the contract functions whose source changed since the last compilation,
+    /// reusing the remaining functions from the existing contract artifact.
+    #[arg(long, conflicts_with = "force_compile")]
+    pub only_changed_functions: bool,
+
+    /// Replace the body of any function with this name by a placeholder returning a zeroed value,
+    /// so that it contributes no real constraints. Stubbed contract functions are tagged with `stub`.
+    #[arg(long = "stub", value_name = "FUNCTION")]
+    pub stub_functions: Vec<String>,
+
+    /// Compile the contract function with this name as an entry point, even if it's a `#[contract_library_method]`.
+    #[arg(long = "make-public", value_name = "FUNCTION")]
+    pub make_public_functions: Vec<String>,
+
+    /// Leave the contract function with this name out of the compiled contract, as if it were a
+    /// `#[contract_library_method]`.
+    #[arg(long = "make-internal", value_name = "FUNCTION")]
+    pub make_internal_functions: Vec<String>,
+
+    /// Compile the function with this name as the program's entry point instead of `main`,
+    /// listing the functions of the crate which are left out as unreachable from it.
+    #[arg(long, value_name = "FUNCTION")]
+    pub reachable_from: Option<String>,
+
+    /// Compile the top-level function with this name as the program's entry point instead of `main`.
+    /// This overrides any `entry-function` set in the package's manifest.
+    #[arg(long = "entry", value_name = "FUNCTION", conflicts_with = "reachable_from")]
+    pub entry_function: Option<String>,
+
+    /// Fail compilation if the ABI of a program or contract function uses a type which isn't supported
+    /// by this profile of ABI types. The only profile is currently `solidity`.
+    #[arg(long, value_name = "PROFILE")]
+    pub strict_abi: Option<AbiProfile>,
+
     /// Emit debug information for the intermediate SSA IR to stdout
     #[arg(long, hide = true)]
     pub show_ssa: bool,
@@ -85,7 +139,7 @@ pub struct CompileOptions {
     pub show_brillig: bool,
 
     /// Display the ACIR for compiled circuit
-    #[arg(long)]
+    #[arg(long, env = "NARGO_PRINT_ACIR")]
     pub print_acir: bool,
 
     /// Pretty print benchmark times of each code generation pass
@@ -93,13 +147,18 @@ pub struct CompileOptions {
     pub benchmark_codegen: bool,
 
     /// Treat all warnings as errors
-    #[arg(long, conflicts_with = "silence_warnings")]
+    #[arg(long, conflicts_with = "silence_warnings", env = "NARGO_DENY_WARNINGS")]
     pub deny_warnings: bool,
 
     /// Suppress warnings
-    #[arg(long, conflicts_with = "deny_warnings")]
+    #[arg(long, conflicts_with = "deny_warnings", env = "NARGO_SILENCE_WARNINGS")]
     pub silence_warnings: bool,
 
+    /// The format which warnings and errors are reported in.
+    /// `json` writes each diagnostic to stdout as a JSON object on its own line.
+    #[arg(long, value_enum, default_value_t)]
+    pub message_format: MessageFormat,
+
     /// Disables the builtin Aztec macros being used in the compiler
     #[arg(long, hide = true)]
     pub disable_macros: bool,
@@ -108,6 +167,31 @@ pub struct CompileOptions {
     #[arg(long, hide = true)]
     pub show_monomorphized: bool,
 
+    /// Print how many function instances monomorphization generated for each program,
+    /// along with the functions which were instantiated more than once.
+    #[arg(long)]
+    pub print_monomorphized_count: bool,
+
+    /// Print the opcode count, the opcodes of each kind and the number of public and private witnesses
+    /// of each compiled program and contract function, as they are after optimization.
+    #[arg(long)]
+    pub print_acir_stats: bool,
+
+    /// Also write the bytecode and debug symbols from before optimization to the artifact,
+    /// for debuggers which need more precise source locations than the optimized debug symbols give.
+    #[arg(long)]
+    pub keep_unoptimized_debug: bool,
+
+    /// Write source paths into artifacts relative to the package they belong to, rather than as absolute paths,
+    /// so that artifacts don't reveal or depend on where they were built.
+    #[arg(long)]
+    pub trim_paths: bool,
+
+    /// Fail compilation if optimizing a circuit leaves it with fewer assertions than it had before,
+    /// as a safety net against optimizations removing constraints the program relies on.
+    #[arg(long)]
+    pub verify_no_assertions_stripped: bool,
+
     /// Insert debug symbols to inspect variables
     #[arg(long, hide = true)]
     pub instrument_debug: bool,
@@ -125,10 +209,15 @@ pub struct CompileOptions {
     #[arg(long, hide = true)]
     pub show_artifact_paths: bool,
 
+    /// Write artifacts as pretty-printed JSON.
+    /// This makes them easier to diff in code review but noticeably larger than the compact default.
+    #[arg(long = "pretty", env = "NARGO_PRETTY_ARTIFACTS")]
+    pub pretty_artifacts: bool,
+
     /// Flag to turn off the compiler check for under constrained values.
     /// Warning: This can improve compilation speed but can also lead to correctness errors.
     /// This check should always be run on production code.
-    #[arg(long)]
+    #[arg(long, env = "NARGO_SKIP_UNDERCONSTRAINED_CHECK")]
     pub skip_underconstrained_check: bool,
 
     /// Setting to decide on an inlining strategy for Brillig functions.
@@ -146,6 +235,36 @@ pub struct CompileOptions {
     pub max_bytecode_increase_percent: Option<i32>,
 }
 
+impl CompileOptions {
+    /// Whether a program must be compiled from scratch rather than reused from a previous compilation,
+    /// because the options ask for output which is only produced while compiling.
+    pub fn requires_fresh_compile(&self) -> bool {
+        self.force_compile
+            || self.profile_opt
+            || self.print_monomorphized_count
+            || self.show_monomorphized
+            || self.show_ssa
+            || self.show_ssa_pass_name.is_some()
+            || self.emit_ssa
+            || self.show_brillig
+            || self.force_brillig
+            || self.print_acir
+            || self.benchmark_codegen
+            || self.verify_no_assertions_stripped
+            || self.debug_comptime_in_file.is_some()
+    }
+}
+
+/// The format which compilation diagnostics are reported in.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Rendered for a human to read, with source snippets
+    #[default]
+    Human,
+    /// One JSON object per line, for tools to consume
+    Json,
+}
+
 pub fn parse_expression_width(input: &str) -> Result<ExpressionWidth, std::io::Error> {
     use std::io::{Error, ErrorKind};
     let width = input
@@ -162,6 +281,15 @@ pub fn parse_expression_width(input: &str) -> Result<ExpressionWidth, std::io::E
     }
 }
 
+pub fn parse_optimization_level(input: &str) -> Result<OptimizationLevel, std::io::Error> {
+    use std::io::{Error, ErrorKind};
+    input
+        .parse::<u8>()
+        .ok()
+        .and_then(OptimizationLevel::from_u8)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "has to be 0, 1 or 2"))
+}
+
 #[derive(Debug)]
 pub enum CompileError {
     MonomorphizationError(MonomorphizationError),
@@ -233,6 +361,30 @@ fn add_debug_source_to_file_manager(file_manager: &mut FileManager) {
         .add_file_with_source_canonical_path(&path_to_debug_lib_file, build_debug_crate_file());
 }
 
+/// Links the file at `prelude_path` to the crate as its custom prelude,
+/// so that its public items are imported into every module of the crate.
+pub fn link_to_prelude_crate(context: &mut Context, crate_id: CrateId, prelude_path: &Path) {
+    let prelude_crate_id = prepare_dependency(context, prelude_path);
+    add_dep(context, crate_id, prelude_crate_id, PRELUDE_CRATE_NAME.parse().unwrap());
+}
+
+/// Returns the source of a crate exposing `version` as the `PACKAGE_VERSION` global.
+pub fn build_package_crate_file(version: &str) -> String {
+    let escaped = version.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("pub global PACKAGE_VERSION: str<{}> = \"{escaped}\";\n", version.len())
+}
+
+/// Links the file at `package_crate_path`, as built by [`build_package_crate_file`], to the root crate
+/// so that `PACKAGE_VERSION` can be referenced from every module of the root crate.
+pub fn link_to_package_crate(
+    context: &mut Context,
+    root_crate_id: CrateId,
+    package_crate_path: &Path,
+) {
+    let package_crate_id = prepare_dependency(context, package_crate_path);
+    add_dep(context, root_crate_id, package_crate_id, PACKAGE_CRATE_NAME.parse().unwrap());
+}
+
 /// Adds the file from the file system at `Path` to the crate graph as a root file
 ///
 /// Note: If the stdlib dependency has not been added yet, it's added. Otherwise
@@ -313,6 +465,7 @@ pub fn check_crate(
         let diagnostic = CustomDiagnostic::from(&error);
         diagnostic.in_file(file_id)
     }));
+    let errors = apply_lint_levels(context, crate_id, errors);
 
     if has_errors(&errors, options.deny_warnings) {
         Err(errors)
@@ -342,18 +495,35 @@ pub fn compile_main(
 ) -> CompilationResult<CompiledProgram> {
     let (_, mut warnings) = check_crate(context, crate_id, options)?;
 
-    let main = context.get_main_function(&crate_id).ok_or_else(|| {
-        // TODO(#2155): This error might be a better to exist in Nargo
-        let err = CustomDiagnostic::from_message(
-            "cannot compile crate into a program as it does not contain a `main` function",
-        )
-        .in_file(FileId::default());
-        vec![err]
-    })?;
+    let mut unreachable_functions = Vec::new();
+    let main = if let Some(entry_name) = &options.reachable_from {
+        let entry = find_crate_function(context, crate_id, entry_name).ok_or_else(|| {
+            let err = CustomDiagnostic::from_message(&format!(
+                "cannot compile from `{entry_name}` as the crate does not contain a function with that name"
+            ))
+            .in_file(FileId::default());
+            vec![err]
+        })?;
+        check_entry_signature(context, entry, entry_name)?;
+        unreachable_functions = find_unreachable_functions(context, crate_id, entry);
+        entry
+    } else if let Some(entry_name) = &options.entry_function {
+        find_entry_function(context, crate_id, entry_name)?
+    } else {
+        context.get_main_function(&crate_id).ok_or_else(|| {
+            // TODO(#2155): This error might be a better to exist in Nargo
+            let err = CustomDiagnostic::from_message(
+                "cannot compile crate into a program as it does not contain a `main` function",
+            )
+            .in_file(FileId::default());
+            vec![err]
+        })?
+    };
 
-    let compiled_program =
+    let mut compiled_program =
         compile_no_check(context, options, main, cached_program, options.force_compile)
             .map_err(FileDiagnostic::from)?;
+    compiled_program.unreachable_functions = unreachable_functions;
 
     let compilation_warnings = vecmap(compiled_program.warnings.clone(), FileDiagnostic::from);
     if options.deny_warnings && !compilation_warnings.is_empty() {
@@ -361,6 +531,14 @@ pub fn compile_main(
     }
     warnings.extend(compilation_warnings);
 
+    if let Some(profile) = options.strict_abi {
+        let errors =
+            check_abi_profile(&compiled_program.abi, profile, context.function_name(&main));
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+    }
+
     if options.print_acir {
         println!("Compiled ACIR for main (unoptimized):");
         println!("{}", compiled_program.program);
@@ -370,10 +548,14 @@ pub fn compile_main(
 }
 
 /// Run the frontend to check the crate for errors then compile all contracts if there were none
+///
+/// If a `cached_contract` is provided then any of its functions which haven't changed are reused
+/// rather than being recompiled.
 pub fn compile_contract(
     context: &mut Context,
     crate_id: CrateId,
     options: &CompileOptions,
+    cached_contract: Option<CompiledContract>,
 ) -> CompilationResult<CompiledContract> {
     let (_, warnings) = check_crate(context, crate_id, options)?;
 
@@ -396,7 +578,7 @@ pub fn compile_contract(
     };
 
     for contract in contracts {
-        match compile_contract_inner(context, contract, options) {
+        match compile_contract_inner(context, contract, options, cached_contract.as_ref()) {
             Ok(contract) => compiled_contracts.push(contract),
             Err(mut more_errors) => errors.append(&mut more_errors),
         }
@@ -422,6 +604,163 @@ pub fn compile_contract(
     }
 }
 
+/// Returns the functions declared directly in the modules of the crate.
+fn crate_functions(context: &Context, crate_id: CrateId) -> impl Iterator<Item = FuncId> + '_ {
+    let def_map = context.def_map(&crate_id).expect("The local crate should be analyzed already");
+    def_map
+        .modules()
+        .iter()
+        .flat_map(|(_, module)| module.value_definitions())
+        .filter_map(|definition| definition.as_function())
+}
+
+/// Finds a function of the crate by either its name or its fully qualified name.
+fn find_crate_function(context: &Context, crate_id: CrateId, name: &str) -> Option<FuncId> {
+    crate_functions(context, crate_id).find(|func_id| {
+        context.function_name(func_id) == name
+            || context.fully_qualified_function_name(&crate_id, func_id) == name
+    })
+}
+
+/// Finds the top-level function with this name to compile in place of `main`,
+/// checking that its signature is one which `main` could have.
+fn find_entry_function(
+    context: &Context,
+    crate_id: CrateId,
+    name: &str,
+) -> Result<FuncId, Vec<FileDiagnostic>> {
+    let def_map = context.def_map(&crate_id).expect("The local crate should be analyzed already");
+    let root_functions: Vec<FuncId> = def_map[def_map.root()]
+        .value_definitions()
+        .filter_map(|definition| definition.as_function())
+        .collect();
+
+    let Some(entry) =
+        root_functions.iter().copied().find(|func_id| context.function_name(func_id) == name)
+    else {
+        let mut available =
+            vecmap(&root_functions, |func_id| context.function_name(func_id).to_owned());
+        available.sort();
+        let available = if available.is_empty() {
+            "the crate has no top-level functions".to_string()
+        } else {
+            format!("available top-level functions are: {}", available.join(", "))
+        };
+        let err = CustomDiagnostic::from_message(&format!(
+            "cannot compile `{name}` as the entry point as the crate does not contain a top-level function with that name, {available}"
+        ))
+        .in_file(FileId::default());
+        return Err(vec![err]);
+    };
+
+    check_entry_signature(context, entry, name)?;
+    Ok(entry)
+}
+
+/// Checks that the signature of `entry` is one which `main` could have, so that it can be compiled in its place.
+fn check_entry_signature(
+    context: &Context,
+    entry: FuncId,
+    name: &str,
+) -> Result<(), Vec<FileDiagnostic>> {
+    let meta = context.def_interner.function_meta(&entry);
+    let has_valid_signature = meta.all_generics.is_empty()
+        && meta.parameters.0.iter().all(|(_, typ, _)| typ.is_valid_for_program_input())
+        && meta.return_type().is_valid_for_program_input();
+    if !has_valid_signature {
+        let err = CustomDiagnostic::simple_error(
+            format!("cannot compile `{name}` as the entry point as its signature isn't valid for `main`"),
+            "entry points can't be generic and their parameters and return value must be valid program inputs"
+                .to_string(),
+            meta.location.span,
+        )
+        .in_file(meta.location.file);
+        return Err(vec![err]);
+    }
+
+    Ok(())
+}
+
+/// Returns the fully qualified names of the functions of the crate which can't be reached from `entry`,
+/// and so won't be compiled.
+///
+/// Methods are left out, as calls to trait methods can't be resolved until monomorphization.
+fn find_unreachable_functions(context: &Context, crate_id: CrateId, entry: FuncId) -> Vec<String> {
+    let reachable = context.def_interner.reachable_functions(entry);
+    let mut unreachable: Vec<String> = crate_functions(context, crate_id)
+        .filter(|func_id| !reachable.contains(func_id))
+        .map(|func_id| context.fully_qualified_function_name(&crate_id, &func_id))
+        .collect();
+    unreachable.sort();
+    unreachable
+}
+
+/// Returns an error for each parameter or return value of `function_name`'s ABI whose type isn't supported by `profile`.
+fn check_abi_profile(abi: &Abi, profile: AbiProfile, function_name: &str) -> Vec<FileDiagnostic> {
+    vecmap(profile.unsupported_types(abi), |unsupported| {
+        CustomDiagnostic::from_message(&format!(
+            "`{}` of `{function_name}` has type `{}`, which is not supported by the `{profile}` ABI profile",
+            unsupported.item,
+            abi_type_to_string(&unsupported.typ),
+        ))
+        .in_file(FileId::default())
+    })
+}
+
+/// Applies the lint levels from `context.lint_levels` to the warnings raised in the crate's own files,
+/// dropping those of allowed lints and turning those of denied lints into errors.
+///
+/// A file can also allow lints for itself with `#![allow(lint_name)]`, whatever level they're configured at.
+fn apply_lint_levels(
+    context: &Context,
+    crate_id: CrateId,
+    diagnostics: Vec<FileDiagnostic>,
+) -> Vec<FileDiagnostic> {
+    let crate_dir = context
+        .file_manager
+        .path(context.crate_graph[crate_id].root_file_id)
+        .and_then(Path::parent);
+    let is_in_crate = |file_id: FileId| {
+        let path = context.file_manager.path(file_id);
+        crate_dir.zip(path).map_or(false, |(crate_dir, path)| path.starts_with(crate_dir))
+    };
+    let is_allowed_in_file = |file_id: FileId, lint: &str| {
+        context.parsed_files.get(&file_id).map_or(false, |(module, _)| {
+            module.inner_attributes.iter().any(
+                |attribute| matches!(attribute, SecondaryAttribute::Allow(name) if name == lint),
+            )
+        })
+    };
+
+    diagnostics
+        .into_iter()
+        .filter_map(|mut diagnostic| {
+            let Some(lint) =
+                diagnostic.diagnostic.lint.filter(|_| diagnostic.diagnostic.is_warning())
+            else {
+                return Some(diagnostic);
+            };
+            if is_allowed_in_file(diagnostic.file_id, lint) {
+                return None;
+            }
+            if !is_in_crate(diagnostic.file_id) {
+                return Some(diagnostic);
+            }
+            match context.lint_levels.get(lint).copied().unwrap_or_default() {
+                LintLevel::Allow => None,
+                LintLevel::Warn => Some(diagnostic),
+                LintLevel::Deny => {
+                    diagnostic.diagnostic.kind = DiagnosticKind::Error;
+                    diagnostic.diagnostic.add_note(format!(
+                        "`{lint}` is denied in the `[lints]` section of Nargo.toml"
+                    ));
+                    Some(diagnostic)
+                }
+            }
+        })
+        .collect()
+}
+
 /// True if there are (non-warning) errors present and we should halt compilation
 fn has_errors(errors: &[FileDiagnostic], deny_warnings: bool) -> bool {
     if deny_warnings {
@@ -436,16 +775,23 @@ fn compile_contract_inner(
     context: &mut Context,
     contract: Contract,
     options: &CompileOptions,
+    cached_contract: Option<&CompiledContract>,
 ) -> Result<CompiledContract, ErrorsAndWarnings> {
     let mut functions = Vec::new();
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
     for contract_function in &contract.functions {
         let function_id = contract_function.function_id;
-        let is_entry_point = contract_function.is_entry_point;
-
         let name = context.function_name(&function_id).to_owned();
 
+        let is_entry_point = if options.make_public_functions.contains(&name) {
+            true
+        } else if options.make_internal_functions.contains(&name) {
+            false
+        } else {
+            contract_function.is_entry_point
+        };
+
         // We assume that functions have already been type checked.
         // This is the exact same assumption that compile_no_check makes.
         // If it is not an entry-point point, we can then just skip the
@@ -454,7 +800,22 @@ fn compile_contract_inner(
             continue;
         }
 
-        let function = match compile_no_check(context, options, function_id, None, true) {
+        // Functions are matched against the cached contract by name.
+        let cached_function = cached_contract.and_then(|cached_contract| {
+            cached_contract
+                .functions
+                .iter()
+                .find(|function| function.name == name)
+                .map(|function| function.as_cached_program(&cached_contract.noir_version))
+        });
+
+        let function = match compile_no_check(
+            context,
+            options,
+            function_id,
+            cached_function,
+            options.force_compile,
+        ) {
             Ok(function) => function,
             Err(new_error) => {
                 errors.push(FileDiagnostic::from(new_error));
@@ -462,6 +823,9 @@ fn compile_contract_inner(
             }
         };
         warnings.extend(function.warnings);
+        if let Some(profile) = options.strict_abi {
+            errors.extend(check_abi_profile(&function.abi, profile, &name));
+        }
         let modifiers = context.def_interner.function_modifiers(&function_id);
 
         let custom_attributes = modifiers
@@ -477,6 +841,7 @@ fn compile_contract_inner(
 
         functions.push(ContractFunction {
             name,
+            hash: function.hash,
             custom_attributes,
             abi: function.abi,
             bytecode: function.program,
@@ -488,6 +853,9 @@ fn compile_contract_inner(
     }
 
     if errors.is_empty() {
+        // Sort the functions so that artifacts don't depend on the order functions were collected in.
+        functions.sort_by(|a, b| a.name.cmp(&b.name));
+
         let debug_infos: Vec<_> =
             functions.iter().flat_map(|function| function.debug.clone()).collect();
         let file_map = filter_relevant_files(&debug_infos, &context.file_manager);
@@ -575,18 +943,20 @@ pub fn compile_no_check(
         println!("{program}");
     }
 
+    let mut monomorphized_functions = BTreeMap::new();
+    for function in &program.functions {
+        *monomorphized_functions.entry(function.name.clone()).or_default() += 1;
+    }
+
     // If user has specified that they want to see intermediate steps printed then we should
     // force compilation even if the program hasn't changed.
-    let force_compile = force_compile
-        || options.print_acir
-        || options.show_brillig
-        || options.force_brillig
-        || options.show_ssa
-        || options.emit_ssa;
+    let force_compile = force_compile || options.requires_fresh_compile();
 
     if !force_compile && hashes_match {
         info!("Program matches existing artifact, returning early");
-        return Ok(cached_program.expect("cache must exist for hashes to match"));
+        let mut cached_program = cached_program.expect("cache must exist for hashes to match");
+        cached_program.monomorphized_functions = monomorphized_functions;
+        return Ok(cached_program);
     }
     let return_visibility = program.return_visibility;
     let ssa_evaluator_options = noirc_evaluator::ssa::SsaEvaluatorOptions {
@@ -630,5 +1000,7 @@ pub fn compile_no_check(
         warnings,
         names,
         brillig_names,
+        monomorphized_functions,
+        unreachable_functions: Vec::new(),
     })
 }
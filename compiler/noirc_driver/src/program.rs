@@ -31,4 +31,12 @@ pub struct CompiledProgram {
     pub names: Vec<String>,
     /// Names of the unconstrained functions in the program.
     pub brillig_names: Vec<String>,
+    /// The number of instances which monomorphization generated of each function of the program.
+    /// Generic functions have an instance for each combination of generics they're used with.
+    #[serde(skip)]
+    pub monomorphized_functions: BTreeMap<String, usize>,
+    /// The fully qualified names of the crate's functions which can't be reached from the `--reachable-from`
+    /// entry point, and so weren't compiled.
+    #[serde(skip)]
+    pub unreachable_functions: Vec<String>,
 }
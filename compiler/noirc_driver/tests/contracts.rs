@@ -1,7 +1,9 @@
 use std::path::Path;
 
 use fm::FileId;
-use noirc_driver::{file_manager_with_stdlib, prepare_crate, CompileOptions, ErrorsAndWarnings};
+use noirc_driver::{
+    file_manager_with_stdlib, prepare_crate, CompileOptions, CompiledContract, ErrorsAndWarnings,
+};
 use noirc_errors::CustomDiagnostic;
 use noirc_frontend::hir::{def_map::parse_file, Context};
 
@@ -27,9 +29,13 @@ contract Bar {}";
     let mut context = Context::new(file_manager, parsed_files);
     let root_crate_id = prepare_crate(&mut context, file_name);
 
-    let errors =
-        noirc_driver::compile_contract(&mut context, root_crate_id, &CompileOptions::default())
-            .unwrap_err();
+    let errors = noirc_driver::compile_contract(
+        &mut context,
+        root_crate_id,
+        &CompileOptions::default(),
+        None,
+    )
+    .unwrap_err();
 
     assert_eq!(
         errors,
@@ -40,3 +46,61 @@ contract Bar {}";
 
     Ok(())
 }
+
+fn compile_contract_source(source: &str) -> Result<CompiledContract, ErrorsAndWarnings> {
+    let root = Path::new("");
+    let file_name = Path::new("main.nr");
+    let mut file_manager = file_manager_with_stdlib(root);
+    file_manager.add_file_with_source(file_name, source.to_owned()).expect(
+        "Adding source buffer to file manager should never fail when file manager is empty",
+    );
+    let parsed_files = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .map(|&file_id| (file_id, parse_file(&file_manager, file_id)))
+        .collect();
+
+    let mut context = Context::new(file_manager, parsed_files);
+    let root_crate_id = prepare_crate(&mut context, file_name);
+
+    noirc_driver::compile_contract(&mut context, root_crate_id, &CompileOptions::default(), None)
+        .map(|(contract, _)| contract)
+}
+
+#[test]
+fn compiled_contracts_are_reproducible() -> Result<(), ErrorsAndWarnings> {
+    let source = "
+contract Foo {
+    #[abi(storage)]
+    pub struct Storage { a: Field, b: Field }
+
+    #[abi(notes)]
+    pub struct Note { value: Field }
+
+    #[abi(values)]
+    pub global Z: Field = 3;
+
+    #[abi(values)]
+    pub global A: Field = 1;
+
+    fn zebra(x: Field) -> pub Field { x + 1 }
+
+    fn apple(x: Field) -> pub Field { x * 2 }
+
+    fn mango(x: Field, y: Field) { assert(x != y); }
+}";
+
+    let first = compile_contract_source(source)?;
+    let second = compile_contract_source(source)?;
+
+    let function_names: Vec<_> = first.functions.iter().map(|function| &function.name).collect();
+    assert_eq!(function_names, vec!["apple", "mango", "zebra"]);
+
+    assert_eq!(
+        serde_json::to_vec(&first).unwrap(),
+        serde_json::to_vec(&second).unwrap(),
+        "compiling the same contract twice produced different artifacts"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use noirc_driver::{check_crate, file_manager_with_stdlib, prepare_crate, CompileOptions};
+use noirc_errors::{FileDiagnostic, LintLevel};
+use noirc_frontend::hir::{def_map::parse_file, Context};
+
+/// Checks `source`, with `unused_variables` set to `level`, returning the diagnostics and whether any were errors.
+fn check_with_unused_variables_level(
+    source: &str,
+    level: LintLevel,
+) -> (Vec<FileDiagnostic>, bool) {
+    let root = Path::new("");
+    let file_name = Path::new("main.nr");
+    let mut file_manager = file_manager_with_stdlib(root);
+    file_manager.add_file_with_source(file_name, source.to_owned()).expect(
+        "Adding source buffer to file manager should never fail when file manager is empty",
+    );
+    let parsed_files = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .map(|&file_id| (file_id, parse_file(&file_manager, file_id)))
+        .collect();
+
+    let mut context = Context::new(file_manager, parsed_files);
+    context.lint_levels = BTreeMap::from([("unused_variables".to_string(), level)]);
+    let root_crate_id = prepare_crate(&mut context, file_name);
+
+    match check_crate(&mut context, root_crate_id, &CompileOptions::default()) {
+        Ok(((), warnings)) => (warnings, false),
+        Err(errors) => (errors, true),
+    }
+}
+
+#[test]
+fn lint_levels_are_applied_to_warnings() {
+    let source = "fn main() { let x = 1; }";
+
+    let (diagnostics, failed) = check_with_unused_variables_level(source, LintLevel::Warn);
+    assert!(!failed);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].diagnostic.lint, Some("unused_variables"));
+
+    let (diagnostics, failed) = check_with_unused_variables_level(source, LintLevel::Deny);
+    assert!(failed);
+    assert!(diagnostics[0].diagnostic.is_error());
+
+    let (diagnostics, failed) = check_with_unused_variables_level(source, LintLevel::Allow);
+    assert!(!failed);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn files_can_allow_denied_lints() {
+    let source = "#![allow(unused_variables)]\nfn main() { let x = 1; }";
+
+    let (diagnostics, failed) = check_with_unused_variables_level(source, LintLevel::Deny);
+    assert!(!failed);
+    assert!(diagnostics.is_empty());
+}
@@ -0,0 +1,54 @@
+use std::path::Path;
+
+use noirc_driver::{
+    compile_main, file_manager_with_stdlib, prepare_crate, CompileOptions, CompiledProgram,
+};
+use noirc_errors::FileDiagnostic;
+use noirc_frontend::hir::{def_map::parse_file, Context};
+
+/// Compiles `source` from the function named `entry`, as with `--reachable-from`.
+fn compile_reachable_from(
+    source: &str,
+    entry: &str,
+) -> Result<CompiledProgram, Vec<FileDiagnostic>> {
+    let root = Path::new("");
+    let file_name = Path::new("main.nr");
+    let mut file_manager = file_manager_with_stdlib(root);
+    file_manager.add_file_with_source(file_name, source.to_owned()).expect(
+        "Adding source buffer to file manager should never fail when file manager is empty",
+    );
+    let parsed_files = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .map(|&file_id| (file_id, parse_file(&file_manager, file_id)))
+        .collect();
+
+    let mut context = Context::new(file_manager, parsed_files);
+    let root_crate_id = prepare_crate(&mut context, file_name);
+    let options = CompileOptions { reachable_from: Some(entry.to_string()), ..Default::default() };
+    compile_main(&mut context, root_crate_id, &options, None).map(|(program, _)| program)
+}
+
+#[test]
+fn returns_the_functions_unreachable_from_the_entry_point() {
+    let source = "
+        fn main(x: Field) { helper(x); }
+        fn entry(x: Field) { helper(x); }
+        fn helper(x: Field) { assert(x != 0); }
+        fn unused() {}
+    ";
+
+    let program = compile_reachable_from(source, "entry").unwrap();
+    assert_eq!(program.unreachable_functions, vec!["main".to_string(), "unused".to_string()]);
+}
+
+#[test]
+fn rejects_entry_points_with_an_invalid_signature() {
+    let source = "
+        fn main(x: Field) { assert(x != 0); }
+        fn entry<T>(_x: T) {}
+    ";
+
+    let errors = compile_reachable_from(source, "entry").unwrap_err();
+    assert!(errors[0].diagnostic.message.contains("signature isn't valid for `main`"));
+}
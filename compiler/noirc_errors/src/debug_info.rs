@@ -55,6 +55,28 @@ pub struct ProgramDebugInfo {
     pub debug_infos: Vec<DebugInfo>,
 }
 
+/// The version of the format in which [ProgramDebugInfo] is serialized into artifacts.
+///
+/// This should be bumped whenever a change to the debug info makes it unreadable by older versions of nargo,
+/// or makes older artifacts unreadable. Artifacts written before the format was versioned have no version,
+/// and are read as version 1.
+pub const DEBUG_INFO_FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct VersionedProgramDebugInfoRef<'a> {
+    version: u32,
+    #[serde(flatten)]
+    debug_info: &'a ProgramDebugInfo,
+}
+
+#[derive(Deserialize)]
+struct VersionedProgramDebugInfo {
+    #[serde(default)]
+    version: Option<u32>,
+    #[serde(flatten)]
+    debug_info: ProgramDebugInfo,
+}
+
 impl ProgramDebugInfo {
     pub fn serialize_compressed_base64_json<S>(
         debug_info: &ProgramDebugInfo,
@@ -63,7 +85,9 @@ impl ProgramDebugInfo {
     where
         S: Serializer,
     {
-        let json_str = serde_json::to_string(debug_info).map_err(S::Error::custom)?;
+        let versioned =
+            VersionedProgramDebugInfoRef { version: DEBUG_INFO_FORMAT_VERSION, debug_info };
+        let json_str = serde_json::to_string(&versioned).map_err(S::Error::custom)?;
 
         let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
         encoder.write_all(json_str.as_bytes()).map_err(S::Error::custom)?;
@@ -89,7 +113,16 @@ impl ProgramDebugInfo {
         decoder.read_to_end(&mut decompressed_data).map_err(D::Error::custom)?;
 
         let json_str = String::from_utf8(decompressed_data).map_err(D::Error::custom)?;
-        serde_json::from_str(&json_str).map_err(D::Error::custom)
+        let versioned: VersionedProgramDebugInfo =
+            serde_json::from_str(&json_str).map_err(D::Error::custom)?;
+        match versioned.version {
+            Some(version) if version > DEBUG_INFO_FORMAT_VERSION => {
+                Err(D::Error::custom(format!(
+                    "debug info format version {version} is newer than the latest supported version {DEBUG_INFO_FORMAT_VERSION}, recompile the program with this version of nargo"
+                )))
+            }
+            _ => Ok(versioned.debug_info),
+        }
     }
 }
 
@@ -109,6 +142,11 @@ pub struct DebugInfo {
     /// This a map per brillig function representing the range of opcodes where a procedure is activated.
     pub brillig_procedure_locs:
         BTreeMap<BrilligFunctionId, BTreeMap<ProcedureDebugId, (usize, usize)>>,
+    /// Map opcode index of an ACIR assertion into the static message it was declared with,
+    /// so that the original `assert` message can be displayed when the constraint fails.
+    #[serde(default)]
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    pub assert_messages: BTreeMap<OpcodeLocation, String>,
 }
 
 impl DebugInfo {
@@ -126,7 +164,15 @@ impl DebugInfo {
             BTreeMap<ProcedureDebugId, (usize, usize)>,
         >,
     ) -> Self {
-        Self { locations, brillig_locations, variables, functions, types, brillig_procedure_locs }
+        Self {
+            locations,
+            brillig_locations,
+            variables,
+            functions,
+            types,
+            brillig_procedure_locs,
+            assert_messages: BTreeMap::default(),
+        }
     }
 
     /// Updates the locations map when the [`Circuit`][acvm::acir::circuit::Circuit] is modified.
@@ -143,9 +189,124 @@ impl DebugInfo {
                 self.locations.insert(new_opcode_location, source_locations.clone());
             });
         }
+
+        let old_assert_messages = mem::take(&mut self.assert_messages);
+
+        for (old_opcode_location, message) in old_assert_messages {
+            update_map.new_locations(old_opcode_location).for_each(|new_opcode_location| {
+                self.assert_messages.insert(new_opcode_location, message.clone());
+            });
+        }
     }
 
     pub fn opcode_location(&self, loc: &OpcodeLocation) -> Option<Vec<Location>> {
         self.locations.get(loc).cloned()
     }
+
+    pub fn assert_message(&self, loc: &OpcodeLocation) -> Option<&str> {
+        self.assert_messages.get(loc).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
+    use acvm::acir::native_types::{Expression, Witness};
+    use acvm::{AcirField, FieldElement};
+
+    use super::{DebugInfo, ProgramDebugInfo, DEBUG_INFO_FORMAT_VERSION};
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Artifact {
+        #[serde(
+            serialize_with = "ProgramDebugInfo::serialize_compressed_base64_json",
+            deserialize_with = "ProgramDebugInfo::deserialize_compressed_base64_json"
+        )]
+        debug_symbols: ProgramDebugInfo,
+    }
+
+    fn compress(json: &str) -> String {
+        use base64::Engine;
+        use std::io::Write;
+
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        let artifact = serde_json::json!({
+            "debug_symbols": base64::prelude::BASE64_STANDARD.encode(encoder.finish().unwrap())
+        });
+        artifact.to_string()
+    }
+
+    #[test]
+    fn round_trips_debug_info() {
+        let artifact =
+            Artifact { debug_symbols: ProgramDebugInfo { debug_infos: vec![Default::default()] } };
+        let json = serde_json::to_string(&artifact).unwrap();
+        let artifact: Artifact = serde_json::from_str(&json).unwrap();
+        assert_eq!(artifact.debug_symbols.debug_infos.len(), 1);
+    }
+
+    #[test]
+    fn reads_unversioned_debug_info() {
+        let artifact: Artifact = serde_json::from_str(&compress(r#"{"debug_infos":[]}"#)).unwrap();
+        assert!(artifact.debug_symbols.debug_infos.is_empty());
+    }
+
+    #[test]
+    fn rejects_newer_debug_info_versions() {
+        let version = DEBUG_INFO_FORMAT_VERSION + 1;
+        let json = compress(&format!(r#"{{"version":{version},"debug_infos":[]}}"#));
+        assert!(serde_json::from_str::<Artifact>(&json).is_err());
+    }
+
+    #[test]
+    fn round_trips_assert_messages() {
+        let mut debug_info = DebugInfo::default();
+        debug_info
+            .assert_messages
+            .insert(OpcodeLocation::Acir(3), "x must be positive".to_string());
+        let artifact =
+            Artifact { debug_symbols: ProgramDebugInfo { debug_infos: vec![debug_info] } };
+
+        let json = serde_json::to_string(&artifact).unwrap();
+        let artifact: Artifact = serde_json::from_str(&json).unwrap();
+        let debug_info = &artifact.debug_symbols.debug_infos[0];
+        assert_eq!(debug_info.assert_message(&OpcodeLocation::Acir(3)), Some("x must be positive"));
+        assert_eq!(debug_info.assert_message(&OpcodeLocation::Acir(0)), None);
+    }
+
+    #[test]
+    fn reads_debug_info_without_assert_messages() {
+        let mut debug_info = serde_json::to_value(DebugInfo::default()).unwrap();
+        debug_info.as_object_mut().unwrap().remove("assert_messages");
+        let json = serde_json::json!({ "debug_infos": [debug_info] }).to_string();
+
+        let artifact: Artifact = serde_json::from_str(&compress(&json)).unwrap();
+        assert!(artifact.debug_symbols.debug_infos[0].assert_messages.is_empty());
+    }
+
+    #[test]
+    fn assert_messages_follow_optimized_opcodes() {
+        let assert_equal = Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (-FieldElement::one(), Witness(2)),
+            ],
+            q_c: FieldElement::zero(),
+        });
+        let circuit =
+            Circuit { current_witness_index: 2, opcodes: vec![assert_equal], ..Circuit::default() };
+        let (_, transformation_map) = acvm::compiler::optimize(circuit);
+
+        let mut debug_info = DebugInfo::default();
+        debug_info.assert_messages.insert(OpcodeLocation::Acir(0), "x != y".to_string());
+        // A message for an opcode which isn't part of the optimized circuit is dropped.
+        debug_info.assert_messages.insert(OpcodeLocation::Acir(1), "removed".to_string());
+        debug_info.update_acir(transformation_map);
+
+        assert_eq!(debug_info.assert_message(&OpcodeLocation::Acir(0)), Some("x != y"));
+        assert_eq!(debug_info.assert_messages.len(), 1);
+    }
 }
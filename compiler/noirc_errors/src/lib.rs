@@ -7,7 +7,7 @@ pub mod debug_info;
 mod position;
 pub mod reporter;
 pub use position::{Location, Position, Span, Spanned};
-pub use reporter::{CustomDiagnostic, DiagnosticKind};
+pub use reporter::{CustomDiagnostic, DiagnosticKind, LintLevel};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FileDiagnostic {
@@ -5,6 +5,7 @@ use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::files::Files;
 use codespan_reporting::term;
 use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CustomDiagnostic {
@@ -15,6 +16,10 @@ pub struct CustomDiagnostic {
     pub deprecated: bool,
     pub unnecessary: bool,
 
+    /// The name of the lint which this warning belongs to, such as `unused_variables`,
+    /// through which its level can be configured in the `[lints]` section of `Nargo.toml`.
+    pub lint: Option<&'static str>,
+
     /// An optional call stack to display the full runtime call stack
     /// leading up to a runtime error. If this is empty it will not be displayed.
     pub call_stack: Vec<Location>,
@@ -28,6 +33,18 @@ pub enum DiagnosticKind {
     Info,
 }
 
+/// How a lint's warnings are reported, as configured in the `[lints]` section of `Nargo.toml`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// The warnings aren't reported.
+    Allow,
+    #[default]
+    Warn,
+    /// The warnings are reported as errors.
+    Deny,
+}
+
 /// A count of errors that have been already reported to stderr
 #[derive(Debug, Copy, Clone)]
 pub struct ReportedErrors {
@@ -43,6 +60,7 @@ impl CustomDiagnostic {
             kind: DiagnosticKind::Error,
             deprecated: false,
             unnecessary: false,
+            lint: None,
             call_stack: Default::default(),
         }
     }
@@ -60,6 +78,7 @@ impl CustomDiagnostic {
             kind,
             deprecated: false,
             unnecessary: false,
+            lint: None,
             call_stack: Default::default(),
         }
     }
@@ -115,6 +134,7 @@ impl CustomDiagnostic {
             kind: DiagnosticKind::Bug,
             deprecated: false,
             unnecessary: false,
+            lint: None,
             call_stack: Default::default(),
         }
     }
@@ -123,6 +143,11 @@ impl CustomDiagnostic {
         FileDiagnostic::new(file_id, self)
     }
 
+    pub fn with_lint(mut self, lint: &'static str) -> Self {
+        self.lint = Some(lint);
+        self
+    }
+
     pub fn with_call_stack(mut self, call_stack: Vec<Location>) -> Self {
         self.call_stack = call_stack;
         self
@@ -210,6 +235,92 @@ pub fn report_all<'files>(
     ReportedErrors { error_count }
 }
 
+/// Writes the given diagnostics to stdout as JSON Lines, with one object per diagnostic,
+/// and returns the count of diagnostics that were errors.
+///
+/// Diagnostics are filtered and ordered in the same way as by [report_all].
+pub fn report_all_json<'files>(
+    files: &'files impl Files<'files, FileId = fm::FileId>,
+    diagnostics: &[FileDiagnostic],
+    deny_warnings: bool,
+    silence_warnings: bool,
+) -> ReportedErrors {
+    let (warnings_and_bugs, mut errors): (Vec<_>, _) =
+        diagnostics.iter().partition(|item| !item.diagnostic.is_error());
+
+    let (warnings, mut bugs): (Vec<_>, _) =
+        warnings_and_bugs.iter().partition(|item| item.diagnostic.is_warning());
+    let mut diagnostics = if silence_warnings { Vec::new() } else { warnings };
+    diagnostics.append(&mut bugs);
+    diagnostics.append(&mut errors);
+
+    let mut error_count = 0;
+    for diagnostic in diagnostics {
+        let json_diagnostic = JsonDiagnostic::new(files, diagnostic, deny_warnings);
+        println!("{}", serde_json::to_string(&json_diagnostic).unwrap());
+        error_count += (deny_warnings || diagnostic.diagnostic.is_error()) as u32;
+    }
+
+    ReportedErrors { error_count }
+}
+
+/// A diagnostic as written by [report_all_json].
+#[derive(Debug, Serialize)]
+struct JsonDiagnostic<'a> {
+    /// One of `error`, `warning`, `bug` or `info`. Warnings are reported as errors when they're denied.
+    level: &'static str,
+    message: &'a str,
+    /// The path of the file which the diagnostic points into.
+    file: Option<String>,
+    /// The location of the diagnostic's first label within `file`, if it has any.
+    span: Option<JsonSpan>,
+    /// The messages of the diagnostic's labels, in the order they're displayed.
+    labels: Vec<&'a str>,
+    notes: &'a [String],
+}
+
+#[derive(Debug, Serialize)]
+struct JsonSpan {
+    start: u32,
+    end: u32,
+    line: u32,
+    column: u32,
+}
+
+impl<'a> JsonDiagnostic<'a> {
+    fn new<'files>(
+        files: &'files impl Files<'files, FileId = fm::FileId>,
+        file_diagnostic: &'a FileDiagnostic,
+        deny_warnings: bool,
+    ) -> Self {
+        let diagnostic = &file_diagnostic.diagnostic;
+        let level = match (diagnostic.kind, deny_warnings) {
+            (DiagnosticKind::Warning, false) => "warning",
+            (DiagnosticKind::Info, _) => "info",
+            (DiagnosticKind::Bug, _) => "bug",
+            _ => "error",
+        };
+
+        let primary_label = diagnostic.secondaries.first();
+        let file_id = primary_label.and_then(|label| label.file).unwrap_or(file_diagnostic.file_id);
+        let file = files.name(file_id).ok().map(|name| name.to_string());
+        let span = primary_label.and_then(|label| {
+            let source = files.source(file_id).ok()?;
+            let (line, column) = line_and_column_from_span(source.as_ref(), &label.span);
+            Some(JsonSpan { start: label.span.start(), end: label.span.end(), line, column })
+        });
+
+        JsonDiagnostic {
+            level,
+            message: &diagnostic.message,
+            file,
+            span,
+            labels: diagnostic.secondaries.iter().map(|label| label.message.as_str()).collect(),
+            notes: &diagnostic.notes,
+        }
+    }
+}
+
 impl FileDiagnostic {
     pub fn report<'files>(
         &self,
@@ -283,16 +394,27 @@ fn stack_trace<'files>(
     let mut result = "Call stack:\n".to_string();
 
     for (i, call_item) in call_stack.iter().enumerate() {
-        let path = files.name(call_item.file).expect("should get file path");
-        let source = files.source(call_item.file).expect("should get file source");
-
-        let (line, column) = line_and_column_from_span(source.as_ref(), &call_item.span);
-        result += &format!("{}. {}:{}:{}\n", i + 1, path, line, column);
+        let location = format_location(files, *call_item).expect("should get file path and source");
+        result += &format!("{}. {location}\n", i + 1);
     }
 
     result
 }
 
+/// Formats `location` as `path:line:column`, pointing at where it starts.
+///
+/// Returns `None` if `files` doesn't contain the location's file.
+pub fn format_location<'files>(
+    files: &'files impl Files<'files, FileId = fm::FileId>,
+    location: Location,
+) -> Option<String> {
+    let path = files.name(location.file).ok()?;
+    let source = files.source(location.file).ok()?;
+
+    let (line, column) = line_and_column_from_span(source.as_ref(), &location.span);
+    Some(format!("{path}:{line}:{column}"))
+}
+
 pub fn line_and_column_from_span(source: &str, span: &Span) -> (u32, u32) {
     let mut line = 1;
     let mut column = 0;
@@ -18,7 +18,7 @@ use crate::errors::{RuntimeError, SsaReport};
 use acvm::{
     acir::{
         circuit::{
-            brillig::BrilligBytecode, Circuit, ErrorSelector, ExpressionWidth,
+            brillig::BrilligBytecode, Circuit, ErrorSelector, ExpressionWidth, OpcodeLocation,
             Program as AcirProgram, PublicInputs,
         },
         native_types::Witness,
@@ -335,6 +335,18 @@ fn convert_generated_acir_into_circuit(
     let public_parameters = PublicInputs(public_parameter_witnesses);
     let return_values = PublicInputs(return_witnesses.iter().copied().collect());
 
+    // Static assertion messages are carried through to the debug info so they can be displayed
+    // without needing to decode the assertion payload.
+    let static_assert_messages: BTreeMap<OpcodeLocation, String> = assert_messages
+        .iter()
+        .filter_map(|(opcode_location, payload)| {
+            match generated_acir.error_types.get(&ErrorSelector::new(payload.error_selector)) {
+                Some(ErrorType::String(message)) => Some((*opcode_location, message.clone())),
+                _ => None,
+            }
+        })
+        .collect();
+
     let circuit = Circuit {
         current_witness_index,
         expression_width: ExpressionWidth::Unbounded,
@@ -370,6 +382,7 @@ fn convert_generated_acir_into_circuit(
         debug_types,
         brillig_procedure_locs,
     );
+    debug_info.assert_messages = static_assert_messages;
 
     // Perform any ACIR-level optimizations
     let (optimized_circuit, transformation_map) = acvm::compiler::optimize(circuit);
@@ -28,7 +28,9 @@ use crate::{
         stmt::HirStatement,
         traits::{ResolvedTraitBound, TraitConstraint},
     },
-    node_interner::{DefinitionKind, ExprId, FuncId, InternedStatementKind, TraitMethodId},
+    node_interner::{
+        DefinitionKind, DependencyId, ExprId, FuncId, InternedStatementKind, TraitMethodId,
+    },
     token::Tokens,
     Kind, QuotedType, Shared, StructType, Type,
 };
@@ -461,6 +463,14 @@ impl<'context> Elaborator<'context> {
 
                 self.check_method_call_visibility(func_id, &object_type, &method_call.method);
 
+                // Only calls made from functions are recorded, as a method call from a global or a type
+                // could otherwise be mistaken for a dependency cycle.
+                if let Some(current_item @ DependencyId::Function(_)) = self.current_item {
+                    if func_id != FuncId::dummy_id() {
+                        self.interner.add_function_dependency(current_item, func_id);
+                    }
+                }
+
                 // Desugar the method call into a normal, resolved function call
                 // so that the backend doesn't need to worry about methods
                 // TODO: update object_type here?
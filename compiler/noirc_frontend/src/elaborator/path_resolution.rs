@@ -139,6 +139,7 @@ impl<'context> Elaborator<'context> {
         };
         let (path, module_id, _) =
             resolve_path_kind(path, importing_module, self.def_maps, references_tracker)?;
+        self.usage_tracker.mark_dependency_as_used(importing_module.krate, module_id.krate);
         self.resolve_name_in_module(path, module_id, importing_module)
     }
 
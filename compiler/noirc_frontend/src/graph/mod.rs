@@ -118,6 +118,14 @@ pub struct CrateGraph {
 /// and we do not want names that differ by a hyphen
 pub const CHARACTER_BLACK_LIST: [char; 1] = ['-'];
 
+/// Name under which a package's custom prelude crate is linked as a dependency.
+/// Every public item at the root of this crate is imported into each module of the crate depending on it.
+pub const PRELUDE_CRATE_NAME: &str = "__prelude";
+
+/// Name under which the crate holding a package's `PACKAGE_VERSION` global is linked as a dependency.
+/// Its public items are imported into each module of the crate depending on it, as with a custom prelude.
+pub const PACKAGE_CRATE_NAME: &str = "__package";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CrateData {
     pub root_file_id: FileId,
@@ -1,7 +1,7 @@
 use super::dc_mod::collect_defs;
 use super::errors::{DefCollectorErrorKind, DuplicateType};
 use crate::elaborator::Elaborator;
-use crate::graph::CrateId;
+use crate::graph::{CrateId, PACKAGE_CRATE_NAME, PRELUDE_CRATE_NAME};
 use crate::hir::comptime::InterpreterError;
 use crate::hir::def_map::{CrateDefMap, LocalModuleId, ModuleId};
 use crate::hir::resolution::errors::ResolverError;
@@ -32,7 +32,7 @@ use noirc_errors::{CustomDiagnostic, Location, Span};
 use fm::FileId;
 use iter_extended::vecmap;
 use rustc_hash::FxHashMap as HashMap;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Write;
 use std::path::PathBuf;
 use std::vec;
@@ -512,52 +512,119 @@ fn inject_prelude(
     crate_root: LocalModuleId,
     collected_imports: &mut Vec<ImportDirective>,
 ) {
+    // The custom prelude is injected first so that its imports are resolved after those of `std::prelude`,
+    // allowing its items to shadow any of the standard library's prelude with the same name.
+    let has_custom_prelude = context.crate_graph[crate_id]
+        .dependencies
+        .iter()
+        .any(|dep| dep.as_name() == PRELUDE_CRATE_NAME);
+    if has_custom_prelude {
+        inject_prelude_module(
+            PRELUDE_CRATE_NAME,
+            true,
+            crate_id,
+            context,
+            crate_root,
+            collected_imports,
+        );
+    }
+
+    let has_package_crate = context.crate_graph[crate_id]
+        .dependencies
+        .iter()
+        .any(|dep| dep.as_name() == PACKAGE_CRATE_NAME);
+    if has_package_crate {
+        inject_prelude_module(
+            PACKAGE_CRATE_NAME,
+            true,
+            crate_id,
+            context,
+            crate_root,
+            collected_imports,
+        );
+    }
+
     if !crate_id.is_stdlib() {
-        let segments: Vec<_> = "std::prelude"
-            .split("::")
-            .map(|segment| {
-                crate::ast::PathSegment::from(crate::ast::Ident::new(
-                    segment.into(),
-                    Span::default(),
-                ))
+        inject_prelude_module(
+            "std::prelude",
+            false,
+            crate_id,
+            context,
+            crate_root,
+            collected_imports,
+        );
+    }
+}
+
+/// Imports the items of the module at `prelude_path` into `crate_root` as prelude items,
+/// which may then be shadowed by explicit imports.
+///
+/// If `only_public` is set then any items which are not public are skipped.
+fn inject_prelude_module(
+    prelude_path: &str,
+    only_public: bool,
+    crate_id: CrateId,
+    context: &mut Context,
+    crate_root: LocalModuleId,
+    collected_imports: &mut Vec<ImportDirective>,
+) {
+    let segments: Vec<_> = prelude_path
+        .split("::")
+        .map(|segment| {
+            crate::ast::PathSegment::from(crate::ast::Ident::new(segment.into(), Span::default()))
+        })
+        .collect();
+
+    let path = Path {
+        segments: segments.clone(),
+        kind: crate::ast::PathKind::Plain,
+        span: Span::default(),
+    };
+
+    if let Ok(resolved_import) = resolve_import(
+        path,
+        ModuleId { krate: crate_id, local_id: crate_root },
+        &context.def_maps,
+        &mut context.usage_tracker,
+        None, // references tracker
+    ) {
+        assert!(resolved_import.errors.is_empty(), "Tried to add private item to prelude");
+
+        let (module_def_id, _, _) = resolved_import
+            .namespace
+            .types
+            .unwrap_or_else(|| panic!("couldn't resolve {prelude_path}"));
+        let module_id = module_def_id
+            .as_module()
+            .unwrap_or_else(|| panic!("{prelude_path} should be a module"));
+        let scope = context.module(module_id).scope();
+        let prelude: BTreeSet<String> = scope
+            .types()
+            .iter()
+            .chain(scope.values())
+            .filter(|(_, scopes)| {
+                !only_public
+                    || scopes
+                        .values()
+                        .any(|(_, visibility, _)| *visibility == ItemVisibility::Public)
             })
+            .map(|(name, _)| name.to_string())
             .collect();
 
-        let path = Path {
-            segments: segments.clone(),
-            kind: crate::ast::PathKind::Plain,
-            span: Span::default(),
-        };
-
-        if let Ok(resolved_import) = resolve_import(
-            path,
-            ModuleId { krate: crate_id, local_id: crate_root },
-            &context.def_maps,
-            &mut context.usage_tracker,
-            None, // references tracker
-        ) {
-            assert!(resolved_import.errors.is_empty(), "Tried to add private item to prelude");
-
-            let (module_def_id, _, _) =
-                resolved_import.namespace.types.expect("couldn't resolve std::prelude");
-            let module_id = module_def_id.as_module().expect("std::prelude should be a module");
-            let prelude = context.module(module_id).scope().names();
-
-            for path in prelude {
-                let mut segments = segments.clone();
-                segments.push(PathSegment::from(Ident::new(path.to_string(), Span::default())));
-
-                collected_imports.insert(
-                    0,
-                    ImportDirective {
-                        visibility: ItemVisibility::Private,
-                        module_id: crate_root,
-                        path: Path { segments, kind: PathKind::Plain, span: Span::default() },
-                        alias: None,
-                        is_prelude: true,
-                    },
-                );
-            }
+        for path in prelude {
+            let mut segments = segments.clone();
+            segments.push(PathSegment::from(Ident::new(path, Span::default())));
+
+            collected_imports.insert(
+                0,
+                ImportDirective {
+                    visibility: ItemVisibility::Private,
+                    module_id: crate_root,
+                    path: Path { segments, kind: PathKind::Plain, span: Span::default() },
+                    alias: None,
+                    is_prelude: true,
+                },
+            );
         }
     }
 }
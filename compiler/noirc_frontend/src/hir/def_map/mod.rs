@@ -183,6 +183,24 @@ impl CrateDefMap {
         })
     }
 
+    /// Go through all modules in this crate, and find all functions in
+    /// each module with the #[bench] attribute
+    pub fn get_all_bench_functions<'a>(
+        &'a self,
+        interner: &'a NodeInterner,
+    ) -> impl Iterator<Item = FuncId> + 'a {
+        self.modules.iter().flat_map(|(_, module)| {
+            module.value_definitions().filter_map(|id| {
+                if let Some(func_id) = id.as_function() {
+                    let attributes = interner.function_attributes(&func_id);
+                    attributes.is_bench_function().then_some(func_id)
+                } else {
+                    None
+                }
+            })
+        })
+    }
+
     /// Go through all modules in this crate, and find all functions in
     /// each module with the #[export] attribute
     pub fn get_all_exported_functions<'a>(
@@ -17,7 +17,7 @@ use def_collector::dc_crate::CompilationError;
 use def_map::{Contract, CrateDefMap};
 use fm::{FileId, FileManager};
 use iter_extended::vecmap;
-use noirc_errors::Location;
+use noirc_errors::{LintLevel, Location};
 use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
@@ -52,6 +52,9 @@ pub struct Context<'file_manager, 'parsed_files> {
     pub parsed_files: Cow<'parsed_files, ParsedFiles>,
 
     pub package_build_path: PathBuf,
+
+    /// The levels of lints configured for the package being compiled, keyed by lint name.
+    pub lint_levels: BTreeMap<String, LintLevel>,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -73,6 +76,7 @@ impl Context<'_, '_> {
             debug_instrumenter: DebugInstrumenter::default(),
             parsed_files: Cow::Owned(parsed_files),
             package_build_path: PathBuf::default(),
+            lint_levels: BTreeMap::new(),
         }
     }
 
@@ -90,6 +94,7 @@ impl Context<'_, '_> {
             debug_instrumenter: DebugInstrumenter::default(),
             parsed_files: Cow::Borrowed(parsed_files),
             package_build_path: PathBuf::default(),
+            lint_levels: BTreeMap::new(),
         }
     }
 
@@ -246,6 +251,31 @@ impl Context<'_, '_> {
             .collect()
     }
 
+    /// Returns the fully qualified names of the crate's `#[bench]` functions which match `pattern`.
+    pub fn get_all_bench_functions_in_crate_matching(
+        &self,
+        crate_id: &CrateId,
+        pattern: FunctionNameMatch,
+    ) -> Vec<(String, FuncId)> {
+        let interner = &self.def_interner;
+        let def_map = self.def_map(crate_id).expect("The local crate should be analyzed already");
+
+        def_map
+            .get_all_bench_functions(interner)
+            .filter_map(|func_id| {
+                let fully_qualified_name = self.fully_qualified_function_name(crate_id, &func_id);
+                match &pattern {
+                    FunctionNameMatch::Anything => Some((fully_qualified_name, func_id)),
+                    FunctionNameMatch::Exact(pattern) => (&fully_qualified_name == pattern)
+                        .then_some((fully_qualified_name, func_id)),
+                    FunctionNameMatch::Contains(pattern) => fully_qualified_name
+                        .contains(pattern)
+                        .then_some((fully_qualified_name, func_id)),
+                }
+            })
+            .collect()
+    }
+
     pub fn get_all_exported_functions_in_crate(&self, crate_id: &CrateId) -> Vec<(String, FuncId)> {
         let interner = &self.def_interner;
         let def_map = self.def_map(crate_id).expect("The local crate should be analyzed already");
@@ -191,7 +191,7 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                     format!("unused variable {name}"),
                     "unused variable ".to_string(),
                     ident.span(),
-                );
+                ).with_lint("unused_variables");
                 diagnostic.unnecessary = true;
                 diagnostic
             }
@@ -199,7 +199,7 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                 let name = &ident.0.contents;
                 let item_type = item.item_type();
 
-                let mut diagnostic =
+                let diagnostic =
                     if let UnusedItem::Struct(..) = item {
                         Diagnostic::simple_warning(
                             format!("{item_type} `{name}` is never constructed"),
@@ -213,6 +213,8 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                             ident.span(),
                         )
                     };
+                let lint = if let UnusedItem::Import = item { "unused_imports" } else { "dead_code" };
+                let mut diagnostic = diagnostic.with_lint(lint);
                 diagnostic.unnecessary = true;
                 diagnostic
             }
@@ -221,7 +223,7 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                     format!("function `{name}` cannot return without recursing"),
                     "function cannot return without recursing".to_string(),
                     *span,
-                )
+                ).with_lint("unconditional_recursion")
             }
             ResolverError::VariableNotDeclared { name, span } =>  {
                 if name == "_" {
@@ -103,6 +103,7 @@ pub fn resolve_import(
 ) -> ImportResolutionResult {
     let (path, module_id, references_tracker) =
         resolve_path_kind(path, importing_module, def_maps, references_tracker)?;
+    usage_tracker.mark_dependency_as_used(importing_module.krate, module_id.krate);
     let mut solver =
         ImportSolver::new(importing_module, def_maps, usage_tracker, references_tracker);
     solver.resolve_name_in_module(path, module_id)
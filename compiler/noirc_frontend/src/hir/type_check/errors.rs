@@ -444,13 +444,14 @@ impl<'a> From<&'a TypeCheckError> for Diagnostic {
                 let primary_message = error.to_string();
                 let secondary_message = note.clone().unwrap_or_default();
 
-                let mut diagnostic = Diagnostic::simple_warning(primary_message, secondary_message, *span);
+                let mut diagnostic = Diagnostic::simple_warning(primary_message, secondary_message, *span)
+                    .with_lint("deprecated");
                 diagnostic.deprecated = true;
                 diagnostic
             }
             TypeCheckError::UnusedResultError { expr_type, expr_span } => {
                 let msg = format!("Unused expression result of type {expr_type}");
-                Diagnostic::simple_warning(msg, String::new(), *expr_span)
+                Diagnostic::simple_warning(msg, String::new(), *expr_span).with_lint("unused_results")
             }
             TypeCheckError::NoMatchingImplFound(error) => error.into(),
             TypeCheckError::UnneededTraitConstraint { trait_name, typ, span } => {
@@ -683,11 +683,16 @@ impl Attributes {
         matches!(self.function(), Some(FunctionAttribute::Test(_)))
     }
 
+    /// True if the function is marked with a `#[bench]` attribute.
+    pub fn is_bench_function(&self) -> bool {
+        matches!(self.function(), Some(FunctionAttribute::Bench))
+    }
+
     /// True if these attributes mean the given function is an entry point function if it was
     /// defined within a contract. Note that this does not check if the function is actually part
     /// of a contract.
     pub fn is_contract_entry_point(&self) -> bool {
-        !self.has_contract_library_method() && !self.is_test_function()
+        !self.has_contract_library_method() && !self.is_test_function() && !self.is_bench_function()
     }
 
     /// Returns note if a deprecated secondary attribute is found
@@ -760,6 +765,7 @@ pub enum FunctionAttribute {
     Builtin(String),
     Oracle(String),
     Test(TestScope),
+    Bench,
     Fold,
     NoPredicates,
     InlineAlways,
@@ -823,6 +829,7 @@ impl FunctionAttribute {
             FunctionAttribute::Builtin(_) => "builtin",
             FunctionAttribute::Oracle(_) => "oracle",
             FunctionAttribute::Test(_) => "test",
+            FunctionAttribute::Bench => "bench",
             FunctionAttribute::Fold => "fold",
             FunctionAttribute::NoPredicates => "no_predicates",
             FunctionAttribute::InlineAlways => "inline_always",
@@ -834,6 +841,7 @@ impl fmt::Display for FunctionAttribute {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             FunctionAttribute::Test(scope) => write!(f, "#[test{scope}]"),
+            FunctionAttribute::Bench => write!(f, "#[bench]"),
             FunctionAttribute::Foreign(ref k) => write!(f, "#[foreign({k})]"),
             FunctionAttribute::Builtin(ref k) => write!(f, "#[builtin({k})]"),
             FunctionAttribute::Oracle(ref k) => write!(f, "#[oracle({k})]"),
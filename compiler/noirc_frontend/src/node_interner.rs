@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fmt;
 use std::hash::Hash;
 use std::marker::Copy;
@@ -11,6 +12,7 @@ use noirc_errors::{Location, Span, Spanned};
 use petgraph::algo::tarjan_scc;
 use petgraph::prelude::DiGraph;
 use petgraph::prelude::NodeIndex as PetGraphIndex;
+use petgraph::visit::Dfs;
 use rustc_hash::FxHashMap as HashMap;
 
 use crate::ast::{
@@ -2067,6 +2069,27 @@ impl NodeInterner {
         index
     }
 
+    /// Returns every function which can be reached from `entry` by following the dependency graph,
+    /// including `entry` itself.
+    ///
+    /// Calls to trait methods only reach the trait's method declaration, as the implementation
+    /// which is called isn't known until monomorphization.
+    pub fn reachable_functions(&self, entry: FuncId) -> HashSet<FuncId> {
+        let mut reachable = HashSet::from([entry]);
+        let Some(entry_index) = self.dependency_graph_indices.get(&DependencyId::Function(entry))
+        else {
+            return reachable;
+        };
+
+        let mut dfs = Dfs::new(&self.dependency_graph, *entry_index);
+        while let Some(index) = dfs.next(&self.dependency_graph) {
+            if let DependencyId::Function(func_id) = self.dependency_graph[index] {
+                reachable.insert(func_id);
+            }
+        }
+        reachable
+    }
+
     pub(crate) fn check_for_dependency_cycles(&self) -> Vec<(CompilationError, FileId)> {
         let strongly_connected_components = tarjan_scc(&self.dependency_graph);
         let mut errors = Vec::new();
@@ -182,6 +182,10 @@ impl<'a> Parser<'a> {
             "allow" => self.parse_single_name_attribute(ident, arguments, start_span, |name| {
                 Attribute::Secondary(SecondaryAttribute::Allow(name))
             }),
+            "bench" => {
+                let attr = Attribute::Function(FunctionAttribute::Bench);
+                self.parse_no_args_attribute(ident, arguments, attr)
+            }
             "builtin" => self.parse_single_name_attribute(ident, arguments, start_span, |name| {
                 Attribute::Function(FunctionAttribute::Builtin(name))
             }),
@@ -567,6 +571,13 @@ mod tests {
         parse_attribute_no_errors(src, expected);
     }
 
+    #[test]
+    fn parses_attribute_bench() {
+        let src = "#[bench]";
+        let expected = Attribute::Function(FunctionAttribute::Bench);
+        parse_attribute_no_errors(src, expected);
+    }
+
     #[test]
     fn parses_meta_attribute_single_identifier_no_arguments() {
         let src = "#[foo]";
@@ -1,7 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     ast::{Ident, ItemVisibility},
+    graph::CrateId,
     hir::def_map::ModuleId,
     node_interner::{FuncId, GlobalId, StructId, TraitId, TypeAliasId},
 };
@@ -32,6 +33,8 @@ impl UnusedItem {
 #[derive(Debug, Default)]
 pub struct UsageTracker {
     unused_items: HashMap<ModuleId, HashMap<Ident, UnusedItem>>,
+    /// Pairs of a crate and a dependency which it references through a path.
+    used_dependencies: HashSet<(CrateId, CrateId)>,
 }
 
 impl UsageTracker {
@@ -75,6 +78,18 @@ impl UsageTracker {
         };
     }
 
+    /// Marks `dependency` as being referenced from within `crate_id`.
+    pub(crate) fn mark_dependency_as_used(&mut self, crate_id: CrateId, dependency: CrateId) {
+        if crate_id != dependency {
+            self.used_dependencies.insert((crate_id, dependency));
+        }
+    }
+
+    /// Returns whether any path within `crate_id` referenced `dependency`.
+    pub fn is_dependency_used(&self, crate_id: CrateId, dependency: CrateId) -> bool {
+        self.used_dependencies.contains(&(crate_id, dependency))
+    }
+
     /// Get all the unused items per module.
     pub fn unused_items(&self) -> &HashMap<ModuleId, HashMap<Ident, UnusedItem>> {
         &self.unused_items
@@ -206,7 +206,7 @@ pub fn compile_contract(
         CompileOptions { expression_width: Some(expression_width), ..CompileOptions::default() };
 
     let compiled_contract =
-        noirc_driver::compile_contract(&mut context, crate_id, &compile_options)
+        noirc_driver::compile_contract(&mut context, crate_id, &compile_options, None)
             .map_err(|errs| {
                 CompileError::with_file_diagnostics(
                     "Failed to compile contract",
@@ -146,7 +146,7 @@ impl CompilerContext {
 
         let root_crate_id = *self.context.root_crate_id();
         let compiled_contract =
-            compile_contract(&mut self.context, root_crate_id, &compile_options)
+            compile_contract(&mut self.context, root_crate_id, &compile_options, None)
                 .map_err(|errs| {
                     CompileError::with_file_diagnostics(
                         "Failed to compile contract",
@@ -28,6 +28,7 @@ done
 
 echo "]" >> Nargo.toml
 
-nargo info --force-brillig --json > gates_report_brillig.json
+# Each line written with --json is a JSON object; keep only the report.
+nargo info --force-brillig --json | grep '^{"programs":' > gates_report_brillig.json
 
 rm Nargo.toml
@@ -40,6 +40,7 @@ done
 
 echo "]" >> Nargo.toml
 
-nargo info --profile-execution --json > gates_report_brillig_execution.json
+# Each line written with --json is a JSON object; keep only the report.
+nargo info --profile-execution --json | grep '^{"programs":' > gates_report_brillig_execution.json
 
 rm Nargo.toml
\ No newline at end of file
@@ -28,7 +28,7 @@ use lsp_types::{
     CodeLens,
 };
 use nargo::{
-    package::{Package, PackageType},
+    package::{CompileSettings, Package, PackageType},
     parse_all,
     workspace::Workspace,
 };
@@ -310,12 +310,21 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
         name: crate_name,
         dependencies: BTreeMap::new(),
         expression_width: None,
+        stdlib_revision: None,
+        prelude_path: None,
+        entry_function: None,
+        lints: BTreeMap::new(),
+        include: None,
+        exclude: Vec::new(),
+        compile: CompileSettings::default(),
     };
     let workspace = Workspace {
         root_dir: PathBuf::from(parent_folder),
         members: vec![assumed_package],
         selected_package_index: Some(0),
         is_assumed: true,
+        artifact_paths: BTreeMap::new(),
+        compile: CompileSettings::default(),
     };
     Ok(workspace)
 }
@@ -15,6 +15,8 @@ pub const EXPORT_DIR: &str = "export";
 pub const PROVER_INPUT_FILE: &str = "Prover";
 /// The package definition file for a Noir project.
 pub const PKG_FILE: &str = "Nargo.toml";
+/// The file which records what each dependency of a workspace resolved to.
+pub const LOCK_FILE: &str = "Nargo.lock";
 
 // Extensions
 /// The extension for files containing circuit proofs.
@@ -28,6 +28,9 @@ pub enum CompileError {
     #[error("Package `{0}` is expected to have a `main` function but it does not")]
     MissingMainFunction(CrateName),
 
+    #[error("Package `{package}` pins the standard library to revision `{required}` but this version of nargo provides revision `{available}`")]
+    UnavailableStdlibRevision { package: CrateName, required: String, available: String },
+
     /// Errors encountered while compiling the Noir program.
     /// These errors are already written to stderr.
     #[error("Aborting due to {} previous error{}", .0.error_count, if .0.error_count == 1 { "" } else { "s" })]
@@ -224,6 +227,19 @@ fn extract_message_from_error(
     }
 }
 
+/// Returns the source location of the opcode which caused `nargo_err`, if it can be found in `debug`.
+pub fn try_to_locate_runtime_error(
+    nargo_err: &NargoError<FieldElement>,
+    debug: &[DebugInfo],
+) -> Option<Location> {
+    match nargo_err {
+        NargoError::ExecutionError(execution_error) => {
+            extract_locations_from_error(execution_error, debug)?.last().copied()
+        }
+        _ => None,
+    }
+}
+
 /// Tries to generate a runtime diagnostic from a nargo error. It will successfully do so if it's a runtime error with a call stack.
 pub fn try_to_diagnose_runtime_error(
     nargo_err: &NargoError<FieldElement>,
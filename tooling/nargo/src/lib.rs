@@ -20,10 +20,17 @@ use std::{
 };
 
 use fm::{FileManager, FILE_EXTENSION};
-use noirc_driver::{add_dep, prepare_crate, prepare_dependency};
+use noirc_driver::{
+    add_dep, build_package_crate_file, link_to_package_crate, link_to_prelude_crate, prepare_crate,
+    prepare_dependency,
+};
 use noirc_frontend::{
+    ast::FunctionDefinition,
     graph::{CrateId, CrateName},
     hir::{def_map::parse_file, Context, ParsedFiles},
+    parse_program,
+    parser::ItemKind,
+    ParsedModule,
 };
 use package::{Dependency, Package};
 use rayon::prelude::*;
@@ -41,6 +48,12 @@ pub fn prepare_dependencies(
             Dependency::Remote { package } | Dependency::Local { package } => {
                 let crate_id = prepare_dependency(context, &package.entry_path);
                 add_dep(context, parent_crate, crate_id, dep_name.clone());
+                if let Some(prelude_path) = &package.prelude_path {
+                    link_to_prelude_crate(context, crate_id, prelude_path);
+                }
+                if package.version.is_some() {
+                    link_to_package_crate(context, crate_id, &package.version_crate_path());
+                }
                 prepare_dependencies(context, crate_id, &package.dependencies);
             }
         }
@@ -96,34 +109,56 @@ fn insert_all_files_for_package_into_file_manager(
         .parent()
         .unwrap_or_else(|| panic!("The entry path is expected to be a single file within a directory and so should have a parent {:?}", package.entry_path));
 
-    for entry in WalkDir::new(entry_path_parent) {
-        let Ok(entry) = entry else {
-            continue;
-        };
-
-        if !entry.file_type().is_file() {
-            continue;
-        }
+    // Entries are sorted so that files are always added to the file manager in the same order.
+    let paths: Vec<PathBuf> = WalkDir::new(entry_path_parent)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| entry.path().extension().map_or(false, |ext| ext == FILE_EXTENSION))
+        .map(|entry| entry.into_path())
+        // Avoid reading the source if the file is already there
+        .filter(|path| !file_manager.has_file(path))
+        .collect();
 
-        if !entry.path().extension().map_or(false, |ext| ext == FILE_EXTENSION) {
-            continue;
-        };
+    // Reads are spread over rayon's global thread pool, which is bounded by the number of available cores.
+    // Collecting an indexed parallel iterator keeps the sources in the same order as `paths`.
+    let sources: Vec<String> = paths
+        .par_iter()
+        .map(|path| {
+            if let Some(src) = overrides.get(path.as_path()) {
+                src.to_string()
+            } else {
+                std::fs::read_to_string(path)
+                    .unwrap_or_else(|_| panic!("could not read file {:?} into string", path))
+            }
+        })
+        .collect();
 
-        let path = entry.into_path();
+    for (path, source) in paths.iter().zip(sources) {
+        file_manager.add_file_with_source(path.as_path(), source);
+    }
 
-        // Avoid reading the source if the file is already there
-        if file_manager.has_file(&path) {
-            continue;
+    // The prelude file may live outside of the package's source directory.
+    if let Some(prelude_path) = &package.prelude_path {
+        if !file_manager.has_file(prelude_path) {
+            let source = if let Some(src) = overrides.get(prelude_path.as_path()) {
+                src.to_string()
+            } else {
+                std::fs::read_to_string(prelude_path).unwrap_or_else(|_| {
+                    panic!("could not read file {:?} into string", prelude_path)
+                })
+            };
+            file_manager.add_file_with_source(prelude_path, source);
         }
+    }
 
-        let source = if let Some(src) = overrides.get(path.as_path()) {
-            src.to_string()
-        } else {
-            std::fs::read_to_string(path.as_path())
-                .unwrap_or_else(|_| panic!("could not read file {:?} into string", path))
-        };
-
-        file_manager.add_file_with_source(path.as_path(), source);
+    // The package's version is exposed to its code through a synthetic crate which doesn't exist on disk.
+    if let Some(version) = &package.version {
+        file_manager.add_file_with_source_canonical_path(
+            &package.version_crate_path(),
+            build_package_crate_file(version),
+        );
     }
 
     insert_all_files_for_packages_dependencies_into_file_manager(
@@ -171,16 +206,66 @@ pub fn parse_all(file_manager: &FileManager) -> ParsedFiles {
         .collect()
 }
 
+/// Replaces the bodies of all functions named in `function_names` with a call to `std::mem::zeroed`,
+/// tagging them with `stub` so that they're recognizable within contract artifacts.
+///
+/// Methods within `impl` blocks are left untouched.
+pub fn stub_functions(parsed_files: &mut ParsedFiles, function_names: &[String]) {
+    if function_names.is_empty() {
+        return;
+    }
+
+    let (mut stub_module, errors) = parse_program("#['stub]\nfn stub() { std::mem::zeroed() }");
+    assert!(errors.is_empty(), "stub function should parse");
+    let ItemKind::Function(stub) = stub_module.items.remove(0).kind else {
+        unreachable!("stub should be a function");
+    };
+
+    for (parsed_module, _) in parsed_files.values_mut() {
+        stub_module_functions(parsed_module, function_names, &stub.def);
+    }
+}
+
+fn stub_module_functions(
+    parsed_module: &mut ParsedModule,
+    function_names: &[String],
+    stub: &FunctionDefinition,
+) {
+    for item in &mut parsed_module.items {
+        match &mut item.kind {
+            ItemKind::Function(function)
+                if function_names.contains(&function.def.name.0.contents) =>
+            {
+                function.def.body = stub.body.clone();
+                function.def.attributes.secondary.extend(stub.attributes.secondary.iter().cloned());
+            }
+            ItemKind::Submodules(submodule) => {
+                stub_module_functions(&mut submodule.contents, function_names, stub);
+            }
+            _ => (),
+        }
+    }
+}
+
 pub fn prepare_package<'file_manager, 'parsed_files>(
     file_manager: &'file_manager FileManager,
     parsed_files: &'parsed_files ParsedFiles,
     package: &Package,
 ) -> (Context<'file_manager, 'parsed_files>, CrateId) {
     let mut context = Context::from_ref_file_manager(file_manager, parsed_files);
+    context.lint_levels = package.lints.clone();
 
     let crate_id = prepare_crate(&mut context, &package.entry_path);
 
     prepare_dependencies(&mut context, crate_id, &package.dependencies);
 
+    if let Some(prelude_path) = &package.prelude_path {
+        link_to_prelude_crate(&mut context, crate_id, prelude_path);
+    }
+
+    if package.version.is_some() {
+        link_to_package_crate(&mut context, crate_id, &package.version_crate_path());
+    }
+
     (context, crate_id)
 }
@@ -0,0 +1,89 @@
+use std::time::{Duration, Instant};
+
+use acvm::{
+    acir::{circuit::ExpressionWidth, native_types::WitnessMap},
+    compiler::OptimizationLevel,
+    BlackBoxFunctionSolver, FieldElement,
+};
+use noirc_driver::{compile_no_check, CompileOptions};
+use noirc_errors::FileDiagnostic;
+use noirc_frontend::{hir::Context, node_interner::FuncId};
+
+use crate::foreign_calls::DefaultForeignCallExecutor;
+
+use super::{execute_program, transform_program_with_optimization_level};
+
+pub enum BenchStatus {
+    Completed(BenchReport),
+    /// The benchmark function takes arguments, which benchmarks can't be given.
+    HasArguments,
+    CompileError(FileDiagnostic),
+}
+
+/// The measurements taken while running a benchmark.
+pub struct BenchReport {
+    /// The number of opcodes across all of the benchmark's circuits, once optimized.
+    pub opcodes: usize,
+    pub samples: Vec<BenchSample>,
+    /// The error which executing the benchmark failed with, if it did.
+    ///
+    /// A failing execution is still timed, as benchmarks aren't meant to check correctness.
+    pub execution_error: Option<String>,
+}
+
+/// The wall-clock time taken by each phase of a single run of a benchmark.
+#[derive(Debug, Clone, Copy)]
+pub struct BenchSample {
+    /// Compiling and optimizing the benchmark function.
+    pub compile: Duration,
+    /// Solving the witness of the compiled program.
+    pub execute: Duration,
+}
+
+/// Compiles and executes the function `bench_function` `samples` times, timing each phase.
+pub fn run_bench<B: BlackBoxFunctionSolver<FieldElement>>(
+    blackbox_solver: &B,
+    context: &mut Context,
+    bench_function: FuncId,
+    samples: usize,
+    expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+    config: &CompileOptions,
+) -> BenchStatus {
+    let has_arguments =
+        !context.def_interner.function_meta(&bench_function).function_signature().0.is_empty();
+    if has_arguments {
+        return BenchStatus::HasArguments;
+    }
+
+    let mut report = BenchReport { opcodes: 0, samples: Vec::new(), execution_error: None };
+    for _ in 0..samples {
+        let compile_start = Instant::now();
+        let compiled_program = match compile_no_check(context, config, bench_function, None, true) {
+            Ok(compiled_program) => compiled_program,
+            Err(err) => return BenchStatus::CompileError(err.into()),
+        };
+        let compiled_program = transform_program_with_optimization_level(
+            compiled_program,
+            expression_width,
+            optimization_level,
+        );
+        let compile = compile_start.elapsed();
+
+        let execute_start = Instant::now();
+        let result = execute_program(
+            &compiled_program.program,
+            WitnessMap::new(),
+            blackbox_solver,
+            &mut DefaultForeignCallExecutor::<FieldElement>::new(false, None, None, None),
+        );
+        let execute = execute_start.elapsed();
+
+        report.opcodes =
+            compiled_program.program.functions.iter().map(|circuit| circuit.opcodes.len()).sum();
+        report.execution_error = result.err().map(|err| err.to_string());
+        report.samples.push(BenchSample { compile, execute });
+    }
+
+    BenchStatus::Completed(report)
+}
@@ -1,4 +1,4 @@
-use acvm::compiler::CircuitSimulator;
+use acvm::{acir::circuit::Program, compiler::CircuitSimulator, FieldElement};
 use noirc_driver::{CompiledProgram, ErrorsAndWarnings};
 use noirc_errors::{CustomDiagnostic, FileDiagnostic};
 
@@ -19,3 +19,42 @@ pub fn check_program(compiled_program: &CompiledProgram) -> Result<(), ErrorsAnd
     }
     Ok(())
 }
+
+/// Counts the assertions in each of the program's circuits, that is the opcodes which carry an assertion message.
+///
+/// Constraints without a message can't be told apart from those introduced by the compiler, so they aren't counted.
+pub fn count_assertions(program: &Program<FieldElement>) -> Vec<usize> {
+    program.functions.iter().map(|circuit| circuit.assert_messages.len()).collect()
+}
+
+/// Checks that none of the program's circuits have fewer assertions than `unoptimized_counts`,
+/// as counted by [`count_assertions`] before the program was optimized.
+pub fn check_assertions_preserved(
+    program: &Program<FieldElement>,
+    names: &[String],
+    unoptimized_counts: &[usize],
+) -> Result<(), ErrorsAndWarnings> {
+    let errors: Vec<_> = count_assertions(program)
+        .into_iter()
+        .zip(unoptimized_counts.iter().copied())
+        .enumerate()
+        .filter(|(_, (optimized, unoptimized))| optimized < unoptimized)
+        .map(|(i, (optimized, unoptimized))| {
+            // Contract functions reused from a previous artifact don't have the names of their circuits.
+            let name = names.get(i).cloned().unwrap_or_else(|| format!("#{i}"));
+            FileDiagnostic {
+                file_id: fm::FileId::dummy(),
+                diagnostic: CustomDiagnostic::from_message(&format!(
+                    "Optimization removed {} of the {unoptimized} assertions in circuit \"{name}\"",
+                    unoptimized - optimized,
+                )),
+            }
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
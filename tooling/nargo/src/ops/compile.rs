@@ -1,7 +1,9 @@
 use fm::FileManager;
 use noirc_driver::{
     link_to_debug_crate, CompilationResult, CompileOptions, CompiledContract, CompiledProgram,
+    MessageFormat,
 };
+use noirc_errors::FileDiagnostic;
 use noirc_frontend::debug::DebugInstrumenter;
 use noirc_frontend::hir::ParsedFiles;
 
@@ -37,7 +39,7 @@ pub fn compile_workspace(
         .collect();
     let contract_results: Vec<CompilationResult<CompiledContract>> = contract_packages
         .par_iter()
-        .map(|package| compile_contract(file_manager, parsed_files, package, compile_options))
+        .map(|package| compile_contract(file_manager, parsed_files, package, compile_options, None))
         .collect();
 
     // Collate any warnings/errors which were encountered during compilation.
@@ -90,7 +92,16 @@ pub fn compile_program_with_debug_instrumenter(
     context.debug_instrumenter = debug_instrumenter;
     context.package_build_path = workspace.package_build_path(package);
 
-    noirc_driver::compile_main(&mut context, crate_id, compile_options, cached_program)
+    // An `--entry` passed on the command line takes precedence over the one in the package's manifest.
+    if compile_options.entry_function.is_none() && package.entry_function.is_some() {
+        let compile_options = CompileOptions {
+            entry_function: package.entry_function.clone(),
+            ..compile_options.clone()
+        };
+        noirc_driver::compile_main(&mut context, crate_id, &compile_options, cached_program)
+    } else {
+        noirc_driver::compile_main(&mut context, crate_id, compile_options, cached_program)
+    }
 }
 
 #[tracing::instrument(level = "trace", skip_all, fields(package_name = package.name.to_string()))]
@@ -99,9 +110,10 @@ pub fn compile_contract(
     parsed_files: &ParsedFiles,
     package: &Package,
     compile_options: &CompileOptions,
+    cached_contract: Option<CompiledContract>,
 ) -> CompilationResult<CompiledContract> {
     let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
-    noirc_driver::compile_contract(&mut context, crate_id, compile_options)
+    noirc_driver::compile_contract(&mut context, crate_id, compile_options, cached_contract)
 }
 
 /// Constructs a single `CompilationResult` for a collection of `CompilationResult`s, merging the set of warnings/errors.
@@ -132,22 +144,29 @@ pub fn report_errors<T>(
     file_manager: &FileManager,
     deny_warnings: bool,
     silence_warnings: bool,
+    message_format: MessageFormat,
 ) -> Result<T, CompileError> {
-    let (t, warnings) = result.map_err(|errors| {
-        noirc_errors::reporter::report_all(
-            file_manager.as_file_map(),
-            &errors,
-            deny_warnings,
-            silence_warnings,
-        )
-    })?;
-
-    noirc_errors::reporter::report_all(
-        file_manager.as_file_map(),
-        &warnings,
-        deny_warnings,
-        silence_warnings,
-    );
+    let report = |diagnostics: &[FileDiagnostic]| {
+        let files = file_manager.as_file_map();
+        match message_format {
+            MessageFormat::Human => noirc_errors::reporter::report_all(
+                files,
+                diagnostics,
+                deny_warnings,
+                silence_warnings,
+            ),
+            MessageFormat::Json => noirc_errors::reporter::report_all_json(
+                files,
+                diagnostics,
+                deny_warnings,
+                silence_warnings,
+            ),
+        }
+    };
+
+    let (t, warnings) = result.map_err(|errors| report(&errors))?;
+
+    report(&warnings);
 
     Ok(t)
 }
@@ -1,17 +1,25 @@
-pub use self::check::check_program;
+pub use self::bench::{run_bench, BenchReport, BenchSample, BenchStatus};
+pub use self::check::{check_assertions_preserved, check_program, count_assertions};
 pub use self::compile::{
     collect_errors, compile_contract, compile_program, compile_program_with_debug_instrumenter,
     compile_workspace, report_errors,
 };
 pub use self::execute::{execute_program, execute_program_with_profiling};
 pub use self::optimize::{optimize_contract, optimize_program};
-pub use self::transform::{transform_contract, transform_program};
+pub use self::transform::{
+    transform_contract, transform_contract_with_optimization_level,
+    transform_contract_with_profile, transform_program, transform_program_with_optimization_level,
+    transform_program_with_profile,
+};
 
 pub use self::test::{run_test, TestStatus};
+pub use self::trim_paths::trim_debug_paths;
 
+mod bench;
 mod check;
 mod compile;
 mod execute;
 mod optimize;
 mod test;
 mod transform;
+mod trim_paths;
@@ -8,16 +8,17 @@ use acvm::{
     pwg::ForeignCallWaitInfo,
     AcirField, BlackBoxFunctionSolver, FieldElement,
 };
+use fm::FileMap;
 use noirc_abi::Abi;
 use noirc_driver::{compile_no_check, CompileError, CompileOptions};
-use noirc_errors::{debug_info::DebugInfo, FileDiagnostic};
+use noirc_errors::{debug_info::DebugInfo, reporter::format_location, FileDiagnostic};
 use noirc_frontend::hir::{def_map::TestFunction, Context};
 use noirc_printable_type::ForeignCallError;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    errors::try_to_diagnose_runtime_error,
+    errors::{try_to_diagnose_runtime_error, try_to_locate_runtime_error},
     foreign_calls::{
         mocker::MockForeignCallExecutor, print::PrintForeignCallExecutor,
         rpc::RPCForeignCallExecutor, DefaultForeignCallExecutor, ForeignCall, ForeignCallExecutor,
@@ -82,6 +83,7 @@ pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
                     compiled_program.abi,
                     compiled_program.debug,
                     circuit_execution,
+                    context.file_manager.as_file_map(),
                 );
 
                 let ignore_foreign_call_failures =
@@ -175,6 +177,7 @@ fn test_status_program_compile_pass(
     abi: Abi,
     debug: Vec<DebugInfo>,
     circuit_execution: Result<WitnessStack<FieldElement>, NargoError<FieldElement>>,
+    file_map: &FileMap,
 ) -> TestStatus {
     let circuit_execution_err = match circuit_execution {
         // Circuit execution was successful; ie no errors or unsatisfied constraints
@@ -197,10 +200,13 @@ fn test_status_program_compile_pass(
     let diagnostic = try_to_diagnose_runtime_error(&circuit_execution_err, &abi, &debug);
     let test_should_have_passed = !test_function.should_fail();
     if test_should_have_passed {
-        return TestStatus::Fail {
-            message: circuit_execution_err.to_string(),
-            error_diagnostic: diagnostic,
-        };
+        let mut message = circuit_execution_err.to_string();
+        if let Some(location) = try_to_locate_runtime_error(&circuit_execution_err, &debug)
+            .and_then(|location| format_location(file_map, location))
+        {
+            message.push_str(&format!("\n  --> {location}"));
+        }
+        return TestStatus::Fail { message, error_diagnostic: diagnostic };
     }
 
     check_expected_failure_message(
@@ -1,5 +1,6 @@
 use acvm::{
     acir::circuit::{ExpressionWidth, Program},
+    compiler::{OptimizationLevel, OptimizationProfile},
     FieldElement,
 };
 use iter_extended::vecmap;
@@ -7,13 +8,45 @@ use noirc_driver::{CompiledContract, CompiledProgram};
 use noirc_errors::debug_info::DebugInfo;
 
 pub fn transform_program(
+    compiled_program: CompiledProgram,
+    expression_width: ExpressionWidth,
+) -> CompiledProgram {
+    transform_program_with_optimization_level(
+        compiled_program,
+        expression_width,
+        OptimizationLevel::default(),
+    )
+}
+
+pub fn transform_program_with_optimization_level(
+    mut compiled_program: CompiledProgram,
+    expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+) -> CompiledProgram {
+    compiled_program.program = transform_program_internal(
+        compiled_program.program,
+        &mut compiled_program.debug,
+        expression_width,
+        optimization_level,
+        None,
+    );
+    compiled_program
+}
+
+/// Same as [`transform_program_with_optimization_level`], but records the time spent in each
+/// optimization pass in `profile`.
+pub fn transform_program_with_profile(
     mut compiled_program: CompiledProgram,
     expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+    profile: &mut OptimizationProfile,
 ) -> CompiledProgram {
     compiled_program.program = transform_program_internal(
         compiled_program.program,
         &mut compiled_program.debug,
         expression_width,
+        optimization_level,
+        Some(profile),
     );
     compiled_program
 }
@@ -21,10 +54,47 @@ pub fn transform_program(
 pub fn transform_contract(
     contract: CompiledContract,
     expression_width: ExpressionWidth,
+) -> CompiledContract {
+    transform_contract_with_optimization_level(
+        contract,
+        expression_width,
+        OptimizationLevel::default(),
+    )
+}
+
+pub fn transform_contract_with_optimization_level(
+    contract: CompiledContract,
+    expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+) -> CompiledContract {
+    transform_contract_internal(contract, expression_width, optimization_level, None)
+}
+
+/// Same as [`transform_contract_with_optimization_level`], but records the time spent in each
+/// optimization pass in `profile`.
+pub fn transform_contract_with_profile(
+    contract: CompiledContract,
+    expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+    profile: &mut OptimizationProfile,
+) -> CompiledContract {
+    transform_contract_internal(contract, expression_width, optimization_level, Some(profile))
+}
+
+fn transform_contract_internal(
+    contract: CompiledContract,
+    expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+    mut profile: Option<&mut OptimizationProfile>,
 ) -> CompiledContract {
     let functions = vecmap(contract.functions, |mut func| {
-        func.bytecode =
-            transform_program_internal(func.bytecode, &mut func.debug, expression_width);
+        func.bytecode = transform_program_internal(
+            func.bytecode,
+            &mut func.debug,
+            expression_width,
+            optimization_level,
+            profile.as_deref_mut(),
+        );
 
         func
     });
@@ -36,6 +106,8 @@ fn transform_program_internal(
     mut program: Program<FieldElement>,
     debug: &mut [DebugInfo],
     expression_width: ExpressionWidth,
+    optimization_level: OptimizationLevel,
+    mut profile: Option<&mut OptimizationProfile>,
 ) -> Program<FieldElement> {
     let functions = std::mem::take(&mut program.functions);
 
@@ -43,8 +115,21 @@ fn transform_program_internal(
         .into_iter()
         .enumerate()
         .map(|(i, function)| {
-            let (optimized_circuit, location_map) =
-                acvm::compiler::compile(function, expression_width);
+            let (optimized_circuit, location_map) = match profile.as_deref_mut() {
+                Some(profile) => acvm::compiler::compile_with_profile(
+                    function,
+                    expression_width,
+                    optimization_level,
+                    profile,
+                ),
+                None => acvm::compiler::compile_with_optimization_level(
+                    function,
+                    expression_width,
+                    optimization_level,
+                ),
+            };
+            // The optimizer never renumbers witnesses, only opcodes, so remapping the opcode locations
+            // is all that's needed to keep the debug info pointing at the right source.
             debug[i].update_acir(location_map);
             optimized_circuit
         })
@@ -53,3 +138,59 @@ fn transform_program_internal(
     program.functions = optimized_functions;
     program
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::{
+        acir::{
+            circuit::{
+                opcodes::{BlackBoxFuncCall, FunctionInput},
+                Circuit, ExpressionWidth, Opcode, OpcodeLocation, Program,
+            },
+            native_types::Witness,
+        },
+        compiler::OptimizationLevel,
+        FieldElement,
+    };
+    use noirc_errors::{debug_info::DebugInfo, Location, Span};
+
+    use super::transform_program_internal;
+
+    fn range(witness: u32, num_bits: u32) -> Opcode<FieldElement> {
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput::witness(Witness(witness), num_bits),
+        })
+    }
+
+    fn location(start: u32) -> Vec<Location> {
+        vec![Location::new(Span::from(start..start + 10), fm::FileId::dummy())]
+    }
+
+    #[test]
+    fn debug_locations_follow_opcodes_removed_by_optimization() {
+        // The first range constraint is implied by the second, so the optimizer removes it.
+        let opcodes = vec![range(1, 32), range(1, 16), range(2, 8)];
+        let circuit = Circuit { current_witness_index: 2, opcodes, ..Circuit::default() };
+        let program = Program { functions: vec![circuit], ..Program::default() };
+
+        let locations: BTreeMap<_, _> = (0..3)
+            .map(|index| (OpcodeLocation::Acir(index), location(index as u32 * 100)))
+            .collect();
+        let mut debug = vec![DebugInfo { locations, ..DebugInfo::default() }];
+
+        let program = transform_program_internal(
+            program,
+            &mut debug,
+            ExpressionWidth::Bounded { width: 4 },
+            OptimizationLevel::default(),
+            None,
+        );
+
+        assert_eq!(program.functions[0].opcodes, vec![range(1, 16), range(2, 8)]);
+        assert_eq!(debug[0].opcode_location(&OpcodeLocation::Acir(0)), Some(location(100)));
+        assert_eq!(debug[0].opcode_location(&OpcodeLocation::Acir(1)), Some(location(200)));
+        assert_eq!(debug[0].opcode_location(&OpcodeLocation::Acir(2)), None);
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use fm::FileId;
+use noirc_driver::DebugFile;
+
+use crate::package::{Dependency, Package};
+
+/// Rewrites the absolute source paths in `file_map` so that they don't depend on where `package` was built.
+///
+/// Files of `package` are made relative to its root directory, and files of its dependencies are made
+/// relative to the dependency's root directory, under a directory named after the dependency.
+/// Paths which are already relative, such as those of the standard library, are left as they are.
+pub fn trim_debug_paths(file_map: &mut BTreeMap<FileId, DebugFile>, package: &Package) {
+    let mut roots = vec![(package.root_dir.clone(), PathBuf::new())];
+    collect_dependency_roots(package, &mut roots);
+    // Dependencies may be nested within the package, so the most specific root has to be tried first.
+    roots.sort_by_key(|(root_dir, _)| std::cmp::Reverse(root_dir.components().count()));
+
+    for file in file_map.values_mut() {
+        if let Some(trimmed_path) = trim_path(&file.path, &roots) {
+            file.path = trimmed_path;
+        }
+    }
+}
+
+fn trim_path(path: &Path, roots: &[(PathBuf, PathBuf)]) -> Option<PathBuf> {
+    if path.is_relative() {
+        return None;
+    }
+    roots.iter().find_map(|(root_dir, prefix)| {
+        path.strip_prefix(root_dir).ok().map(|relative_path| prefix.join(relative_path))
+    })
+}
+
+fn collect_dependency_roots(package: &Package, roots: &mut Vec<(PathBuf, PathBuf)>) {
+    for dependency in package.dependencies.values() {
+        let dependency = match dependency {
+            Dependency::Local { package } | Dependency::Remote { package } => package,
+        };
+        if roots.iter().any(|(root_dir, _)| root_dir == &dependency.root_dir) {
+            continue;
+        }
+        let prefix = match &dependency.version {
+            Some(version) => format!("{}-{version}", dependency.name),
+            None => dependency.name.to_string(),
+        };
+        roots.push((dependency.root_dir.clone(), PathBuf::from(prefix)));
+        collect_dependency_roots(dependency, roots);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+
+    use fm::{FileId, FileManager};
+    use noirc_driver::DebugFile;
+
+    use crate::package::{CompileSettings, Dependency, Package, PackageType};
+
+    use super::trim_debug_paths;
+
+    fn package(name: &str, root_dir: &str, dependencies: Vec<Package>) -> Package {
+        Package {
+            version: None,
+            compiler_required_version: None,
+            root_dir: PathBuf::from(root_dir),
+            package_type: PackageType::Binary,
+            entry_path: Path::new(root_dir).join("src/main.nr"),
+            name: name.parse().unwrap(),
+            dependencies: dependencies
+                .into_iter()
+                .map(|package| (package.name.clone(), Dependency::Remote { package }))
+                .collect(),
+            expression_width: None,
+            stdlib_revision: None,
+            prelude_path: None,
+            entry_function: None,
+            lints: BTreeMap::new(),
+            include: None,
+            exclude: Vec::new(),
+            compile: CompileSettings::default(),
+        }
+    }
+
+    #[test]
+    fn trims_package_and_dependency_paths() {
+        let dependency = package("dep", "/home/user/nargo/github.com/org/dep", Vec::new());
+        let package = package("main", "/home/user/project", vec![dependency]);
+
+        let paths = [
+            "/home/user/project/src/main.nr",
+            "/home/user/nargo/github.com/org/dep/src/lib.nr",
+            "std/lib.nr",
+            "/elsewhere/file.nr",
+        ];
+        let mut file_manager = FileManager::new(Path::new("/"));
+        let mut file_map: BTreeMap<FileId, DebugFile> = paths
+            .iter()
+            .map(|path| {
+                let file_id = file_manager
+                    .add_file_with_source_canonical_path(Path::new(path), String::new())
+                    .unwrap();
+                (file_id, DebugFile { source: String::new(), path: PathBuf::from(path) })
+            })
+            .collect();
+
+        trim_debug_paths(&mut file_map, &package);
+
+        let trimmed: Vec<&Path> = file_map.values().map(|file| file.path.as_path()).collect();
+        assert_eq!(
+            trimmed,
+            vec![
+                Path::new("src/main.nr"),
+                Path::new("dep/src/lib.nr"),
+                Path::new("std/lib.nr"),
+                Path::new("/elsewhere/file.nr"),
+            ]
+        );
+        assert!(file_map.values().all(|file| !file.path.starts_with("/home/user")));
+    }
+}
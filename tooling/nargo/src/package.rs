@@ -1,11 +1,15 @@
 use std::{collections::BTreeMap, fmt::Display, path::PathBuf};
 
 use acvm::acir::circuit::ExpressionWidth;
+use noirc_driver::CompileOptions;
 pub use noirc_driver::CrateName;
+pub use noirc_errors::LintLevel;
+use noirc_frontend::graph::PACKAGE_CRATE_NAME;
+use serde::{Deserialize, Serialize};
 
 use crate::constants::PROVER_INPUT_FILE;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PackageType {
     Library,
     Binary,
@@ -22,7 +26,59 @@ impl Display for PackageType {
     }
 }
 
-#[derive(Clone)]
+/// Defaults for compiler flags, as set in the `[workspace.compile]` or `[package.compile]` section of `Nargo.toml`.
+///
+/// Flags passed on the command line take precedence over these.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompileSettings {
+    pub deny_warnings: Option<bool>,
+    pub silence_warnings: Option<bool>,
+    pub show_ssa: Option<bool>,
+    pub show_brillig: Option<bool>,
+    pub print_acir: Option<bool>,
+    pub print_acir_stats: Option<bool>,
+    pub force_brillig: Option<bool>,
+    pub trim_paths: Option<bool>,
+}
+
+impl CompileSettings {
+    /// Returns these settings, falling back to `defaults` for any which aren't set.
+    pub fn or(&self, defaults: &CompileSettings) -> CompileSettings {
+        CompileSettings {
+            deny_warnings: self.deny_warnings.or(defaults.deny_warnings),
+            silence_warnings: self.silence_warnings.or(defaults.silence_warnings),
+            show_ssa: self.show_ssa.or(defaults.show_ssa),
+            show_brillig: self.show_brillig.or(defaults.show_brillig),
+            print_acir: self.print_acir.or(defaults.print_acir),
+            print_acir_stats: self.print_acir_stats.or(defaults.print_acir_stats),
+            force_brillig: self.force_brillig.or(defaults.force_brillig),
+            trim_paths: self.trim_paths.or(defaults.trim_paths),
+        }
+    }
+
+    /// Returns `options` with any flag which wasn't passed on the command line taken from these settings.
+    ///
+    /// As the flags can only be switched on from the command line, a flag which was passed always wins.
+    pub fn apply_to(&self, options: &CompileOptions) -> CompileOptions {
+        let mut options = options.clone();
+        // `deny_warnings` and `silence_warnings` conflict, so a default is only applied if the other wasn't passed.
+        if !options.silence_warnings {
+            options.deny_warnings |= self.deny_warnings.unwrap_or_default();
+        }
+        if !options.deny_warnings {
+            options.silence_warnings |= self.silence_warnings.unwrap_or_default();
+        }
+        options.show_ssa |= self.show_ssa.unwrap_or_default();
+        options.show_brillig |= self.show_brillig.unwrap_or_default();
+        options.print_acir |= self.print_acir.unwrap_or_default();
+        options.print_acir_stats |= self.print_acir_stats.unwrap_or_default();
+        options.force_brillig |= self.force_brillig.unwrap_or_default();
+        options.trim_paths |= self.trim_paths.unwrap_or_default();
+        options
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub enum Dependency {
     Local { package: Package },
     Remote { package: Package },
@@ -42,7 +98,7 @@ impl Dependency {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Package {
     pub version: Option<String>,
     // A semver string which specifies the compiler version required to compile this package
@@ -53,6 +109,22 @@ pub struct Package {
     pub name: CrateName,
     pub dependencies: BTreeMap<CrateName, Dependency>,
     pub expression_width: Option<ExpressionWidth>,
+    /// The git revision of the standard library which this package must be compiled against.
+    pub stdlib_revision: Option<String>,
+    /// A Noir file whose public items are imported into every module of the package.
+    pub prelude_path: Option<PathBuf>,
+    /// The function to compile as the program's entry point instead of `main`.
+    pub entry_function: Option<String>,
+    /// The levels of lints set in the `[lints]` section of the manifest, keyed by lint name.
+    pub lints: BTreeMap<String, LintLevel>,
+    /// The paths, relative to `root_dir`, which `nargo package` restricts the package's files to.
+    /// All files are included if this isn't set.
+    pub include: Option<Vec<PathBuf>>,
+    /// The paths, relative to `root_dir`, which `nargo package` leaves out of the package.
+    pub exclude: Vec<PathBuf>,
+    /// Defaults for compiler flags, from the package's `[package.compile]` section falling back to
+    /// the `[workspace.compile]` section of its workspace.
+    pub compile: CompileSettings,
 }
 
 impl Package {
@@ -73,4 +145,11 @@ impl Package {
     pub fn is_library(&self) -> bool {
         self.package_type == PackageType::Library
     }
+
+    /// Path of the synthetic file exposing the package's version to its code as `PACKAGE_VERSION`.
+    ///
+    /// This file doesn't exist on disk and is only present in the file manager if the package has a version.
+    pub fn version_crate_path(&self) -> PathBuf {
+        self.root_dir.join(PACKAGE_CRATE_NAME).join("lib.nr")
+    }
 }
@@ -4,6 +4,7 @@
 // - library will be default
 
 use std::{
+    collections::BTreeMap,
     iter::{once, Once},
     path::PathBuf,
     slice,
@@ -11,13 +12,14 @@ use std::{
 
 use fm::FileManager;
 use noirc_driver::file_manager_with_stdlib;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     constants::{CONTRACT_DIR, EXPORT_DIR, PROOFS_DIR, TARGET_DIR},
-    package::Package,
+    package::{CompileSettings, CrateName, Package},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Workspace {
     pub root_dir: PathBuf,
     pub members: Vec<Package>,
@@ -25,14 +27,32 @@ pub struct Workspace {
     pub selected_package_index: Option<usize>,
     /// If we could not resolve the workspace we would inform the user we have assumed it (ie. from lsp file path given)
     pub is_assumed: bool,
+    /// Explicit artifact paths for packages, as mapped in the `[artifacts]` section of the workspace's manifest.
+    pub artifact_paths: BTreeMap<CrateName, PathBuf>,
+    /// Defaults for compiler flags, as set in the `[workspace.compile]` section of the workspace's manifest,
+    /// or in the `[package.compile]` section if the workspace is a single package.
+    pub compile: CompileSettings,
 }
 
 impl Workspace {
     pub fn package_build_path(&self, package: &Package) -> PathBuf {
+        if let Some(artifact_path) = self.package_artifact_path(package) {
+            return artifact_path.clone();
+        }
+        self.default_package_build_path(package)
+    }
+
+    /// Returns the path to the package's artifact within the target directory, ignoring any mapping in the manifest.
+    pub fn default_package_build_path(&self, package: &Package) -> PathBuf {
         let name: String = package.name.clone().into();
         self.target_directory_path().join(name).with_extension("json")
     }
 
+    /// Returns the path which the package's artifact is written to, if it was mapped in the manifest.
+    pub fn package_artifact_path(&self, package: &Package) -> Option<&PathBuf> {
+        self.artifact_paths.get(&package.name)
+    }
+
     pub fn contracts_directory_path(&self, package: &Package) -> PathBuf {
         let name: String = package.name.clone().into();
         self.root_dir.join(CONTRACT_DIR).join(name)
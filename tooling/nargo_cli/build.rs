@@ -371,19 +371,25 @@ fn generate_compile_success_empty_tests(test_file: &mut File, test_data_dir: &Pa
         if !output.status.success() {{
             panic!("`nargo info` failed with: {}", String::from_utf8(output.stderr).unwrap_or_default());
         }}
+
+        // With `--json` every line of stdout is a JSON object: the diagnostics, the report and a final status.
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let lines: Vec<serde_json::Value> = stdout.lines().map(|line| {
+            serde_json::from_str(line).unwrap_or_else(|e| {{
+                panic!("JSON was not well-formatted {:?}\n\n{:?}", e, line)
+            }})
+        }).collect();
         "#.to_string();
 
         if !TESTS_WITH_EXPECTED_WARNINGS.contains(&test_name.as_str()) {
             assert_zero_opcodes += r#"
-            nargo.assert().success().stderr(predicate::str::contains("warning:").not());
+            assert!(lines.iter().all(|line| line["level"] != "warning"), "unexpected warnings:\n{stdout}");
             "#;
         }
 
         assert_zero_opcodes += r#"
         // `compile_success_empty` tests should be able to compile down to an empty circuit.
-        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {{
-            panic!("JSON was not well-formatted {:?}\n\n{:?}", e, std::str::from_utf8(&output.stdout))
-        }});
+        let json = lines.iter().find(|line| line.get("programs").is_some()).expect("`nargo info` should report the programs");
         let num_opcodes = &json["programs"][0]["functions"][0]["opcodes"];
         assert_eq!(num_opcodes.as_u64().expect("number of opcodes should fit in a u64"), 0);
         "#;
@@ -0,0 +1,184 @@
+use std::path::{Component, Path, PathBuf};
+
+use clap::Args;
+use nargo::package::CrateName;
+use nargo_toml::get_package_manifest;
+use semver::VersionReq;
+use toml_edit::InlineTable;
+
+use crate::errors::CliError;
+
+use super::fs::manifest::{dependencies_table, read_manifest_document, write_manifest_document};
+use super::registry::{fetch_package, registry_url, RegistryArgs};
+use super::NargoConfig;
+
+/// Add a dependency to the package's Nargo.toml
+#[derive(Debug, Clone, Args)]
+pub(crate) struct AddCommand {
+    /// The dependency to add, as `<name>` or `<name>@<version>`.
+    ///
+    /// Without `--path` or `--git`, the dependency is looked up in the package registry and
+    /// the latest version which matches `<version>` and the current compiler is added.
+    dependency: String,
+
+    /// Add the package at this local path
+    #[clap(long, conflicts_with = "git")]
+    path: Option<PathBuf>,
+
+    /// Add the package from this git repository
+    #[clap(long)]
+    git: Option<String>,
+
+    /// The git tag to depend on. Defaults to the `<version>` given with the dependency.
+    #[clap(long, requires = "git")]
+    tag: Option<String>,
+
+    /// The directory within the git repository which contains the package
+    #[clap(long, requires = "git")]
+    directory: Option<String>,
+
+    #[clap(flatten)]
+    pub(super) registry: RegistryArgs,
+}
+
+pub(crate) fn run(args: AddCommand, config: NargoConfig) -> Result<(), CliError> {
+    let (name, version) = match args.dependency.split_once('@') {
+        Some((name, version)) => (name, Some(version)),
+        None => (args.dependency.as_str(), None),
+    };
+    let name: CrateName = name.parse().map_err(CliError::Generic)?;
+
+    let mut dependency = InlineTable::new();
+    if let Some(path) = &args.path {
+        if version.is_some() {
+            return Err(CliError::Generic(
+                "A version can't be given for a path dependency".to_string(),
+            ));
+        }
+        let current_dir = std::env::current_dir().map_err(|err| {
+            CliError::Generic(format!("Failed to read the current directory: {err}"))
+        })?;
+        let path = current_dir.join(path);
+        let path = relative_path(&config.program_dir, &path);
+        dependency.insert("path", path.to_string_lossy().into_owned().into());
+    } else if let Some(git) = &args.git {
+        let Some(tag) = args.tag.as_deref().or(version) else {
+            return Err(CliError::Generic(format!(
+                "A git dependency needs a tag. Use `--tag` or `{name}@<tag>`"
+            )));
+        };
+        dependency.insert("git", git.as_str().into());
+        dependency.insert("tag", tag.into());
+        if let Some(directory) = &args.directory {
+            dependency.insert("directory", directory.as_str().into());
+        }
+    } else {
+        let requirement = version
+            .map(|version| {
+                VersionReq::parse(version).map_err(|err| {
+                    CliError::Generic(format!("Invalid version requirement `{version}`: {err}"))
+                })
+            })
+            .transpose()?;
+
+        let package = fetch_package(&name.to_string())?;
+        let Some((selected_version, registry_version)) =
+            package.latest_compatible_version(requirement.as_ref())
+        else {
+            return Err(CliError::Generic(format!(
+                "No version of `{}` in the registry at {} matches {} and the current compiler version",
+                package.name,
+                registry_url(),
+                version.unwrap_or("*")
+            )));
+        };
+        human_println!("Selected `{name}` version {selected_version}");
+        dependency.insert("git", package.repository.as_str().into());
+        dependency.insert("tag", registry_version.tag.as_str().into());
+    }
+
+    let manifest_path = get_package_manifest(&config.program_dir)?;
+    let mut document = read_manifest_document(&manifest_path)?;
+    let dependencies = dependencies_table(&manifest_path, &mut document)?;
+
+    let name = name.to_string();
+    let action = if dependencies.contains_key(&name) { "Updating" } else { "Adding" };
+    dependencies.insert(&name, toml_edit::value(dependency));
+
+    write_manifest_document(&manifest_path, &document)?;
+    human_println!("{action} `{name}` in {}", manifest_path.display());
+
+    Ok(())
+}
+
+/// Expresses `path` relative to `base`, so that path dependencies don't depend on where the package is checked out.
+/// Falls back to the absolute path if the two don't share a root.
+fn relative_path(base: &Path, path: &Path) -> PathBuf {
+    let normalize = |path: &Path| {
+        let mut components = Vec::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => (),
+                Component::ParentDir if matches!(components.last(), Some(Component::Normal(_))) => {
+                    components.pop();
+                }
+                component => components.push(component),
+            }
+        }
+        components
+    };
+    let base = normalize(base);
+    let path = normalize(path);
+    if base.first() != path.first() {
+        return path.iter().collect();
+    }
+
+    let common = base.iter().zip(&path).take_while(|(a, b)| a == b).count();
+    let mut relative: PathBuf =
+        std::iter::repeat(Component::ParentDir).take(base.len() - common).collect();
+    relative.extend(&path[common..]);
+    if relative.as_os_str().is_empty() {
+        relative.push(Component::CurDir);
+    }
+    relative
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::relative_path;
+
+    #[test]
+    fn relative_path_between_siblings() {
+        assert_eq!(
+            relative_path(Path::new("/work/app"), Path::new("/work/lib")),
+            PathBuf::from("../lib")
+        );
+        assert_eq!(
+            relative_path(Path::new("/work/app"), Path::new("/work/app/crates/lib")),
+            PathBuf::from("crates/lib")
+        );
+    }
+
+    #[test]
+    fn relative_path_to_the_base_itself() {
+        assert_eq!(
+            relative_path(Path::new("/work/app"), Path::new("/work/app")),
+            PathBuf::from(".")
+        );
+    }
+
+    #[test]
+    fn relative_path_normalizes_dots() {
+        assert_eq!(
+            relative_path(Path::new("/work/./app"), Path::new("/work/app/../libs/./lib")),
+            PathBuf::from("../libs/lib")
+        );
+    }
+
+    #[test]
+    fn relative_path_without_a_shared_root() {
+        assert_eq!(relative_path(Path::new("work/app"), Path::new("/lib")), PathBuf::from("/lib"));
+    }
+}
@@ -0,0 +1,249 @@
+use std::{path::PathBuf, time::Duration};
+
+use bn254_blackbox_solver::Bn254BlackBoxSolver;
+use clap::Args;
+use nargo::{
+    insert_all_files_for_workspace_into_file_manager, ops::BenchStatus, package::CrateName,
+    parse_all, prepare_package,
+};
+use nargo_toml::{
+    check_lock_file, get_package_manifest, resolve_workspace_from_toml, PackageSelection,
+};
+use noirc_driver::{CompileOptions, MessageFormat, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::hir::FunctionNameMatch;
+use serde::Serialize;
+
+use crate::{cli::check_cmd::check_crate_and_report_errors, errors::CliError};
+
+use super::{compile_cmd::get_target_width, json_output, NargoConfig};
+
+/// Run the benchmarks for this program, timing the compilation and execution of each `#[bench]` function
+#[derive(Debug, Clone, Args)]
+pub(crate) struct BenchCommand {
+    /// If given, only benchmarks with names containing this string will be run
+    bench_name: Option<String>,
+
+    /// Only run benchmarks that match exactly
+    #[clap(long)]
+    exact: bool,
+
+    /// How many times to run each benchmark
+    #[clap(long, value_name = "N", default_value_t = 10)]
+    samples: usize,
+
+    /// Also write the results to this file as JSON
+    #[clap(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// The name of the package to benchmark
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Benchmark all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(mut args: BenchCommand, config: NargoConfig) -> Result<(), CliError> {
+    if json_output() {
+        args.compile_options.message_format = MessageFormat::Json;
+    }
+    if args.samples == 0 {
+        return Err(CliError::Generic("The number of samples must be at least 1".to_string()));
+    }
+
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+    check_lock_file(&workspace)?;
+
+    let pattern = match &args.bench_name {
+        Some(name) if args.exact => FunctionNameMatch::Exact(name),
+        Some(name) => FunctionNameMatch::Contains(name),
+        None => FunctionNameMatch::Anything,
+    };
+
+    let mut workspace_file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
+    let blackbox_solver = Bn254BlackBoxSolver::default();
+    let compile_options = &args.compile_options;
+    let mut results = Vec::new();
+    let mut count_found = 0;
+    let mut any_errored = false;
+
+    // Benchmarks are run one at a time so that they don't compete with each other for the CPU.
+    for package in &workspace {
+        let (mut context, crate_id) =
+            prepare_package(&workspace_file_manager, &parsed_files, package);
+        check_crate_and_report_errors(&mut context, crate_id, compile_options)?;
+
+        let bench_functions = context.get_all_bench_functions_in_crate_matching(&crate_id, pattern);
+        count_found += bench_functions.len();
+        let plural = if bench_functions.len() == 1 { "" } else { "s" };
+        human_println!("[{}] Running {} benchmark{plural}", package.name, bench_functions.len());
+
+        let expression_width =
+            get_target_width(package.expression_width, compile_options.expression_width);
+        let optimization_level = compile_options.opt_level.unwrap_or_default();
+        for (name, func_id) in bench_functions {
+            let status = nargo::ops::run_bench(
+                &blackbox_solver,
+                &mut context,
+                func_id,
+                args.samples,
+                expression_width,
+                optimization_level,
+                compile_options,
+            );
+            let report = match status {
+                BenchStatus::Completed(report) => report,
+                BenchStatus::HasArguments => {
+                    human_println!(
+                        "[{}] {name}: benchmark functions can't take arguments",
+                        package.name
+                    );
+                    any_errored = true;
+                    continue;
+                }
+                BenchStatus::CompileError(diagnostic) => {
+                    noirc_errors::reporter::report_all(
+                        workspace_file_manager.as_file_map(),
+                        &[diagnostic],
+                        compile_options.deny_warnings,
+                        compile_options.silence_warnings,
+                    );
+                    any_errored = true;
+                    continue;
+                }
+            };
+
+            let result = BenchResult {
+                package: package.name.to_string(),
+                name,
+                opcodes: report.opcodes,
+                samples: report.samples.len(),
+                compile: PhaseStats::new(report.samples.iter().map(|sample| sample.compile)),
+                execute: PhaseStats::new(report.samples.iter().map(|sample| sample.execute)),
+                execution_error: report.execution_error,
+            };
+            print_bench_result(&result);
+            results.push(result);
+        }
+    }
+
+    let bench_report = BenchReport { samples: args.samples, benchmarks: results };
+    if json_output() {
+        println!("{}", serde_json::to_string(&bench_report).unwrap());
+    }
+    if let Some(output) = &args.output {
+        let json = serde_json::to_string_pretty(&bench_report).unwrap();
+        std::fs::write(output, json).map_err(|error| {
+            CliError::Generic(format!("Failed to write {}: {error}", output.display()))
+        })?;
+        human_println!("Saved benchmark results to: {}", output.display());
+    }
+
+    if count_found == 0 {
+        match &pattern {
+            FunctionNameMatch::Exact(pattern) => {
+                return Err(CliError::Generic(format!(
+                    "Found 0 benchmarks matching input '{pattern}'."
+                )))
+            }
+            FunctionNameMatch::Contains(pattern) => {
+                return Err(CliError::Generic(format!(
+                    "Found 0 benchmarks containing '{pattern}'."
+                )))
+            }
+            FunctionNameMatch::Anything => {}
+        }
+    }
+
+    if any_errored {
+        Err(CliError::Generic(String::new()))
+    } else {
+        Ok(())
+    }
+}
+
+fn print_bench_result(result: &BenchResult) {
+    human_println!("[{}] {} ({} opcodes)", result.package, result.name, result.opcodes);
+    human_println!("    compile: {}", result.compile);
+    human_println!("    execute: {}", result.execute);
+    if let Some(error) = &result.execution_error {
+        human_println!("    execution failed: {error}");
+    }
+}
+
+/// The results of a run of `nargo bench`, as written to `--output` and to stdout with `--format json`.
+#[derive(Debug, Serialize)]
+struct BenchReport {
+    /// How many times each benchmark was run.
+    samples: usize,
+    benchmarks: Vec<BenchResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct BenchResult {
+    package: String,
+    name: String,
+    /// The number of opcodes in the optimized program.
+    opcodes: usize,
+    samples: usize,
+    compile: PhaseStats,
+    execute: PhaseStats,
+    /// Benchmarks which fail to execute are still timed, and don't fail the command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    execution_error: Option<String>,
+}
+
+/// Latencies of a phase across all samples of a benchmark, in milliseconds.
+#[derive(Debug, Serialize)]
+struct PhaseStats {
+    mean_ms: f64,
+    median_ms: f64,
+    p99_ms: f64,
+}
+
+impl PhaseStats {
+    fn new(durations: impl Iterator<Item = Duration>) -> Self {
+        let mut millis: Vec<f64> =
+            durations.map(|duration| duration.as_secs_f64() * 1000.0).collect();
+        millis.sort_by(f64::total_cmp);
+        if millis.is_empty() {
+            return PhaseStats { mean_ms: 0.0, median_ms: 0.0, p99_ms: 0.0 };
+        }
+
+        let count = millis.len();
+        let mean_ms = millis.iter().sum::<f64>() / count as f64;
+        let median_ms = if count % 2 == 0 {
+            (millis[count / 2 - 1] + millis[count / 2]) / 2.0
+        } else {
+            millis[count / 2]
+        };
+        // The nearest-rank percentile: the smallest sample which at least 99% of the samples don't exceed.
+        let p99_index = ((count as f64 * 0.99).ceil() as usize).saturating_sub(1);
+        PhaseStats { mean_ms, median_ms, p99_ms: millis[p99_index] }
+    }
+}
+
+impl std::fmt::Display for PhaseStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mean {:.3} ms, median {:.3} ms, p99 {:.3} ms",
+            self.mean_ms, self.median_ms, self.p99_ms
+        )
+    }
+}
@@ -13,7 +13,8 @@ use nargo::{
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::{AbiParameter, AbiType, MAIN_RETURN_NAME};
 use noirc_driver::{
-    check_crate, compute_function_abi, CompileOptions, CrateId, NOIR_ARTIFACT_VERSION_STRING,
+    check_crate, compute_function_abi, CompileOptions, CrateId, MessageFormat,
+    NOIR_ARTIFACT_VERSION_STRING,
 };
 use noirc_frontend::hir::{Context, ParsedFiles};
 
@@ -40,7 +41,11 @@ pub(crate) struct CheckCommand {
     compile_options: CompileOptions,
 }
 
-pub(crate) fn run(args: CheckCommand, config: NargoConfig) -> Result<(), CliError> {
+pub(crate) fn run(mut args: CheckCommand, config: NargoConfig) -> Result<(), CliError> {
+    if json_output() {
+        args.compile_options.message_format = MessageFormat::Json;
+    }
+
     let toml_path = get_package_manifest(&config.program_dir)?;
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
@@ -64,7 +69,7 @@ pub(crate) fn run(args: CheckCommand, config: NargoConfig) -> Result<(), CliErro
             args.allow_overwrite,
         )?;
         if any_file_written {
-            println!("[{}] Constraint system successfully built!", package.name);
+            human_println!("[{}] Constraint system successfully built!", package.name);
         }
     }
     Ok(())
@@ -152,7 +157,13 @@ pub(crate) fn check_crate_and_report_errors(
     options: &CompileOptions,
 ) -> Result<(), CompileError> {
     let result = check_crate(context, crate_id, options);
-    report_errors(result, &context.file_manager, options.deny_warnings, options.silence_warnings)
+    report_errors(
+        result,
+        &context.file_manager,
+        options.deny_warnings,
+        options.silence_warnings,
+        options.message_format,
+    )
 }
 
 #[cfg(test)]
@@ -0,0 +1,53 @@
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use sha2::{Digest, Sha256};
+
+use crate::errors::{CliError, FilesystemError};
+
+use super::fs::write_to_file;
+use super::NargoConfig;
+
+/// The extension appended to an artifact's file name to form the name of its checksum file.
+const CHECKSUM_EXTENSION: &str = "sha256";
+
+/// Writes a detached SHA-256 checksum file for a compiled artifact
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ChecksumCommand {
+    /// Path to the artifact to checksum
+    artifact_path: PathBuf,
+
+    /// Write the checksum to this file rather than alongside the artifact
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+pub(crate) fn run(args: ChecksumCommand, config: NargoConfig) -> Result<(), CliError> {
+    let artifact_path = config.program_dir.join(&args.artifact_path);
+    let checksum = artifact_checksum(&artifact_path)?;
+
+    let checksum_path = match &args.output {
+        Some(output) => config.program_dir.join(output),
+        None => default_checksum_path(&artifact_path),
+    };
+
+    // Follows the format of `sha256sum` so that the file can also be checked with standard tooling.
+    let file_name = artifact_path.file_name().unwrap_or_default().to_string_lossy();
+    write_to_file(format!("{checksum}  {file_name}\n").as_bytes(), &checksum_path);
+    human_println!("Saved checksum to: {}", checksum_path.display());
+
+    Ok(())
+}
+
+pub(super) fn artifact_checksum(artifact_path: &Path) -> Result<String, CliError> {
+    let artifact = std::fs::read(artifact_path)
+        .map_err(|_| FilesystemError::PathNotValid(artifact_path.to_path_buf()))?;
+    Ok(format!("{:x}", Sha256::digest(artifact)))
+}
+
+pub(super) fn default_checksum_path(artifact_path: &Path) -> PathBuf {
+    let mut file_name = artifact_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(CHECKSUM_EXTENSION);
+    artifact_path.with_file_name(file_name)
+}
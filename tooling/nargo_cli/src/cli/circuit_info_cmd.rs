@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use acvm::acir::circuit::{Circuit, ExpressionWidth, Opcode, Program};
+use acvm::acir::BlackBoxFunc;
+use acvm::FieldElement;
+use clap::Args;
+use noirc_driver::parse_expression_width;
+use prettytable::{row, table, Table};
+use serde::Serialize;
+
+use crate::errors::CliError;
+
+use super::compile_report::circuit_witnesses;
+use super::fs::program::{read_contract_from_file, read_program_from_file};
+use super::{json_output, NargoConfig};
+
+/// Reports the size of a compiled circuit, broken down by the kinds of opcodes it's made up of
+#[derive(Debug, Clone, Args)]
+pub(crate) struct CircuitInfoCommand {
+    /// Path to the program or contract artifact to report on
+    artifact_path: PathBuf,
+
+    /// The name of the contract function to report on.
+    /// Required for contract artifacts.
+    #[clap(long)]
+    function: Option<String>,
+
+    /// Recompile the circuit for this expression width before reporting on it,
+    /// rather than reporting on the circuit as it was compiled.
+    #[clap(long, value_parser = parse_expression_width)]
+    expression_width: Option<ExpressionWidth>,
+
+    /// Output the report as JSON. Same as `--format json`
+    #[clap(long)]
+    pub(super) json: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct CircuitInfo {
+    pub(super) function_name: String,
+    total_opcodes: usize,
+    /// The number of opcodes of each kind, only including kinds which occur in the circuit.
+    opcodes: BTreeMap<&'static str, usize>,
+    public_inputs: usize,
+    /// The number of witnesses which aren't public inputs.
+    private_witnesses: usize,
+    witnesses: usize,
+}
+
+pub(crate) fn run(args: CircuitInfoCommand, config: NargoConfig) -> Result<(), CliError> {
+    let artifact_path = config.program_dir.join(&args.artifact_path);
+
+    let (names, program) = match &args.function {
+        Some(function_name) => {
+            let contract = read_contract_from_file(&artifact_path)?;
+            let function = contract
+                .functions
+                .into_iter()
+                .find(|function| &function.name == function_name)
+                .ok_or_else(|| {
+                    CliError::Generic(format!(
+                        "Contract `{}` has no function named `{function_name}`",
+                        contract.name
+                    ))
+                })?;
+            (vec![function.name], function.bytecode)
+        }
+        None => {
+            let program = read_program_from_file(&artifact_path).map_err(|err| {
+                CliError::Generic(format!(
+                    "{err}\nIf this is a contract artifact, select a function with `--function`"
+                ))
+            })?;
+            (program.names, program.bytecode)
+        }
+    };
+
+    let program = match args.expression_width {
+        Some(expression_width) => Program {
+            functions: program
+                .functions
+                .into_iter()
+                .map(|circuit| acvm::compiler::compile(circuit, expression_width).0)
+                .collect(),
+            ..program
+        },
+        None => program,
+    };
+    let reports = program_circuit_info(&names, &program);
+
+    if json_output() {
+        println!("{}", serde_json::to_string(&reports).unwrap());
+        return Ok(());
+    }
+
+    for report in reports {
+        human_println!("{}", report.function_name);
+        circuit_info_table(&report).printstd();
+    }
+
+    Ok(())
+}
+
+/// Reports on each circuit of `program`, naming them after the functions in `names`.
+pub(super) fn program_circuit_info(
+    names: &[String],
+    program: &Program<FieldElement>,
+) -> Vec<CircuitInfo> {
+    program
+        .functions
+        .iter()
+        .enumerate()
+        .map(|(index, circuit)| {
+            // Subsequent ACIR functions of a contract function aren't named in the artifact.
+            let function_name =
+                names.get(index).cloned().unwrap_or_else(|| format!("{}[{index}]", names[0]));
+            circuit_info(function_name, circuit)
+        })
+        .collect()
+}
+
+pub(super) fn circuit_info_table(report: &CircuitInfo) -> Table {
+    let mut info_table = table!([Fm->"Opcodes", Fm->"Count"]);
+    info_table.add_row(row![Fc->"Total", Fc->format!("{}", report.total_opcodes)]);
+    for (kind, count) in &report.opcodes {
+        info_table.add_row(row![kind, Fc->format!("{count}")]);
+    }
+    info_table.add_row(row![Fm->"Public inputs", Fc->format!("{}", report.public_inputs)]);
+    info_table.add_row(row![Fm->"Private witnesses", Fc->format!("{}", report.private_witnesses)]);
+    info_table.add_row(row![Fm->"Witnesses", Fc->format!("{}", report.witnesses)]);
+    info_table
+}
+
+fn circuit_info(function_name: String, circuit: &Circuit<FieldElement>) -> CircuitInfo {
+    let mut opcodes = BTreeMap::new();
+    for opcode in &circuit.opcodes {
+        *opcodes.entry(opcode_kind(opcode)).or_default() += 1;
+    }
+
+    // Return values are also public inputs from the point of view of a verifier.
+    let public_inputs = circuit.public_parameters.0.len() + circuit.return_values.0.len();
+    let witnesses = circuit_witnesses(circuit).len();
+    CircuitInfo {
+        function_name,
+        total_opcodes: circuit.opcodes.len(),
+        opcodes,
+        public_inputs,
+        private_witnesses: witnesses.saturating_sub(public_inputs),
+        witnesses,
+    }
+}
+
+/// Groups opcodes into the kinds which contribute differently to the cost of proving a circuit.
+fn opcode_kind(opcode: &Opcode<FieldElement>) -> &'static str {
+    match opcode {
+        Opcode::AssertZero(_) => "arithmetic",
+        Opcode::BlackBoxFuncCall(call) => match call.get_black_box_func() {
+            BlackBoxFunc::RANGE => "range",
+            BlackBoxFunc::AND | BlackBoxFunc::XOR => "logic",
+            BlackBoxFunc::Blake2s
+            | BlackBoxFunc::Blake3
+            | BlackBoxFunc::Keccakf1600
+            | BlackBoxFunc::Poseidon2Permutation
+            | BlackBoxFunc::Sha256Compression => "hash",
+            BlackBoxFunc::SchnorrVerify
+            | BlackBoxFunc::EcdsaSecp256k1
+            | BlackBoxFunc::EcdsaSecp256r1 => "signature",
+            BlackBoxFunc::MultiScalarMul | BlackBoxFunc::EmbeddedCurveAdd => "elliptic curve",
+            BlackBoxFunc::BigIntAdd
+            | BlackBoxFunc::BigIntSub
+            | BlackBoxFunc::BigIntMul
+            | BlackBoxFunc::BigIntDiv
+            | BlackBoxFunc::BigIntFromLeBytes
+            | BlackBoxFunc::BigIntToLeBytes => "bigint",
+            BlackBoxFunc::AES128Encrypt => "encryption",
+            BlackBoxFunc::RecursiveAggregation => "recursion",
+        },
+        Opcode::MemoryOp { .. } | Opcode::MemoryInit { .. } => "memory",
+        Opcode::BrilligCall { .. } => "unconstrained call",
+        Opcode::Call { .. } => "call",
+    }
+}
@@ -0,0 +1,239 @@
+//! A content-addressed cache of compiled programs, so that packages which haven't changed aren't recompiled.
+//!
+//! Each entry is named after a digest of everything which affects the compiled program:
+//! the version of Noir, the compile options which change the program, and the sources and manifests
+//! of the package and its dependencies.
+//! Any change to these produces a different digest, so stale entries are never read.
+//!
+//! The digest is also recorded in each program artifact, so that a package whose artifact is
+//! already up to date can skip both compilation and optimization.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use acvm::acir::circuit::ExpressionWidth;
+use fm::FileManager;
+use nargo::constants::PKG_FILE;
+use nargo::package::{Dependency, Package};
+use nargo::workspace::Workspace;
+use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
+use sha2::{Digest, Sha256};
+
+use super::fs::program::save_build_artifact_to_file;
+
+/// The directory within the target directory which holds the cached programs.
+const CACHE_DIR: &str = "cache";
+
+/// Computes the digest which the compiled program of `package` is cached under.
+pub(super) fn program_cache_key(
+    file_manager: &FileManager,
+    package: &Package,
+    compile_options: &CompileOptions,
+) -> String {
+    let mut root_dirs = BTreeSet::new();
+    collect_root_dirs(package, &mut root_dirs);
+
+    let mut hasher = Sha256::new();
+    hasher.update(NOIR_ARTIFACT_VERSION_STRING);
+    hash_compile_options(&mut hasher, compile_options);
+
+    // The manifests cover changes to the dependencies, including ones which don't change any source files.
+    for (root_dir, version) in &root_dirs {
+        hasher.update(version.as_deref().unwrap_or_default());
+        let manifest = std::fs::read(root_dir.join(PKG_FILE)).unwrap_or_default();
+        hasher.update(root_dir.to_string_lossy().as_bytes());
+        hasher.update(manifest);
+    }
+
+    let mut sources: Vec<(&Path, &str)> = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .filter_map(|file_id| {
+            Some((file_manager.path(*file_id)?, file_manager.fetch_file(*file_id)?))
+        })
+        .filter(|(path, _)| {
+            root_dirs.iter().any(|(root_dir, _)| path.starts_with(root_dir))
+                || package.prelude_path.as_deref() == Some(*path)
+        })
+        .collect();
+    sources.sort_unstable_by_key(|(path, _)| *path);
+    for (path, source) in sources {
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update(source);
+    }
+
+    format!("{}-{:x}", package.name, hasher.finalize())
+}
+
+/// Hashes the compile options which change the compiled program or the artifact written from it.
+///
+/// Options which only change what's reported, such as `--silence-warnings` or `--print-acir`, are left out
+/// so that they share the same cache entries. Options which need a fresh compilation bypass the cache instead.
+fn hash_compile_options(hasher: &mut Sha256, options: &CompileOptions) {
+    let expression_width = match options.expression_width {
+        None => "default".to_string(),
+        Some(ExpressionWidth::Unbounded) => "unbounded".to_string(),
+        Some(ExpressionWidth::Bounded { width }) => width.to_string(),
+    };
+    let strict_abi = match options.strict_abi {
+        None => "none",
+        Some(noirc_abi::profile::AbiProfile::Solidity) => "solidity",
+    };
+    let semantic_options = [
+        ("expression_width", expression_width),
+        ("bounded_codegen", options.bounded_codegen.to_string()),
+        ("opt_level", (options.opt_level.unwrap_or_default() as u8).to_string()),
+        ("stub_functions", options.stub_functions.join(",")),
+        ("make_public_functions", options.make_public_functions.join(",")),
+        ("make_internal_functions", options.make_internal_functions.join(",")),
+        ("reachable_from", options.reachable_from.clone().unwrap_or_default()),
+        ("entry_function", options.entry_function.clone().unwrap_or_default()),
+        ("strict_abi", strict_abi.to_string()),
+        ("disable_macros", options.disable_macros.to_string()),
+        ("instrument_debug", options.instrument_debug.to_string()),
+        ("keep_unoptimized_debug", options.keep_unoptimized_debug.to_string()),
+        ("trim_paths", options.trim_paths.to_string()),
+        ("pretty_artifacts", options.pretty_artifacts.to_string()),
+        ("skip_underconstrained_check", options.skip_underconstrained_check.to_string()),
+        ("inliner_aggressiveness", options.inliner_aggressiveness.to_string()),
+        (
+            "max_bytecode_increase_percent",
+            options
+                .max_bytecode_increase_percent
+                .map(|percent| percent.to_string())
+                .unwrap_or_default(),
+        ),
+    ];
+    for (name, value) in semantic_options {
+        hasher.update(format!("{name}={value}\n"));
+    }
+}
+
+/// Collects the root directories of `package` and all of its dependencies, along with their resolved versions.
+fn collect_root_dirs(package: &Package, root_dirs: &mut BTreeSet<(PathBuf, Option<String>)>) {
+    if !root_dirs.insert((package.root_dir.clone(), package.version.clone())) {
+        return;
+    }
+    for dependency in package.dependencies.values() {
+        match dependency {
+            Dependency::Local { package } | Dependency::Remote { package } => {
+                collect_root_dirs(package, root_dirs);
+            }
+        }
+    }
+}
+
+/// Reads the program cached under `key`, if there is one.
+pub(super) fn read_cached_program(workspace: &Workspace, key: &str) -> Option<CompiledProgram> {
+    let cache_path = cache_dir(workspace).join(key).with_extension("json");
+    let bytes = std::fs::read(cache_path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Caches `program` under `key`, replacing any entry previously cached for the same package.
+pub(super) fn write_cached_program(
+    workspace: &Workspace,
+    package: &Package,
+    key: &str,
+    program: &CompiledProgram,
+) {
+    let cache_dir = cache_dir(workspace);
+    let package_prefix = format!("{}-", package.name);
+    if let Ok(entries) = std::fs::read_dir(&cache_dir) {
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_string_lossy();
+            if file_name.starts_with(&package_prefix) && file_name != format!("{key}.json") {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+    save_build_artifact_to_file(program, key, cache_dir, false);
+}
+
+fn cache_dir(workspace: &Workspace) -> PathBuf {
+    workspace.target_directory_path().join(CACHE_DIR)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use nargo::insert_all_files_for_workspace_into_file_manager;
+    use nargo_toml::{resolve_workspace_from_toml, PackageSelection};
+    use noirc_driver::CompileOptions;
+
+    use super::program_cache_key;
+
+    /// Writes a binary package depending on a library in `dir`.
+    fn write_packages(dir: &Path) {
+        let files = [
+            ("bin/Nargo.toml", "[package]\nname = \"bin\"\ntype = \"bin\"\n\n[dependencies]\nlib = { path = \"../lib\" }\n"),
+            ("bin/src/main.nr", "fn main(x: Field) { lib::check(x); }\n"),
+            ("lib/Nargo.toml", "[package]\nname = \"lib\"\ntype = \"lib\"\n"),
+            ("lib/src/lib.nr", "pub fn check(x: Field) { assert(x != 0); }\n"),
+        ];
+        for (path, contents) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+    }
+
+    fn cache_key(dir: &Path, compile_options: &CompileOptions) -> String {
+        let workspace = resolve_workspace_from_toml(
+            &dir.join("bin").join("Nargo.toml"),
+            PackageSelection::All,
+            None,
+        )
+        .unwrap();
+        let mut file_manager = workspace.new_file_manager();
+        insert_all_files_for_workspace_into_file_manager(&workspace, &mut file_manager);
+        program_cache_key(&file_manager, &workspace.members[0], compile_options)
+    }
+
+    #[test]
+    fn unchanged_package_hits_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        write_packages(dir.path());
+
+        let key = cache_key(dir.path(), &CompileOptions::default());
+        assert_eq!(cache_key(dir.path(), &CompileOptions::default()), key);
+
+        // Options which only change what's reported share the same entry.
+        let reporting_options =
+            CompileOptions { silence_warnings: true, print_acir: true, ..Default::default() };
+        assert_eq!(cache_key(dir.path(), &reporting_options), key);
+
+        let semantic_options = CompileOptions { bounded_codegen: true, ..Default::default() };
+        assert_ne!(cache_key(dir.path(), &semantic_options), key);
+    }
+
+    #[test]
+    fn source_edit_misses_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        write_packages(dir.path());
+        let key = cache_key(dir.path(), &CompileOptions::default());
+
+        std::fs::write(
+            dir.path().join("bin/src/main.nr"),
+            "fn main(x: Field) { assert(x != 1); }\n",
+        )
+        .unwrap();
+        assert_ne!(cache_key(dir.path(), &CompileOptions::default()), key);
+    }
+
+    #[test]
+    fn dependency_edit_misses_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        write_packages(dir.path());
+        let key = cache_key(dir.path(), &CompileOptions::default());
+
+        std::fs::write(
+            dir.path().join("lib/src/lib.nr"),
+            "pub fn check(x: Field) { assert(x != 1); }\n",
+        )
+        .unwrap();
+        assert_ne!(cache_key(dir.path(), &CompileOptions::default()), key);
+    }
+}
@@ -1,27 +1,64 @@
-use std::io::Write;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::RecvTimeoutError;
 use std::time::Duration;
 
-use acvm::acir::circuit::ExpressionWidth;
-use fm::FileManager;
+use acvm::acir::circuit::{Circuit, ExpressionWidth, Program};
+use acvm::acir::BlackBoxFunc;
+use acvm::compiler::{OptimizationLevel, OptimizationProfile};
+use acvm::FieldElement;
+use fm::{FileId, FileManager};
+use nargo::constants::PKG_FILE;
+use nargo::errors::CompileError;
 use nargo::ops::{collect_errors, compile_contract, compile_program, report_errors};
-use nargo::package::{CrateName, Package};
+use nargo::package::{CrateName, Package, PackageType};
 use nargo::workspace::Workspace;
-use nargo::{insert_all_files_for_workspace_into_file_manager, parse_all};
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use nargo::{
+    insert_all_files_for_workspace_into_file_manager, parse_all, prepare_package, stub_functions,
+};
+use nargo_toml::{
+    check_lock_file, get_package_manifest, resolve_workspace_from_toml_str,
+    resolve_workspace_from_toml_with_max_depth, ManifestError, PackageSelection,
+};
+use noirc_artifacts::{
+    contract::{ContractAbiArtifact, ContractArtifact},
+    program::{ProgramArtifact, UnoptimizedDebug},
+};
 use noirc_driver::DEFAULT_EXPRESSION_WIDTH;
-use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
-use noirc_driver::{CompilationResult, CompileOptions, CompiledContract};
+use noirc_driver::{
+    CompilationResult, CompileOptions, CompiledContract, ErrorsAndWarnings, MessageFormat,
+};
+use noirc_driver::{GIT_COMMIT, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::debug_info::ProgramDebugInfo;
+use noirc_errors::{CustomDiagnostic, FileDiagnostic};
 
-use clap::Args;
-use noirc_frontend::hir::ParsedFiles;
+use clap::{Args, ValueEnum};
+use noirc_frontend::hir::{FunctionNameMatch, ParsedFiles};
 use notify::{EventKind, RecursiveMode, Watcher};
 use notify_debouncer_full::new_debouncer;
+use serde::Serialize;
 
 use crate::errors::CliError;
 
-use super::fs::program::{read_program_from_file, save_contract_to_file, save_program_to_file};
-use super::NargoConfig;
+use super::check_cmd::check_crate_and_report_errors;
+use super::circuit_info_cmd::{circuit_info_table, program_circuit_info};
+use super::compile_cache::{program_cache_key, read_cached_program, write_cached_program};
+use super::compile_replay::replay_artifact;
+use super::compile_report::{
+    collect_call_sites, collect_recursion_info, compute_io_sizes, contract_circuits,
+    count_disallowed_black_box_functions, count_field_ops, count_witnesses, emit_acir_asm,
+    emit_acir_dot, emit_acir_mermaid, hash_abis, parse_black_box_func, write_artifact_index,
+    Baseline, CompileReport, WorkspaceArtifacts,
+};
+use super::compile_shards::shard_workspace_programs;
+use super::fs::check_dir_writable;
+use super::fs::program::{
+    read_program_from_file, read_workspace_contract, save_contract_abi_to_path,
+    save_contract_to_file, save_contract_to_path, save_program_to_path,
+};
+use super::test_cmd::run_workspace_tests;
+use super::{json_output, NargoConfig};
 use rayon::prelude::*;
 
 /// Compile the program and its secret execution trace into ACIR format
@@ -38,34 +75,717 @@ pub(crate) struct CompileCommand {
     #[clap(flatten)]
     compile_options: CompileOptions,
 
-    /// Watch workspace and recompile on changes.
-    #[clap(long, hide = true)]
+    /// Stay running and recompile the workspace whenever one of its Noir source files or manifests changes.
+    #[clap(long)]
     watch: bool,
+
+    /// List the entry points which would be compiled into artifacts, without compiling them.
+    #[clap(long)]
+    print_entrypoints: bool,
+
+    /// Print the number of distinct witnesses used by each compiled function.
+    #[clap(long)]
+    print_witness_count: bool,
+
+    /// Print a hash of each compiled function's ABI, which only changes when its inputs or outputs do.
+    #[clap(long)]
+    print_abi_hash: bool,
+
+    /// Print the serialized size in bytes of each compiled function's public inputs, private inputs and return value.
+    #[clap(long)]
+    print_io_sizes: bool,
+
+    /// Print the recursive proof verifications within each compiled function, along with their opcode indices.
+    #[clap(long)]
+    print_recursion_info: bool,
+
+    /// Print the number of field additions and multiplications made by each compiled function's arithmetic opcodes.
+    #[clap(long)]
+    print_field_ops: bool,
+
+    /// Print which source functions each compiled function's opcodes were generated by,
+    /// as ranges of opcode indices in the optimized circuit.
+    #[clap(long)]
+    emit_call_sites: bool,
+
+    /// Output any reports requested through `--print-*` flags or `--emit-call-sites` as JSON. Same as `--format json`.
+    #[clap(long)]
+    pub(super) json: bool,
+
+    /// Exit with this code if warnings were reported but compilation otherwise succeeded.
+    /// `--deny-warnings` takes precedence as it turns any warnings into errors.
+    /// The code must be between 1 and 255, as 0 would report success.
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(i32).range(1..=255))]
+    exit_code_on_warnings: Option<i32>,
+
+    /// Experimental: split each compiled program's circuit into N contiguous shards,
+    /// written as separate artifacts alongside a manifest describing how they relate.
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    shard: Option<u32>,
+
+    /// Error if any chain of dependencies is longer than this many levels.
+    #[clap(long, value_name = "N")]
+    max_depth: Option<usize>,
+
+    /// Report the approximate peak memory usage of the compilation once it finishes.
+    /// This is read from the peak resident set size of the process, so is only available on Linux.
+    #[clap(long)]
+    profile_memory: bool,
+
+    /// Only permit these black box functions to be used by the compiled circuits.
+    #[clap(long = "allow-blackbox", value_name = "NAME", value_parser = parse_black_box_func)]
+    allowed_black_box_functions: Vec<BlackBoxFunc>,
+
+    /// Forbid these black box functions from being used by the compiled circuits.
+    #[clap(long = "deny-blackbox", value_name = "NAME", value_parser = parse_black_box_func)]
+    denied_black_box_functions: Vec<BlackBoxFunc>,
+
+    /// Lower each package to unoptimized ACIR and list every call to a black box function which
+    /// `--allow-blackbox` or `--deny-blackbox` don't permit, counted per function, without optimizing
+    /// or writing artifacts.
+    #[clap(long, conflicts_with_all = ["watch", "dry_run", "replay"])]
+    list_opcodes_unsupported: bool,
+
+    /// Compare the compiled artifact against this baseline artifact, failing if the ABI changed
+    /// or the number of ACIR opcodes grew by more than `--tolerance`.
+    #[clap(long, value_name = "ARTIFACT")]
+    compare_with: Option<PathBuf>,
+
+    /// The percentage by which the number of ACIR opcodes may grow before `--compare-with` fails.
+    #[clap(long, value_name = "PERCENT", default_value_t = 0.0, requires = "compare_with")]
+    tolerance: f64,
+
+    /// Error rather than warn if the target directory holds artifacts compiled by a different version of Noir.
+    #[clap(long)]
+    strict_artifact_versions: bool,
+
+    /// Write additional representations of each compiled circuit to the target directory.
+    #[clap(long, value_enum, value_name = "FORMAT")]
+    emit: Vec<EmitFormat>,
+
+    /// Once compiled, run the `#[test]` functions of each selected package and report whether they passed.
+    #[clap(long)]
+    run_tests: bool,
+
+    /// Report which packages would be compiled and which artifacts would be written, without compiling anything.
+    #[clap(long, conflicts_with = "watch")]
+    dry_run: bool,
+
+    /// Abort compilation if it hasn't finished after this many seconds.
+    #[clap(long, value_name = "SECONDS", conflicts_with = "watch")]
+    compile_timeout: Option<u64>,
+
+    /// The maximum number of packages to compile in parallel. Defaults to the number of logical cores.
+    #[clap(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: Option<u32>,
+
+    /// Read the package's manifest from stdin instead of looking for a Nargo.toml, taking its sources from `--src-dir`.
+    #[clap(long, requires = "src_dir", conflicts_with = "watch")]
+    package_from_stdin: bool,
+
+    /// The directory holding the sources of a package whose manifest is read with `--package-from-stdin`.
+    /// Unless the manifest sets an `entry`, the package's entry file is `main.nr` or `lib.nr` within it.
+    #[clap(long, value_name = "DIR", requires = "package_from_stdin")]
+    src_dir: Option<PathBuf>,
+
+    /// Recompile this program artifact from the sources embedded in it instead of compiling the workspace,
+    /// failing with a diff if the result doesn't match the artifact.
+    #[clap(long, value_name = "ARTIFACT", conflicts_with_all = ["watch", "dry_run"])]
+    replay: Option<PathBuf>,
+}
+
+impl CompileCommand {
+    /// True if the package's manifest is read from stdin rather than found on disk.
+    pub(crate) fn reads_manifest_from_stdin(&self) -> bool {
+        self.package_from_stdin
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum EmitFormat {
+    /// A textual assembly of the optimized ACIR, with one opcode per line, which can be parsed back into a circuit.
+    AcirAsm,
+    /// A Graphviz graph of the optimized ACIR, linking each opcode to the witnesses it reads and writes.
+    Dot,
+    /// A Mermaid flowchart of the optimized ACIR, with the same nodes and edges as `dot`.
+    Mermaid,
 }
 
-pub(crate) fn run(args: CompileCommand, config: NargoConfig) -> Result<(), CliError> {
-    let toml_path = get_package_manifest(&config.program_dir)?;
+pub(crate) fn run(mut args: CompileCommand, config: NargoConfig) -> Result<(), CliError> {
+    if json_output() {
+        args.json = true;
+        args.compile_options.message_format = MessageFormat::Json;
+    }
+
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
-    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let selection = args.package.clone().map_or(default_selection, PackageSelection::Selected);
 
-    let workspace = resolve_workspace_from_toml(
-        &toml_path,
-        selection,
-        Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
-    )?;
+    let resolve_workspace = || {
+        let toml_path = get_package_manifest(&config.program_dir)?;
+        let workspace = resolve_workspace_from_toml_with_max_depth(
+            &toml_path,
+            selection.clone(),
+            Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+            args.max_depth,
+        )?;
+        Ok(workspace)
+    };
+    let workspace = match &args.src_dir {
+        Some(src_dir) if args.package_from_stdin => {
+            let mut manifest = String::new();
+            std::io::stdin().read_to_string(&mut manifest).map_err(|err| {
+                CliError::Generic(format!("Failed to read manifest from stdin: {err}"))
+            })?;
+            resolve_workspace_from_toml_str(
+                &manifest,
+                &config.program_dir.join(src_dir),
+                selection.clone(),
+                Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+                args.max_depth,
+            )?
+        }
+        _ => resolve_workspace()?,
+    };
+    args.compile_options = workspace.compile.apply_to(&args.compile_options);
+
+    let compile_options = &args.compile_options;
+    if let Some(name) = compile_options
+        .make_public_functions
+        .iter()
+        .find(|name| compile_options.make_internal_functions.contains(name))
+    {
+        return Err(CliError::Generic(format!(
+            "Function `{name}` can't be passed to both `--make-public` and `--make-internal`"
+        )));
+    }
+
+    if args.print_entrypoints {
+        return print_entrypoints(&workspace, &args.compile_options, args.json);
+    }
+
+    if args.dry_run {
+        print_dry_run(&workspace, &args);
+        return Ok(());
+    }
+
+    if let Some(artifact_path) = &args.replay {
+        return replay_artifact(&workspace, artifact_path, &args.compile_options);
+    }
+
+    if args.list_opcodes_unsupported {
+        return list_unsupported_opcodes(&workspace, &args);
+    }
+
+    // The lock file is only checked, and possibly written, by the commands above which actually build
+    // the workspace, so that inspecting a workspace never changes it.
+    check_lock_file(&workspace)?;
+    check_artifact_dirs_writable(&workspace)?;
+
+    let checks = ArtifactChecks {
+        allowed_black_box_functions: args.allowed_black_box_functions.clone(),
+        denied_black_box_functions: args.denied_black_box_functions.clone(),
+        baseline: args
+            .compare_with
+            .as_deref()
+            .map(|baseline_path| Baseline::read(&workspace, baseline_path, args.tolerance))
+            .transpose()?,
+    };
 
     if args.watch {
-        watch_workspace(&workspace, &args.compile_options)
+        let resolve_locked_workspace = || {
+            let workspace = resolve_workspace()?;
+            check_lock_file(&workspace)?;
+            Ok(workspace)
+        };
+        watch_workspace(workspace, resolve_locked_workspace, &args.compile_options)
             .map_err(|err| CliError::Generic(err.to_string()))?;
     } else {
-        compile_workspace_full(&workspace, &args.compile_options)?;
+        let warning_count = match args.compile_timeout {
+            Some(timeout) => compile_workspace_with_timeout(
+                &workspace,
+                &args.compile_options,
+                args.jobs,
+                &checks,
+                Duration::from_secs(timeout),
+            )?,
+            None => {
+                compile_workspace_with_jobs(&workspace, &args.compile_options, args.jobs, &checks)?
+            }
+        };
+        if let Some(shard_count) = args.shard {
+            shard_workspace_programs(
+                &workspace,
+                shard_count as usize,
+                args.compile_options.pretty_artifacts,
+            )?;
+        }
+        let index_path = write_artifact_index(&workspace)?;
+        if args.compile_options.show_artifact_paths {
+            human_println!("Saved artifact index to: {}", index_path.display());
+        }
+        check_artifact_versions(&workspace, args.strict_artifact_versions)?;
+        if !args.emit.is_empty() {
+            let artifacts = WorkspaceArtifacts::read(&workspace)?;
+            let target_dir = workspace.target_directory_path();
+            for format in &args.emit {
+                let (paths, description) = match format {
+                    EmitFormat::AcirAsm => {
+                        (emit_acir_asm(&artifacts, &target_dir), "ACIR assembly")
+                    }
+                    EmitFormat::Dot => (emit_acir_dot(&artifacts, &target_dir), "circuit graph"),
+                    EmitFormat::Mermaid => {
+                        (emit_acir_mermaid(&artifacts, &target_dir), "circuit flowchart")
+                    }
+                };
+                if args.compile_options.show_artifact_paths {
+                    for path in paths {
+                        human_println!("Saved {description} to: {}", path.display());
+                    }
+                }
+            }
+        }
+        if args.print_witness_count
+            || args.print_abi_hash
+            || args.print_io_sizes
+            || args.print_recursion_info
+            || args.print_field_ops
+            || args.emit_call_sites
+        {
+            let artifacts = WorkspaceArtifacts::read(&workspace)?;
+            let report = CompileReport {
+                witness_counts: args.print_witness_count.then(|| count_witnesses(&artifacts)),
+                abi_hashes: args.print_abi_hash.then(|| hash_abis(&artifacts)),
+                io_sizes: args.print_io_sizes.then(|| compute_io_sizes(&artifacts)),
+                recursion_info: args
+                    .print_recursion_info
+                    .then(|| collect_recursion_info(&artifacts)),
+                field_ops: args.print_field_ops.then(|| count_field_ops(&artifacts)),
+                call_sites: args.emit_call_sites.then(|| collect_call_sites(&artifacts)),
+            };
+            report.print(args.json);
+        }
+        if args.profile_memory {
+            match peak_memory_usage() {
+                Some(peak_bytes) => {
+                    human_println!(
+                        "Peak memory usage: {:.1} MiB",
+                        peak_bytes as f64 / (1024.0 * 1024.0)
+                    );
+                }
+                None => human_println!("Peak memory usage is not available on this platform"),
+            }
+        }
+        if args.run_tests {
+            run_workspace_tests(
+                &workspace,
+                FunctionNameMatch::Anything,
+                &[],
+                false,
+                None,
+                None,
+                &args.compile_options,
+            )?;
+        }
+        if let Some(exit_code) = args.exit_code_on_warnings {
+            if warning_count > 0 {
+                return Err(CliError::WarningsReported { warning_count, exit_code });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the target directory and the directory of any artifact mapped elsewhere in the manifest
+/// can be written to, so that a read-only location fails before compiling rather than after.
+fn check_artifact_dirs_writable(workspace: &Workspace) -> Result<(), CliError> {
+    let mut dirs = vec![workspace.target_directory_path()];
+    for package in workspace {
+        if let Some(parent) =
+            workspace.package_artifact_path(package).and_then(|path| path.parent())
+        {
+            if !dirs.iter().any(|dir| dir == parent) {
+                dirs.push(parent.to_path_buf());
+            }
+        }
+    }
+
+    for dir in dirs {
+        check_dir_writable(&dir).map_err(|err| CliError::TargetDirectoryNotWritable(dir, err))?;
+    }
+    Ok(())
+}
+
+/// Warns about any artifacts in the target directory which were compiled by a different version of Noir,
+/// erroring instead if `strict` is set.
+fn check_artifact_versions(workspace: &Workspace, strict: bool) -> Result<(), CliError> {
+    let mismatched = find_mismatched_artifact_versions(
+        &workspace.target_directory_path(),
+        NOIR_ARTIFACT_VERSION_STRING,
+    );
+    if mismatched.is_empty() {
+        return Ok(());
     }
 
+    let descriptions: Vec<String> = mismatched
+        .iter()
+        .map(|(path, version)| format!("{} (compiled by {version})", path.display()))
+        .collect();
+    if strict {
+        return Err(CliError::Generic(format!(
+            "Found artifacts compiled by a different version of Noir than {NOIR_ARTIFACT_VERSION_STRING}:\n{}",
+            descriptions.join("\n")
+        )));
+    }
+    for description in descriptions {
+        eprintln!("Warning: stale artifact {description}");
+    }
     Ok(())
 }
 
-fn watch_workspace(workspace: &Workspace, compile_options: &CompileOptions) -> notify::Result<()> {
+/// Returns the peak resident set size of the current process in bytes, as reported by the kernel.
+///
+/// This covers the whole process rather than just compilation, so should be treated as an approximation.
+fn peak_memory_usage() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let peak_kib = status.lines().find_map(|line| {
+        line.strip_prefix("VmHWM:")?.trim().strip_suffix("kB")?.trim().parse::<u64>().ok()
+    })?;
+    Some(peak_kib * 1024)
+}
+
+#[derive(Debug, Serialize)]
+struct DryRunReport {
+    target_directory: PathBuf,
+    packages: Vec<PlannedPackage>,
+    /// The additional steps requested through flags, run once every package is compiled.
+    steps: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlannedPackage {
+    package_name: String,
+    package_type: String,
+    /// The artifact which compiling the package would write, if any.
+    artifact: Option<PathBuf>,
+}
+
+/// Prints the packages which `nargo compile` would compile with the given arguments, along with the artifacts
+/// it would write and any additional steps it would take.
+fn print_dry_run(workspace: &Workspace, args: &CompileCommand) {
+    let target_directory = workspace.target_directory_path();
+    let packages = workspace
+        .into_iter()
+        .map(|package| {
+            let artifact = match package.package_type {
+                PackageType::Binary => Some(workspace.package_build_path(package)),
+                // A contract's artifact is also named after the contract, which isn't known until it's compiled.
+                PackageType::Contract => {
+                    Some(workspace.package_artifact_path(package).cloned().unwrap_or_else(|| {
+                        target_directory.join(format!("{}-*.json", package.name))
+                    }))
+                }
+                PackageType::Library => None,
+            };
+            PlannedPackage {
+                package_name: package.name.to_string(),
+                package_type: package.package_type.to_string(),
+                artifact,
+            }
+        })
+        .collect();
+
+    let mut steps = Vec::new();
+    if !args.compile_options.stub_functions.is_empty() {
+        steps.push(format!(
+            "stub the bodies of: {}",
+            args.compile_options.stub_functions.join(", ")
+        ));
+    }
+    if !args.compile_options.make_public_functions.is_empty() {
+        steps.push(format!(
+            "compile as contract entry points: {}",
+            args.compile_options.make_public_functions.join(", ")
+        ));
+    }
+    if !args.compile_options.make_internal_functions.is_empty() {
+        steps.push(format!(
+            "leave out of the compiled contracts: {}",
+            args.compile_options.make_internal_functions.join(", ")
+        ));
+    }
+    if let Some(entry) = &args.compile_options.reachable_from {
+        steps.push(format!("compile each program from `{entry}` instead of `main`"));
+    }
+    if let Some(entry) = &args.compile_options.entry_function {
+        steps.push(format!("compile `{entry}` as the entry point of each program"));
+    }
+    if let Some(shard_count) = args.shard {
+        steps.push(format!("split each program into {shard_count} shards"));
+    }
+    if !args.allowed_black_box_functions.is_empty() || !args.denied_black_box_functions.is_empty() {
+        steps.push("check the black box functions used by each circuit".to_string());
+    }
+    if let Some(baseline_path) = &args.compare_with {
+        steps.push(format!(
+            "compare against {} with a tolerance of {}%",
+            baseline_path.display(),
+            args.tolerance
+        ));
+    }
+    for (requested, report) in [
+        (args.print_witness_count, "witness counts"),
+        (args.print_abi_hash, "ABI hashes"),
+        (args.print_io_sizes, "input and output sizes"),
+        (args.print_recursion_info, "recursive verifications"),
+        (args.print_field_ops, "field operations"),
+        (args.emit_call_sites, "the source functions of each circuit's opcodes"),
+    ] {
+        if requested {
+            steps.push(format!("print {report}"));
+        }
+    }
+    for format in &args.emit {
+        let representation = match format {
+            EmitFormat::AcirAsm => "ACIR assembly",
+            EmitFormat::Dot => "Graphviz graph",
+            EmitFormat::Mermaid => "Mermaid flowchart",
+        };
+        steps.push(format!("write the {representation} of each circuit"));
+    }
+    if args.run_tests {
+        steps.push("run the tests of each package".to_string());
+    }
+    if let Some(jobs) = args.jobs {
+        steps.push(format!("compile at most {jobs} packages at once"));
+    }
+    if let Some(timeout) = args.compile_timeout {
+        steps.push(format!("abort if compilation takes longer than {timeout} seconds"));
+    }
+
+    let report = DryRunReport { target_directory, packages, steps };
+    if args.json {
+        println!("{}", serde_json::to_string(&report).unwrap());
+        return;
+    }
+
+    human_println!("Output directory: {}", report.target_directory.display());
+    for package in report.packages {
+        match package.artifact {
+            Some(artifact) => human_println!(
+                "[{}] would compile {} package to {}",
+                package.package_name,
+                package.package_type,
+                artifact.display()
+            ),
+            None => human_println!(
+                "[{}] would skip {} package, as libraries have no artifact",
+                package.package_name,
+                package.package_type
+            ),
+        }
+    }
+    for step in report.steps {
+        human_println!("Would {step}");
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct EntryPointsReport {
+    package_name: String,
+    package_type: String,
+    entrypoints: Vec<String>,
+}
+
+/// Type-checks each package in the workspace and lists the entry points which a build would produce artifacts for:
+/// `main` for binaries, the entry-point functions of contracts and any `#[export]` functions of libraries.
+fn print_entrypoints(
+    workspace: &Workspace,
+    compile_options: &CompileOptions,
+    json: bool,
+) -> Result<(), CliError> {
+    let mut workspace_file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager(workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
+    let mut reports = Vec::new();
+    for package in workspace {
+        let (mut context, crate_id) =
+            prepare_package(&workspace_file_manager, &parsed_files, package);
+        check_crate_and_report_errors(&mut context, crate_id, compile_options)?;
+
+        let entrypoints = match package.package_type {
+            PackageType::Binary => context
+                .get_main_function(&crate_id)
+                .map(|main| vec![context.function_name(&main).to_owned()])
+                .unwrap_or_default(),
+            PackageType::Contract => context
+                .get_all_contracts(&crate_id)
+                .into_iter()
+                .flat_map(|contract| {
+                    contract
+                        .functions
+                        .into_iter()
+                        .filter(|function| function.is_entry_point)
+                        .map(|function| context.function_name(&function.function_id).to_owned())
+                        .collect::<Vec<_>>()
+                })
+                .collect(),
+            PackageType::Library => context
+                .get_all_exported_functions_in_crate(&crate_id)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect(),
+        };
+
+        reports.push(EntryPointsReport {
+            package_name: package.name.to_string(),
+            package_type: package.package_type.to_string(),
+            entrypoints,
+        });
+    }
+
+    if json {
+        println!("{}", serde_json::to_string(&reports).unwrap());
+    } else {
+        for report in reports {
+            for entrypoint in report.entrypoints {
+                human_println!("[{}] {entrypoint}", report.package_name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct UnsupportedOpcodes {
+    package_name: String,
+    function_name: String,
+    black_box: &'static str,
+    count: usize,
+}
+
+/// Compiles each package in the workspace without optimizing it, then lists the calls its circuits make
+/// to black box functions which aren't permitted by `--allow-blackbox` and `--deny-blackbox`.
+///
+/// Errors if there were any such calls.
+fn list_unsupported_opcodes(workspace: &Workspace, args: &CompileCommand) -> Result<(), CliError> {
+    let compile_options = &args.compile_options;
+    let mut workspace_file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager(workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
+    let mut unsupported = Vec::new();
+    for package in workspace {
+        let circuits: Vec<(String, Circuit<FieldElement>)> = match package.package_type {
+            PackageType::Binary => {
+                let program = report_errors(
+                    compile_program(
+                        &workspace_file_manager,
+                        &parsed_files,
+                        workspace,
+                        package,
+                        compile_options,
+                        None,
+                    ),
+                    &workspace_file_manager,
+                    compile_options.deny_warnings,
+                    compile_options.silence_warnings,
+                    compile_options.message_format,
+                )?;
+                program.names.into_iter().zip(program.program.functions).collect()
+            }
+            PackageType::Contract => {
+                let contract = report_errors(
+                    compile_contract(
+                        &workspace_file_manager,
+                        &parsed_files,
+                        package,
+                        compile_options,
+                        None,
+                    ),
+                    &workspace_file_manager,
+                    compile_options.deny_warnings,
+                    compile_options.silence_warnings,
+                    compile_options.message_format,
+                )?;
+                contract
+                    .functions
+                    .into_iter()
+                    .flat_map(|function| {
+                        let name = function.name;
+                        function.bytecode.functions.into_iter().enumerate().map(
+                            move |(index, circuit)| {
+                                // Subsequent ACIR functions of a contract function are suffixed by their index.
+                                let name = if index == 0 {
+                                    name.clone()
+                                } else {
+                                    format!("{name}[{index}]")
+                                };
+                                (name, circuit)
+                            },
+                        )
+                    })
+                    .collect()
+            }
+            PackageType::Library => Vec::new(),
+        };
+
+        for (function_name, circuit) in circuits {
+            let counts = count_disallowed_black_box_functions(
+                &circuit,
+                &args.allowed_black_box_functions,
+                &args.denied_black_box_functions,
+            );
+            unsupported.extend(counts.into_iter().map(|(black_box, count)| UnsupportedOpcodes {
+                package_name: package.name.to_string(),
+                function_name: function_name.clone(),
+                black_box,
+                count,
+            }));
+        }
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string(&unsupported).unwrap());
+    } else if unsupported.is_empty() {
+        human_println!("No unsupported opcodes");
+    } else {
+        for entry in &unsupported {
+            human_println!(
+                "[{}] {}: {} call{} to {}",
+                entry.package_name,
+                entry.function_name,
+                entry.count,
+                if entry.count == 1 { "" } else { "s" },
+                entry.black_box
+            );
+        }
+    }
+
+    if unsupported.is_empty() {
+        Ok(())
+    } else {
+        let total: usize = unsupported.iter().map(|entry| entry.count).sum();
+        Err(CliError::Generic(format!(
+            "Found {total} calls to black box functions which are not permitted"
+        )))
+    }
+}
+
+/// Compiles the workspace, then recompiles it each time a Noir source file or manifest within it changes.
+///
+/// Changes to a manifest re-resolve the workspace using `resolve_workspace`, keeping the previous
+/// workspace if the manifest is invalid. Failed compilations are reported without stopping the watcher,
+/// which runs until nargo is interrupted. Artifacts are moved into place once fully written,
+/// so interrupting a compilation never leaves a partial artifact behind.
+fn watch_workspace(
+    mut workspace: Workspace,
+    resolve_workspace: impl Fn() -> Result<Workspace, ManifestError>,
+    compile_options: &CompileOptions,
+) -> notify::Result<()> {
     let (tx, rx) = std::sync::mpsc::channel();
 
     // No specific tickrate, max debounce time 1 seconds
@@ -78,28 +798,41 @@ fn watch_workspace(workspace: &Workspace, compile_options: &CompileOptions) -> n
     let mut screen = std::io::stdout();
     write!(screen, "{}", termion::cursor::Save).unwrap();
     screen.flush().unwrap();
-    let _ = compile_workspace_full(workspace, compile_options);
+    report_watched_compilation(&workspace, compile_options);
     for res in rx {
         let debounced_events = res.map_err(|mut err| err.remove(0))?;
 
-        // We only want to trigger a rebuild if a noir source file has been modified.
-        let noir_files_modified = debounced_events.iter().any(|event| {
-            let mut event_paths = event.event.paths.iter();
-            let event_affects_noir_file =
-                event_paths.any(|path| path.extension().map_or(false, |ext| ext == "nr"));
+        let relevant_paths = || {
+            debounced_events
+                .iter()
+                .filter(|event| {
+                    matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    )
+                })
+                .flat_map(|event| event.event.paths.iter())
+        };
 
-            let is_relevant_event_kind = matches!(
-                event.kind,
-                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
-            );
-
-            is_relevant_event_kind && event_affects_noir_file
-        });
+        // We only want to trigger a rebuild if a noir source file or a manifest has been modified.
+        let noir_files_modified =
+            relevant_paths().any(|path| path.extension().map_or(false, |ext| ext == "nr"));
+        let manifest_modified =
+            relevant_paths().any(|path| path.file_name().map_or(false, |name| name == PKG_FILE));
 
-        if noir_files_modified {
+        if noir_files_modified || manifest_modified {
             write!(screen, "{}{}", termion::cursor::Restore, termion::clear::AfterCursor).unwrap();
             screen.flush().unwrap();
-            let _ = compile_workspace_full(workspace, compile_options);
+            if manifest_modified {
+                match resolve_workspace() {
+                    Ok(resolved_workspace) => workspace = resolved_workspace,
+                    Err(err) => {
+                        eprintln!("{err}");
+                        continue;
+                    }
+                }
+            }
+            report_watched_compilation(&workspace, compile_options);
         }
     }
 
@@ -108,24 +841,112 @@ fn watch_workspace(workspace: &Workspace, compile_options: &CompileOptions) -> n
     Ok(())
 }
 
+/// Compiles the workspace for `--watch`, printing rather than returning any error so that watching can continue.
+fn report_watched_compilation(workspace: &Workspace, compile_options: &CompileOptions) {
+    if let Err(err) = compile_workspace_full(workspace, compile_options) {
+        eprintln!("{err}");
+    }
+}
+
+/// Compiles all packages in the workspace, saving their artifacts and reporting any warnings or errors.
+///
+/// Returns the number of warnings which were reported.
 pub(super) fn compile_workspace_full(
     workspace: &Workspace,
     compile_options: &CompileOptions,
-) -> Result<(), CliError> {
+) -> Result<usize, CliError> {
+    compile_workspace_with_jobs(workspace, compile_options, None, &ArtifactChecks::default())
+}
+
+/// Runs [compile_workspace_full], compiling at most `jobs` packages at once
+/// and only saving the artifacts which pass `checks`.
+fn compile_workspace_with_jobs(
+    workspace: &Workspace,
+    compile_options: &CompileOptions,
+    jobs: Option<u32>,
+    checks: &ArtifactChecks,
+) -> Result<usize, CliError> {
+    check_stdlib_revisions(workspace)?;
+
     let mut workspace_file_manager = workspace.new_file_manager();
     insert_all_files_for_workspace_into_file_manager(workspace, &mut workspace_file_manager);
-    let parsed_files = parse_all(&workspace_file_manager);
+    let mut parsed_files = parse_all(&workspace_file_manager);
+    stub_functions(&mut parsed_files, &compile_options.stub_functions);
+
+    let compiled_workspace = compile_workspace(
+        &workspace_file_manager,
+        &parsed_files,
+        workspace,
+        compile_options,
+        jobs,
+        checks,
+    );
 
-    let compiled_workspace =
-        compile_workspace(&workspace_file_manager, &parsed_files, workspace, compile_options);
+    let warning_count = match &compiled_workspace {
+        Ok((_, warnings)) if !compile_options.silence_warnings => {
+            warnings.iter().filter(|warning| warning.diagnostic.is_warning()).count()
+        }
+        _ => 0,
+    };
 
     report_errors(
         compiled_workspace,
         &workspace_file_manager,
         compile_options.deny_warnings,
         compile_options.silence_warnings,
+        compile_options.message_format,
     )?;
 
+    Ok(warning_count)
+}
+
+/// Runs [compile_workspace_with_jobs] on a separate thread, erroring if it doesn't finish within `timeout`.
+///
+/// Compilation can't be interrupted part way through, so on timeout the compiling thread is
+/// abandoned and is stopped once nargo exits with the returned error.
+fn compile_workspace_with_timeout(
+    workspace: &Workspace,
+    compile_options: &CompileOptions,
+    jobs: Option<u32>,
+    checks: &ArtifactChecks,
+    timeout: Duration,
+) -> Result<usize, CliError> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let workspace = workspace.clone();
+    let compile_options = compile_options.clone();
+    let checks = checks.clone();
+    std::thread::spawn(move || {
+        // The receiver is gone if the deadline has already passed, in which case the result is discarded.
+        let _ =
+            sender.send(compile_workspace_with_jobs(&workspace, &compile_options, jobs, &checks));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(CliError::CompileTimeout(timeout.as_secs())),
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(CliError::Generic("Compilation stopped unexpectedly".to_string()))
+        }
+    }
+}
+
+/// Checks that any standard library revision pinned by a package matches the one embedded in this version of nargo.
+///
+/// The standard library is compiled into nargo so the embedded revision is the only one available.
+fn check_stdlib_revisions(workspace: &Workspace) -> Result<(), CompileError> {
+    for package in workspace {
+        let Some(required) = &package.stdlib_revision else {
+            continue;
+        };
+        // Allow abbreviated revisions in the same way as git does.
+        if required.is_empty() || !GIT_COMMIT.starts_with(required.as_str()) {
+            return Err(CompileError::UnavailableStdlibRevision {
+                package: package.name.clone(),
+                required: required.clone(),
+                available: GIT_COMMIT.to_string(),
+            });
+        }
+    }
     Ok(())
 }
 
@@ -134,6 +955,8 @@ fn compile_workspace(
     parsed_files: &ParsedFiles,
     workspace: &Workspace,
     compile_options: &CompileOptions,
+    jobs: Option<u32>,
+    checks: &ArtifactChecks,
 ) -> CompilationResult<()> {
     let (binary_packages, contract_packages): (Vec<_>, Vec<_>) = workspace
         .into_iter()
@@ -141,16 +964,41 @@ fn compile_workspace(
         .cloned()
         .partition(|package| package.is_binary());
 
-    // Compile all of the packages in parallel.
-    let program_warnings_or_errors: CompilationResult<()> =
-        compile_programs(file_manager, parsed_files, workspace, &binary_packages, compile_options);
-    let contract_warnings_or_errors: CompilationResult<()> = compiled_contracts(
-        file_manager,
-        parsed_files,
-        &contract_packages,
-        compile_options,
-        &workspace.target_directory_path(),
-    );
+    // Configure a thread pool with a larger stack size to prevent overflowing stack in large programs.
+    // Default is 2MB.
+    // A thread count of zero lets rayon pick one thread per logical core.
+    let pool = rayon::ThreadPoolBuilder::new()
+        .stack_size(4 * 1024 * 1024)
+        .num_threads(jobs.unwrap_or_default() as usize)
+        .build()
+        .unwrap();
+
+    // Compile all of the packages in parallel, with programs and contracts sharing the same pool
+    // so that neither kind has to wait for the other to finish.
+    let (program_warnings_or_errors, contract_warnings_or_errors) = pool.install(|| {
+        rayon::join(
+            || {
+                compile_programs(
+                    file_manager,
+                    parsed_files,
+                    workspace,
+                    &binary_packages,
+                    compile_options,
+                    checks,
+                )
+            },
+            || {
+                compiled_contracts(
+                    file_manager,
+                    parsed_files,
+                    workspace,
+                    &contract_packages,
+                    compile_options,
+                    checks,
+                )
+            },
+        )
+    });
 
     match (program_warnings_or_errors, contract_warnings_or_errors) {
         (Ok((_, program_warnings)), Ok((_, contract_warnings))) => {
@@ -170,60 +1018,292 @@ fn compile_programs(
     workspace: &Workspace,
     binary_packages: &[Package],
     compile_options: &CompileOptions,
+    checks: &ArtifactChecks,
 ) -> CompilationResult<()> {
+    let optimization_level = compile_options.opt_level.unwrap_or_default();
     let load_cached_program = |package| {
         let program_artifact_path = workspace.package_build_path(package);
         read_program_from_file(program_artifact_path)
             .ok()
             .filter(|p| p.noir_version == NOIR_ARTIFACT_VERSION_STRING)
+            .filter(|p| {
+                p.optimization_level.unwrap_or(OptimizationLevel::default() as u8)
+                    == optimization_level as u8
+            })
             .map(|p| p.into())
     };
 
-    let compile_package = |package| {
-        let (program, warnings) = compile_program(
-            file_manager,
-            parsed_files,
-            workspace,
-            package,
-            compile_options,
-            load_cached_program(package),
-        )?;
+    let compile_package = |package: &Package| {
+        let compile_options = &package.compile.apply_to(compile_options);
+        let cache_key = program_cache_key(file_manager, package, compile_options);
+
+        // An artifact built from the same sources and options is already up to date.
+        // It's rebuilt anyway when the options ask for output which is only produced while compiling,
+        // as that output would otherwise be missing.
+        let bypass_cache = compile_options.requires_fresh_compile();
+        if !bypass_cache {
+            let existing_artifact = read_program_from_file(workspace.package_build_path(package));
+            if let Ok(artifact) = existing_artifact {
+                if artifact.source_hash.as_ref() == Some(&cache_key) {
+                    checks.check_program(package, &artifact)?;
+                    if compile_options.print_acir_stats {
+                        print_acir_stats(
+                            &package.name.to_string(),
+                            &artifact.names,
+                            &artifact.bytecode,
+                        );
+                    }
+                    return Ok(((), Vec::new()));
+                }
+            }
+        }
+
+        let cached_program =
+            if bypass_cache { None } else { read_cached_program(workspace, &cache_key) };
+        let (program, warnings) = match cached_program {
+            Some(program) => (program, Vec::new()),
+            None => {
+                // A previous artifact's bytecode is already optimized, so it can't stand in for the unoptimized program.
+                let cached_program = if compile_options.keep_unoptimized_debug {
+                    None
+                } else {
+                    load_cached_program(package)
+                };
+                let (program, warnings) = compile_program(
+                    file_manager,
+                    parsed_files,
+                    workspace,
+                    package,
+                    compile_options,
+                    cached_program,
+                )?;
+                // Programs with warnings aren't cached, so that their warnings are reported again next time.
+                if warnings.is_empty() {
+                    write_cached_program(workspace, package, &cache_key, &program);
+                }
+                if let Some(entry) = &compile_options.reachable_from {
+                    print_unreachable_functions(package, entry, &program.unreachable_functions);
+                }
+                (program, warnings)
+            }
+        };
+
+        if compile_options.print_monomorphized_count {
+            print_monomorphized_count(package, &program.monomorphized_functions);
+        }
+
+        let unoptimized_debug = compile_options.keep_unoptimized_debug.then(|| UnoptimizedDebug {
+            bytecode: program.program.clone(),
+            debug_symbols: ProgramDebugInfo { debug_infos: program.debug.clone() },
+        });
+
+        let assertion_counts = nargo::ops::count_assertions(&program.program);
 
         let target_width =
             get_target_width(package.expression_width, compile_options.expression_width);
-        let program = nargo::ops::transform_program(program, target_width);
+        let program = if compile_options.profile_opt {
+            let mut profile = OptimizationProfile::default();
+            let program = nargo::ops::transform_program_with_profile(
+                program,
+                target_width,
+                optimization_level,
+                &mut profile,
+            );
+            human_println!("[{}] Optimization profile:\n{profile}", package.name);
+            program
+        } else {
+            nargo::ops::transform_program_with_optimization_level(
+                program,
+                target_width,
+                optimization_level,
+            )
+        };
         nargo::ops::check_program(&program)?;
-        save_program_to_file(&program.into(), &package.name, workspace.target_directory_path());
+        if compile_options.verify_no_assertions_stripped {
+            nargo::ops::check_assertions_preserved(
+                &program.program,
+                &program.names,
+                &assertion_counts,
+            )?;
+        }
+        if compile_options.print_acir_stats {
+            print_acir_stats(&package.name.to_string(), &program.names, &program.program);
+        }
+        let mut artifact: ProgramArtifact = program.into();
+        artifact.optimization_level = Some(optimization_level as u8);
+        artifact.entry_function = compile_options
+            .reachable_from
+            .clone()
+            .or_else(|| compile_options.entry_function.clone())
+            .or_else(|| package.entry_function.clone());
+        // As with the cache, programs with warnings are never up to date so that their warnings are reported again.
+        artifact.source_hash = warnings.is_empty().then_some(cache_key);
+        artifact.unoptimized_debug = unoptimized_debug;
+        if compile_options.trim_paths {
+            nargo::ops::trim_debug_paths(&mut artifact.file_map, package);
+        }
+        checks.check_program(package, &artifact)?;
+        save_program_to_path(
+            &artifact,
+            &workspace.package_build_path(package),
+            compile_options.pretty_artifacts,
+        );
 
         Ok(((), warnings))
     };
 
-    // Configure a thread pool with a larger stack size to prevent overflowing stack in large programs.
-    // Default is 2MB.
-    let pool = rayon::ThreadPoolBuilder::new().stack_size(4 * 1024 * 1024).build().unwrap();
     let program_results: Vec<CompilationResult<()>> =
-        pool.install(|| binary_packages.par_iter().map(compile_package).collect());
+        binary_packages.par_iter().map(compile_package).collect();
 
     // Collate any warnings/errors which were encountered during compilation.
     collect_errors(program_results).map(|(_, warnings)| ((), warnings))
 }
 
+/// Prints the number of function instances generated by monomorphizing the package's program,
+/// followed by the functions which were instantiated more than once, most instantiated first.
+fn print_monomorphized_count(package: &Package, monomorphized_functions: &BTreeMap<String, usize>) {
+    let total: usize = monomorphized_functions.values().sum();
+    let mut instantiated: Vec<(&String, &usize)> =
+        monomorphized_functions.iter().filter(|(_, count)| **count > 1).collect();
+    instantiated.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+    // The report is built up front so that packages compiled in parallel don't interleave their lines.
+    let mut report = format!("[{}] {total} monomorphized functions", package.name);
+    for (name, count) in instantiated {
+        report.push_str(&format!("\n    {count} instances of {name}"));
+    }
+    human_println!("{report}");
+}
+
+/// Prints the functions of the package which were left out as unreachable from the `--reachable-from` entry point.
+fn print_unreachable_functions(package: &Package, entry: &str, unreachable_functions: &[String]) {
+    // The report is built up front so that packages compiled in parallel don't interleave their lines.
+    let report = if unreachable_functions.is_empty() {
+        format!("[{}] Every function is reachable from `{entry}`", package.name)
+    } else {
+        let mut report =
+            format!("[{}] Functions excluded as unreachable from `{entry}`:", package.name);
+        for name in unreachable_functions {
+            report.push_str(&format!("\n    {name}"));
+        }
+        report
+    };
+    human_println!("{report}");
+}
+
+/// Prints a table of statistics for each circuit of a compiled program or contract function.
+fn print_acir_stats(name: &str, names: &[String], program: &Program<FieldElement>) {
+    // The report is built up front so that packages compiled in parallel don't interleave their tables.
+    let mut report = format!("[{name}] ACIR statistics");
+    for circuit_info in program_circuit_info(names, program) {
+        report.push_str(&format!(
+            "\n{}\n{}",
+            circuit_info.function_name,
+            circuit_info_table(&circuit_info)
+        ));
+    }
+    human_println!("{}", report.trim_end());
+}
+
 fn compiled_contracts(
     file_manager: &FileManager,
     parsed_files: &ParsedFiles,
+    workspace: &Workspace,
     contract_packages: &[Package],
     compile_options: &CompileOptions,
-    target_dir: &Path,
+    checks: &ArtifactChecks,
 ) -> CompilationResult<()> {
+    let load_cached_contract = |package| {
+        // Functions reused from a previous artifact are already optimized, so there'd be no unoptimized debug symbols to keep.
+        if compile_options.only_changed_functions && !compile_options.keep_unoptimized_debug {
+            read_cached_contract(workspace, package, compile_options)
+        } else {
+            None
+        }
+    };
+
     let contract_results: Vec<CompilationResult<()>> = contract_packages
         .par_iter()
         .map(|package| {
-            let (contract, warnings) =
-                compile_contract(file_manager, parsed_files, package, compile_options)?;
+            let compile_options = &package.compile.apply_to(compile_options);
+            let cached_contract = load_cached_contract(package);
+            let (contract, warnings) = compile_contract(
+                file_manager,
+                parsed_files,
+                package,
+                compile_options,
+                cached_contract.clone().map(CompiledContract::from),
+            )?;
+            let unoptimized_debug: BTreeMap<String, UnoptimizedDebug> = if compile_options
+                .keep_unoptimized_debug
+            {
+                contract
+                    .functions
+                    .iter()
+                    .map(|function| {
+                        let unoptimized_debug = UnoptimizedDebug {
+                            bytecode: function.bytecode.clone(),
+                            debug_symbols: ProgramDebugInfo { debug_infos: function.debug.clone() },
+                        };
+                        (function.name.clone(), unoptimized_debug)
+                    })
+                    .collect()
+            } else {
+                BTreeMap::new()
+            };
+
+            let assertion_counts: BTreeMap<String, Vec<usize>> = contract
+                .functions
+                .iter()
+                .map(|function| {
+                    (function.name.clone(), nargo::ops::count_assertions(&function.bytecode))
+                })
+                .collect();
+
             let target_width =
                 get_target_width(package.expression_width, compile_options.expression_width);
-            let contract = nargo::ops::transform_contract(contract, target_width);
-            save_contract(contract, package, target_dir, compile_options.show_artifact_paths);
+            let optimization_level = compile_options.opt_level.unwrap_or_default();
+            let contract = if compile_options.profile_opt {
+                let mut profile = OptimizationProfile::default();
+                let contract = nargo::ops::transform_contract_with_profile(
+                    contract,
+                    target_width,
+                    optimization_level,
+                    &mut profile,
+                );
+                human_println!("[{}] Optimization profile:\n{profile}", package.name);
+                contract
+            } else {
+                nargo::ops::transform_contract_with_optimization_level(
+                    contract,
+                    target_width,
+                    optimization_level,
+                )
+            };
+            if compile_options.verify_no_assertions_stripped {
+                for function in &contract.functions {
+                    nargo::ops::check_assertions_preserved(
+                        &function.bytecode,
+                        &function.names,
+                        &assertion_counts[&function.name],
+                    )?;
+                }
+            }
+            if compile_options.print_acir_stats {
+                for function in &contract.functions {
+                    let name = format!("{}::{}", contract.name, function.name);
+                    print_acir_stats(&name, &function.names, &function.bytecode);
+                }
+            }
+            save_contract(
+                contract,
+                cached_contract,
+                unoptimized_debug,
+                workspace,
+                package,
+                compile_options,
+                checks,
+            )?;
             Ok(((), warnings))
         })
         .collect();
@@ -232,21 +1312,144 @@ fn compiled_contracts(
     collect_errors(contract_results).map(|(_, warnings)| ((), warnings))
 }
 
+/// Reads the contract artifact previously saved for `package`, if there is one compiled with the current version of Noir.
+fn read_cached_contract(
+    workspace: &Workspace,
+    package: &Package,
+    compile_options: &CompileOptions,
+) -> Option<ContractArtifact> {
+    let optimization_level = compile_options.opt_level.unwrap_or_default() as u8;
+    read_workspace_contract(workspace, package)
+        .filter(|contract| contract.noir_version == NOIR_ARTIFACT_VERSION_STRING)
+        .filter(|contract| {
+            contract.optimization_level.unwrap_or(OptimizationLevel::default() as u8)
+                == optimization_level
+        })
+}
+
+/// Saves the contract artifact of `package`, unless it fails `checks`.
+///
+/// Functions which are unchanged since `cached_contract` keep the data they had in the previous artifact.
 fn save_contract(
     contract: CompiledContract,
+    cached_contract: Option<ContractArtifact>,
+    mut unoptimized_debug: BTreeMap<String, UnoptimizedDebug>,
+    workspace: &Workspace,
     package: &Package,
-    target_dir: &Path,
-    show_artifact_paths: bool,
-) {
+    compile_options: &CompileOptions,
+    checks: &ArtifactChecks,
+) -> Result<(), ErrorsAndWarnings> {
     let contract_name = contract.name.clone();
-    let artifact_path = save_contract_to_file(
-        &contract.into(),
-        &format!("{}-{}", package.name, contract_name),
-        target_dir,
+    let mut artifact: ContractArtifact = contract.into();
+    artifact.optimization_level = Some(compile_options.opt_level.unwrap_or_default() as u8);
+    let made_public = compile_options.make_public_functions.iter().map(|name| (name, "public"));
+    let made_internal =
+        compile_options.make_internal_functions.iter().map(|name| (name, "internal"));
+    artifact.visibility_overrides = made_public
+        .chain(made_internal)
+        .map(|(name, visibility)| (name.clone(), visibility.to_string()))
+        .collect();
+    for function in &mut artifact.functions {
+        function.unoptimized_debug = unoptimized_debug.remove(&function.name);
+    }
+    if let Some(cached_contract) = cached_contract {
+        artifact.reuse_unchanged_functions(cached_contract);
+    }
+    if compile_options.trim_paths {
+        nargo::ops::trim_debug_paths(&mut artifact.file_map, package);
+    }
+    checks.check_contract(package, &artifact)?;
+    let artifact_path = match workspace.package_artifact_path(package) {
+        Some(artifact_path) => {
+            save_contract_to_path(&artifact, artifact_path, compile_options.pretty_artifacts)
+        }
+        None => save_contract_to_file(
+            &artifact,
+            &format!("{}-{}", package.name, contract_name),
+            workspace.target_directory_path(),
+            compile_options.pretty_artifacts,
+        ),
+    };
+    if compile_options.show_artifact_paths {
+        human_println!("Saved contract artifact to: {}", artifact_path.display());
+    }
+
+    // Named after the package as well as the contract, as the artifact is, so that packages in the same workspace
+    // which have contracts of the same name don't overwrite each other's ABI.
+    let abi_path =
+        artifact_path.with_file_name(format!("{}-{contract_name}.abi.json", package.name));
+    let abi_path = save_contract_abi_to_path(
+        &ContractAbiArtifact::from(&artifact),
+        &abi_path,
+        compile_options.pretty_artifacts,
     );
-    if show_artifact_paths {
-        println!("Saved contract artifact to: {}", artifact_path.display());
+    if compile_options.show_artifact_paths {
+        human_println!("Saved contract ABI to: {}", abi_path.display());
     }
+    Ok(())
+}
+
+/// Checks which each compiled program and contract must pass before its artifact is saved,
+/// so that an artifact which fails them never replaces the one left by a previous compilation.
+#[derive(Clone, Default)]
+struct ArtifactChecks {
+    allowed_black_box_functions: Vec<BlackBoxFunc>,
+    denied_black_box_functions: Vec<BlackBoxFunc>,
+    baseline: Option<Baseline>,
+}
+
+impl ArtifactChecks {
+    fn check_program(
+        &self,
+        package: &Package,
+        program: &ProgramArtifact,
+    ) -> Result<(), ErrorsAndWarnings> {
+        let circuits = program.names.iter().cloned().zip(&program.bytecode.functions);
+        self.check_black_box_functions(package, circuits)?;
+        if let Some(baseline) = &self.baseline {
+            baseline.compare_program(program).map_err(|err| vec![artifact_check_error(&err)])?;
+        }
+        Ok(())
+    }
+
+    fn check_contract(
+        &self,
+        package: &Package,
+        contract: &ContractArtifact,
+    ) -> Result<(), ErrorsAndWarnings> {
+        self.check_black_box_functions(package, contract_circuits(contract))?;
+        if let Some(baseline) = &self.baseline {
+            baseline.compare_contract(contract).map_err(|err| vec![artifact_check_error(&err)])?;
+        }
+        Ok(())
+    }
+
+    /// Errors if any of the circuits calls a black box function which is denied,
+    /// or which isn't allowed when an allowlist is given.
+    fn check_black_box_functions<'a>(
+        &self,
+        package: &Package,
+        circuits: impl IntoIterator<Item = (String, &'a Circuit<FieldElement>)>,
+    ) -> Result<(), ErrorsAndWarnings> {
+        for (function_name, circuit) in circuits {
+            let disallowed = count_disallowed_black_box_functions(
+                circuit,
+                &self.allowed_black_box_functions,
+                &self.denied_black_box_functions,
+            );
+            if let Some(black_box) = disallowed.keys().next() {
+                return Err(vec![artifact_check_error(&format!(
+                    "function `{function_name}` in package `{}` uses the black box function `{black_box}`, which is not permitted",
+                    package.name
+                ))]);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn artifact_check_error(message: &str) -> FileDiagnostic {
+    FileDiagnostic { file_id: FileId::dummy(), diagnostic: CustomDiagnostic::from_message(message) }
 }
 
 /// If a target width was not specified in the CLI we can safely override the default.
@@ -0,0 +1,246 @@
+//! Recompiles a program artifact from the sources embedded in its file map and checks that
+//! the result matches the artifact, so that an artifact can be shown to be reproducible.
+//!
+//! The embedded sources only cover the files which contributed to the program's debug information,
+//! so the workspace's manifest is still used to build the crate graph and any other file is read from disk.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use acvm::acir::circuit::asm::print_circuit;
+use acvm::compiler::OptimizationLevel;
+use fm::FileManager;
+use nargo::ops::{compile_program, report_errors};
+use nargo::package::Package;
+use nargo::workspace::Workspace;
+use nargo::{insert_all_files_for_workspace_into_file_manager_with_overrides, parse_all};
+use noirc_artifacts::program::ProgramArtifact;
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::hir::ParsedFiles;
+
+use crate::errors::CliError;
+
+use super::compile_cmd::get_target_width;
+use super::fs::program::read_program_from_file;
+
+/// The number of unchanged lines printed around each difference.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Recompiles the program artifact at `artifact_path` from its embedded sources,
+/// printing a diff of each part which doesn't match the original.
+pub(super) fn replay_artifact(
+    workspace: &Workspace,
+    artifact_path: &Path,
+    compile_options: &CompileOptions,
+) -> Result<(), CliError> {
+    let original = read_program_from_file(artifact_path)?;
+    let package = find_artifact_package(workspace, artifact_path)?;
+
+    if original.noir_version != NOIR_ARTIFACT_VERSION_STRING {
+        eprintln!(
+            "Warning: {} was compiled by Noir {} but is being replayed with {NOIR_ARTIFACT_VERSION_STRING}",
+            artifact_path.display(),
+            original.noir_version,
+        );
+    }
+
+    let overrides: HashMap<&Path, &str> = original
+        .file_map
+        .values()
+        .map(|file| (file.path.as_path(), file.source.as_str()))
+        .collect();
+    let mut file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager_with_overrides(
+        workspace,
+        &mut file_manager,
+        &overrides,
+    );
+    for path in overrides.keys() {
+        if !file_manager.has_file(path) {
+            eprintln!(
+                "Warning: embedded source {} is not part of the workspace and was ignored",
+                path.display()
+            );
+        }
+    }
+    let parsed_files = parse_all(&file_manager);
+
+    let replayed =
+        recompile(&file_manager, &parsed_files, workspace, package, &original, compile_options)?;
+
+    let mut matches = true;
+    if original.noir_version != replayed.noir_version {
+        matches = false;
+        human_println!(
+            "Noir version differs: {} != {}",
+            original.noir_version,
+            replayed.noir_version
+        );
+    }
+    if original.hash != replayed.hash {
+        matches = false;
+        human_println!("Program hash differs: {} != {}", original.hash, replayed.hash);
+    }
+    let original_abi = serde_json::to_string_pretty(&original.abi).unwrap();
+    let replayed_abi = serde_json::to_string_pretty(&replayed.abi).unwrap();
+    matches &= print_diff("ABI", &original_abi, &replayed_abi);
+
+    let original_functions = &original.bytecode.functions;
+    let replayed_functions = &replayed.bytecode.functions;
+    if original_functions.len() != replayed_functions.len() {
+        matches = false;
+        human_println!(
+            "Number of ACIR functions differs: {} != {}",
+            original_functions.len(),
+            replayed_functions.len()
+        );
+    }
+    for (index, (original_circuit, replayed_circuit)) in
+        original_functions.iter().zip(replayed_functions).enumerate()
+    {
+        let name = original.names.get(index).map_or("<unnamed>", String::as_str);
+        matches &= print_diff(
+            &format!("ACIR function {index} ({name})"),
+            &print_circuit(original_circuit),
+            &print_circuit(replayed_circuit),
+        );
+    }
+
+    let original_brillig = &original.bytecode.unconstrained_functions;
+    let replayed_brillig = &replayed.bytecode.unconstrained_functions;
+    if original_brillig.len() != replayed_brillig.len() {
+        matches = false;
+        human_println!(
+            "Number of unconstrained functions differs: {} != {}",
+            original_brillig.len(),
+            replayed_brillig.len()
+        );
+    }
+    for (index, (original_function, replayed_function)) in
+        original_brillig.iter().zip(replayed_brillig).enumerate()
+    {
+        if original_function != replayed_function {
+            let name = original.brillig_names.get(index).map_or("<unnamed>", String::as_str);
+            matches &= print_diff(
+                &format!("Unconstrained function {index} ({name})"),
+                &format!("{original_function:#?}"),
+                &format!("{replayed_function:#?}"),
+            );
+        }
+    }
+
+    if matches {
+        human_println!("[{}] Reproduced {}", package.name, artifact_path.display());
+        Ok(())
+    } else {
+        Err(CliError::ReplayMismatch(artifact_path.to_path_buf()))
+    }
+}
+
+/// Finds the package which `artifact_path` is the build artifact of, falling back to
+/// the package named after the artifact's file.
+fn find_artifact_package<'a>(
+    workspace: &'a Workspace,
+    artifact_path: &Path,
+) -> Result<&'a Package, CliError> {
+    let artifact_path = artifact_path.with_extension("json");
+    let canonical_path = artifact_path.canonicalize().ok();
+    let file_stem = artifact_path.file_stem().and_then(|stem| stem.to_str());
+
+    let packages: Vec<&Package> =
+        workspace.into_iter().filter(|package| package.is_binary()).collect();
+    let by_build_path = packages.iter().find(|package| {
+        let build_path = workspace.package_build_path(package).with_extension("json");
+        canonical_path.is_some() && build_path.canonicalize().ok() == canonical_path
+    });
+    by_build_path
+        .or_else(|| {
+            packages.iter().find(|package| Some(package.name.to_string().as_str()) == file_stem)
+        })
+        .copied()
+        .ok_or_else(|| {
+            CliError::Generic(format!(
+                "No binary package in the workspace builds {}",
+                artifact_path.display()
+            ))
+        })
+}
+
+fn recompile(
+    file_manager: &FileManager,
+    parsed_files: &ParsedFiles,
+    workspace: &Workspace,
+    package: &Package,
+    original: &ProgramArtifact,
+    compile_options: &CompileOptions,
+) -> Result<ProgramArtifact, CliError> {
+    // The entry point is taken from the artifact in case it was compiled from a function other than `main`.
+    let compile_options = &CompileOptions {
+        entry_function: original
+            .entry_function
+            .clone()
+            .or_else(|| compile_options.entry_function.clone()),
+        ..compile_options.clone()
+    };
+    let compilation_result =
+        compile_program(file_manager, parsed_files, workspace, package, compile_options, None);
+    let program = report_errors(
+        compilation_result,
+        file_manager,
+        compile_options.deny_warnings,
+        compile_options.silence_warnings,
+        compile_options.message_format,
+    )?;
+
+    // The optimization level is taken from the artifact so that it's replayed the way it was built.
+    let optimization_level = original
+        .optimization_level
+        .and_then(OptimizationLevel::from_u8)
+        .or(compile_options.opt_level)
+        .unwrap_or_default();
+    let target_width = get_target_width(package.expression_width, compile_options.expression_width);
+    let program = nargo::ops::transform_program_with_optimization_level(
+        program,
+        target_width,
+        optimization_level,
+    );
+    Ok(program.into())
+}
+
+/// Prints a line diff between `original` and `replayed` under `title`, returning whether they match.
+///
+/// Only the range between the common prefix and suffix is reported, with a few lines of context.
+fn print_diff(title: &str, original: &str, replayed: &str) -> bool {
+    if original == replayed {
+        return true;
+    }
+
+    let original: Vec<&str> = original.lines().collect();
+    let replayed: Vec<&str> = replayed.lines().collect();
+    let prefix = original.iter().zip(&replayed).take_while(|(a, b)| a == b).count();
+    let suffix = original[prefix..]
+        .iter()
+        .rev()
+        .zip(replayed[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    human_println!("{title} differs:");
+    let context_start = prefix.saturating_sub(DIFF_CONTEXT_LINES);
+    human_println!("@@ line {} @@", context_start + 1);
+    for line in &original[context_start..prefix] {
+        human_println!("  {line}");
+    }
+    for line in &original[prefix..original.len() - suffix] {
+        human_println!("- {line}");
+    }
+    for line in &replayed[prefix..replayed.len() - suffix] {
+        human_println!("+ {line}");
+    }
+    let context_end = (original.len() - suffix + DIFF_CONTEXT_LINES).min(original.len());
+    for line in &original[original.len() - suffix..context_end] {
+        human_println!("  {line}");
+    }
+
+    false
+}
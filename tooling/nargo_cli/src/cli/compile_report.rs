@@ -0,0 +1,1105 @@
+//! Reports and checks on the compiled artifacts of a workspace, as requested through the flags of `nargo compile`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use acvm::acir::circuit::asm::print_circuit;
+use acvm::acir::circuit::brillig::{BrilligInputs, BrilligOutputs};
+use acvm::acir::circuit::opcodes::BlackBoxFuncCall;
+use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation};
+use acvm::acir::native_types::{Expression, Witness};
+use acvm::acir::BlackBoxFunc;
+use acvm::{AcirField, FieldElement};
+use fm::FileId;
+use nargo::package::PackageType;
+use nargo::workspace::Workspace;
+use noirc_abi::{Abi, AbiParameter};
+use noirc_artifacts::contract::ContractArtifact;
+use noirc_artifacts::program::ProgramArtifact;
+use noirc_driver::DebugFile;
+use noirc_errors::debug_info::DebugInfo;
+use noirc_errors::Span;
+use noirc_frontend::ast::{NoirFunction, NoirTraitImpl, TypeImpl, Visitor};
+use noirc_frontend::parse_program;
+use prettytable::{row, table};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::errors::CliError;
+
+use super::checksum_cmd::artifact_checksum;
+use super::fs::program::{
+    find_workspace_contract, read_contract_from_file, read_program_from_file,
+    read_workspace_contract,
+};
+use super::fs::write_to_file;
+
+/// The artifacts produced by compiling each binary and contract package in a workspace.
+pub(super) struct WorkspaceArtifacts {
+    programs: Vec<(String, ProgramArtifact)>,
+    contracts: Vec<(String, ContractArtifact)>,
+}
+
+impl WorkspaceArtifacts {
+    /// Reads the artifacts of the selected packages from the workspace's target directory.
+    pub(super) fn read(workspace: &Workspace) -> Result<Self, CliError> {
+        let mut programs = Vec::new();
+        let mut contracts = Vec::new();
+        for package in workspace {
+            if package.is_binary() {
+                let program = read_program_from_file(workspace.package_build_path(package))?;
+                programs.push((package.name.to_string(), program));
+            } else if package.is_contract() {
+                if let Some(contract) = read_workspace_contract(workspace, package) {
+                    contracts.push((package.name.to_string(), contract));
+                }
+            }
+        }
+        Ok(Self { programs, contracts })
+    }
+
+    /// Returns each ACIR function of the workspace's artifacts alongside the name of its package.
+    ///
+    /// Contract functions which are made up of several ACIR functions have each subsequent one suffixed by its index.
+    fn functions(&self) -> Vec<(&str, String, &Circuit<FieldElement>)> {
+        let program_functions = self.programs.iter().flat_map(|(package_name, program)| {
+            program.bytecode.functions.iter().enumerate().map(|(index, circuit)| {
+                (package_name.as_str(), program.names[index].clone(), circuit)
+            })
+        });
+        let contract_functions = self.contracts.iter().flat_map(|(package_name, contract)| {
+            contract_circuits(contract)
+                .into_iter()
+                .map(move |(name, circuit)| (package_name.as_str(), name, circuit))
+        });
+        program_functions.chain(contract_functions).collect()
+    }
+
+    /// Returns the ABI of each program and contract function alongside the name of its package.
+    fn abis(&self) -> Vec<(&str, String, &Abi)> {
+        let program_abis = self.programs.iter().map(|(package_name, program)| {
+            (package_name.as_str(), program.names[0].clone(), &program.abi)
+        });
+        let contract_abis = self.contracts.iter().flat_map(|(package_name, contract)| {
+            contract
+                .functions
+                .iter()
+                .map(move |function| (package_name.as_str(), function.name.clone(), &function.abi))
+        });
+        program_abis.chain(contract_abis).collect()
+    }
+}
+
+/// The reports requested for a compilation, each of which is only present if it was requested.
+#[derive(Debug, Default, Serialize)]
+pub(super) struct CompileReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) witness_counts: Option<Vec<WitnessCount>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) abi_hashes: Option<Vec<AbiHash>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) io_sizes: Option<Vec<IoSizes>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) recursion_info: Option<Vec<RecursionInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) field_ops: Option<Vec<FieldOps>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) call_sites: Option<Vec<CallSites>>,
+}
+
+impl CompileReport {
+    /// Prints the report, either as a single JSON object or as human-readable tables.
+    pub(super) fn print(self, json: bool) {
+        if json {
+            println!("{}", serde_json::to_string(&self).unwrap());
+            return;
+        }
+
+        if let Some(witness_counts) = self.witness_counts {
+            let mut witness_table = table!([Fm->"Package", Fm->"Function", Fm->"Witnesses"]);
+            for witness_count in witness_counts {
+                witness_table.add_row(row![
+                    Fm->format!("{}", witness_count.package_name),
+                    Fc->format!("{}", witness_count.function_name),
+                    Fc->format!("{}", witness_count.witnesses),
+                ]);
+            }
+            witness_table.printstd();
+        }
+
+        if let Some(abi_hashes) = self.abi_hashes {
+            let mut abi_table = table!([Fm->"Package", Fm->"Function", Fm->"ABI Hash"]);
+            for abi_hash in abi_hashes {
+                abi_table.add_row(row![
+                    Fm->format!("{}", abi_hash.package_name),
+                    Fc->format!("{}", abi_hash.function_name),
+                    Fc->format!("{}", abi_hash.abi_hash),
+                ]);
+            }
+            abi_table.printstd();
+        }
+
+        if let Some(io_sizes) = self.io_sizes {
+            let mut io_table = table!([
+                Fm->"Package",
+                Fm->"Function",
+                Fm->"Public Inputs (bytes)",
+                Fm->"Private Inputs (bytes)",
+                Fm->"Return Value (bytes)"
+            ]);
+            for io_size in io_sizes {
+                io_table.add_row(row![
+                    Fm->format!("{}", io_size.package_name),
+                    Fc->format!("{}", io_size.function_name),
+                    Fc->format!("{}", io_size.public_inputs),
+                    Fc->format!("{}", io_size.private_inputs),
+                    Fc->format!("{}", io_size.return_value),
+                ]);
+            }
+            io_table.printstd();
+        }
+
+        if let Some(recursion_info) = self.recursion_info {
+            let mut recursion_table = table!([
+                Fm->"Package",
+                Fm->"Function",
+                Fm->"Recursive Verifications",
+                Fm->"Opcode Indices"
+            ]);
+            for info in recursion_info {
+                let opcode_indices = info
+                    .verifications
+                    .iter()
+                    .map(|verification| verification.opcode_index.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                recursion_table.add_row(row![
+                    Fm->format!("{}", info.package_name),
+                    Fc->format!("{}", info.function_name),
+                    Fc->format!("{}", info.verifications.len()),
+                    Fc->format!("{}", opcode_indices),
+                ]);
+            }
+            recursion_table.printstd();
+        }
+
+        if let Some(field_ops) = self.field_ops {
+            let mut field_ops_table = table!([
+                Fm->"Package",
+                Fm->"Function",
+                Fm->"Arithmetic Opcodes",
+                Fm->"Additions",
+                Fm->"Multiplications"
+            ]);
+            for ops in field_ops {
+                field_ops_table.add_row(row![
+                    Fm->format!("{}", ops.package_name),
+                    Fc->format!("{}", ops.function_name),
+                    Fc->format!("{}", ops.arithmetic_opcodes),
+                    Fc->format!("{}", ops.additions),
+                    Fc->format!("{}", ops.multiplications),
+                ]);
+            }
+            field_ops_table.printstd();
+        }
+
+        if let Some(call_sites) = self.call_sites {
+            let mut call_sites_table = table!([
+                Fm->"Package",
+                Fm->"Function",
+                Fm->"Source Function",
+                Fm->"Location",
+                Fm->"Opcodes",
+                Fm->"Opcode Indices"
+            ]);
+            for circuit_call_sites in call_sites {
+                for source_function in circuit_call_sites.source_functions {
+                    let opcode_ranges = source_function
+                        .opcode_ranges
+                        .iter()
+                        .map(|(start, end)| {
+                            if start == end {
+                                start.to_string()
+                            } else {
+                                format!("{start}-{end}")
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    call_sites_table.add_row(row![
+                        Fm->format!("{}", circuit_call_sites.package_name),
+                        Fc->format!("{}", circuit_call_sites.function_name),
+                        Fc->format!("{}", source_function.name),
+                        Fc->format!("{}", source_function.location),
+                        Fc->format!("{}", source_function.opcodes),
+                        Fc->format!("{}", opcode_ranges),
+                    ]);
+                }
+            }
+            call_sites_table.printstd();
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct WitnessCount {
+    package_name: String,
+    function_name: String,
+    witnesses: usize,
+}
+
+/// Counts the distinct witnesses used by each ACIR function of the workspace's artifacts.
+pub(super) fn count_witnesses(artifacts: &WorkspaceArtifacts) -> Vec<WitnessCount> {
+    artifacts
+        .functions()
+        .into_iter()
+        .map(|(package_name, function_name, circuit)| WitnessCount {
+            package_name: package_name.to_string(),
+            function_name,
+            witnesses: circuit_witnesses(circuit).len(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct AbiHash {
+    package_name: String,
+    function_name: String,
+    abi_hash: String,
+}
+
+/// Hashes the ABI of each program and contract function of the workspace's artifacts.
+pub(super) fn hash_abis(artifacts: &WorkspaceArtifacts) -> Vec<AbiHash> {
+    artifacts
+        .abis()
+        .into_iter()
+        .map(|(package_name, function_name, abi)| AbiHash {
+            package_name: package_name.to_string(),
+            function_name,
+            abi_hash: abi_hash(abi),
+        })
+        .collect()
+}
+
+/// The number of bytes taken up by each field element once serialized.
+const FIELD_ELEMENT_BYTES: u32 = 32;
+
+#[derive(Debug, Serialize)]
+pub(super) struct IoSizes {
+    package_name: String,
+    function_name: String,
+    public_inputs: u32,
+    private_inputs: u32,
+    return_value: u32,
+}
+
+/// Computes the serialized size of each program and contract function's inputs and return value from its ABI,
+/// assuming every field element is serialized as 32 bytes.
+pub(super) fn compute_io_sizes(artifacts: &WorkspaceArtifacts) -> Vec<IoSizes> {
+    artifacts
+        .abis()
+        .into_iter()
+        .map(|(package_name, function_name, abi)| {
+            let (public_parameters, private_parameters): (Vec<_>, Vec<_>) =
+                abi.parameters.iter().partition(|parameter| parameter.is_public());
+            let field_count = |parameters: Vec<&AbiParameter>| -> u32 {
+                parameters.iter().map(|parameter| parameter.typ.field_count()).sum()
+            };
+            let return_value = abi
+                .return_type
+                .as_ref()
+                .map_or(0, |return_type| return_type.abi_type.field_count());
+
+            IoSizes {
+                package_name: package_name.to_string(),
+                function_name,
+                public_inputs: field_count(public_parameters) * FIELD_ELEMENT_BYTES,
+                private_inputs: field_count(private_parameters) * FIELD_ELEMENT_BYTES,
+                return_value: return_value * FIELD_ELEMENT_BYTES,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct RecursionInfo {
+    package_name: String,
+    function_name: String,
+    verifications: Vec<RecursiveVerification>,
+}
+
+/// A proof verified within the circuit through a `RecursiveAggregation` black box call.
+#[derive(Debug, Serialize)]
+struct RecursiveVerification {
+    /// The index of the opcode within the function's circuit.
+    opcode_index: usize,
+    proof_type: u32,
+    proof_size: usize,
+    public_inputs: usize,
+    verification_key_size: usize,
+}
+
+/// Finds the recursive proof verifications within each ACIR function of the workspace's artifacts.
+pub(super) fn collect_recursion_info(artifacts: &WorkspaceArtifacts) -> Vec<RecursionInfo> {
+    artifacts
+        .functions()
+        .into_iter()
+        .map(|(package_name, function_name, circuit)| {
+            let verifications = circuit
+                .opcodes
+                .iter()
+                .enumerate()
+                .filter_map(|(opcode_index, opcode)| match opcode {
+                    Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RecursiveAggregation {
+                        verification_key,
+                        proof,
+                        public_inputs,
+                        proof_type,
+                        ..
+                    }) => Some(RecursiveVerification {
+                        opcode_index,
+                        proof_type: *proof_type,
+                        proof_size: proof.len(),
+                        public_inputs: public_inputs.len(),
+                        verification_key_size: verification_key.len(),
+                    }),
+                    _ => None,
+                })
+                .collect();
+            RecursionInfo { package_name: package_name.to_string(), function_name, verifications }
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct FieldOps {
+    package_name: String,
+    function_name: String,
+    arithmetic_opcodes: usize,
+    additions: usize,
+    multiplications: usize,
+}
+
+/// Counts the field additions and multiplications made by the `AssertZero` opcodes of each ACIR function
+/// of the workspace's artifacts.
+///
+/// Each quadratic term is counted as a single multiplication, with its scaling by a constant left out,
+/// and summing the terms of an expression takes one fewer addition than there are terms.
+pub(super) fn count_field_ops(artifacts: &WorkspaceArtifacts) -> Vec<FieldOps> {
+    artifacts
+        .functions()
+        .into_iter()
+        .map(|(package_name, function_name, circuit)| {
+            let mut field_ops = FieldOps {
+                package_name: package_name.to_string(),
+                function_name,
+                arithmetic_opcodes: 0,
+                additions: 0,
+                multiplications: 0,
+            };
+            for opcode in &circuit.opcodes {
+                let Opcode::AssertZero(expr) = opcode else {
+                    continue;
+                };
+                let terms = expr.mul_terms.len()
+                    + expr.linear_combinations.len()
+                    + usize::from(!expr.q_c.is_zero());
+                field_ops.arithmetic_opcodes += 1;
+                field_ops.additions += terms.saturating_sub(1);
+                field_ops.multiplications += expr.mul_terms.len();
+            }
+            field_ops
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct CallSites {
+    package_name: String,
+    function_name: String,
+    /// The source functions which contributed opcodes to the circuit, in the order of their first opcode.
+    source_functions: Vec<SourceFunctionOpcodes>,
+}
+
+#[derive(Debug, Serialize)]
+struct SourceFunctionOpcodes {
+    name: String,
+    /// The file and line the function is declared at.
+    location: String,
+    opcodes: usize,
+    /// The inclusive ranges of opcode indices which the function contributed to the circuit.
+    opcode_ranges: Vec<(usize, usize)>,
+}
+
+/// The name under which opcodes without a location in any known function are reported.
+const UNKNOWN_SOURCE_FUNCTION: &str = "<unknown>";
+
+/// Attributes each opcode of each ACIR function of the workspace's artifacts to the source function it was
+/// generated by.
+///
+/// The debug information of an artifact maps each opcode to the call stack it was inlined through,
+/// so the opcode is attributed to the function containing the innermost call. Functions are found
+/// by parsing the sources embedded in the artifact's file map.
+pub(super) fn collect_call_sites(artifacts: &WorkspaceArtifacts) -> Vec<CallSites> {
+    let mut call_sites = Vec::new();
+    for (package_name, program) in &artifacts.programs {
+        let mut source_functions = SourceFunctions::new(&program.file_map);
+        for (index, circuit) in program.bytecode.functions.iter().enumerate() {
+            call_sites.push(CallSites {
+                package_name: package_name.clone(),
+                function_name: program.names[index].clone(),
+                source_functions: source_functions
+                    .attribute_opcodes(circuit, &program.debug_symbols.debug_infos[index]),
+            });
+        }
+    }
+    for (package_name, contract) in &artifacts.contracts {
+        let mut source_functions = SourceFunctions::new(&contract.file_map);
+        for function in &contract.functions {
+            for (index, circuit) in function.bytecode.functions.iter().enumerate() {
+                // Subsequent ACIR functions are named in the same way as by `WorkspaceArtifacts::functions`.
+                let function_name = if index == 0 {
+                    function.name.clone()
+                } else {
+                    format!("{}[{index}]", function.name)
+                };
+                call_sites.push(CallSites {
+                    package_name: package_name.clone(),
+                    function_name,
+                    source_functions: source_functions
+                        .attribute_opcodes(circuit, &function.debug_symbols.debug_infos[index]),
+                });
+            }
+        }
+    }
+    call_sites
+}
+
+/// The functions declared in the files of an artifact's file map, parsed as they're needed.
+struct SourceFunctions<'a> {
+    file_map: &'a BTreeMap<FileId, DebugFile>,
+    functions: HashMap<FileId, Vec<(String, Span)>>,
+}
+
+impl<'a> SourceFunctions<'a> {
+    fn new(file_map: &'a BTreeMap<FileId, DebugFile>) -> Self {
+        Self { file_map, functions: HashMap::new() }
+    }
+
+    fn attribute_opcodes(
+        &mut self,
+        circuit: &Circuit<FieldElement>,
+        debug_info: &DebugInfo,
+    ) -> Vec<SourceFunctionOpcodes> {
+        let mut source_functions: Vec<SourceFunctionOpcodes> = Vec::new();
+        for opcode_index in 0..circuit.opcodes.len() {
+            let call_stack = debug_info.locations.get(&OpcodeLocation::Acir(opcode_index));
+            let (name, location) = call_stack
+                .and_then(|call_stack| call_stack.last())
+                .and_then(|location| self.find_function(location.file, location.span))
+                .unwrap_or_else(|| (UNKNOWN_SOURCE_FUNCTION.to_string(), String::new()));
+
+            let position = source_functions
+                .iter()
+                .position(|function| function.name == name && function.location == location);
+            let source_function = match position {
+                Some(position) => &mut source_functions[position],
+                None => {
+                    source_functions.push(SourceFunctionOpcodes {
+                        name,
+                        location,
+                        opcodes: 0,
+                        opcode_ranges: Vec::new(),
+                    });
+                    source_functions.last_mut().unwrap()
+                }
+            };
+            source_function.opcodes += 1;
+            match source_function.opcode_ranges.last_mut() {
+                Some((_, end)) if *end + 1 == opcode_index => *end = opcode_index,
+                _ => source_function.opcode_ranges.push((opcode_index, opcode_index)),
+            }
+        }
+        source_functions
+    }
+
+    /// Returns the name and declaration site of the innermost function in `file` which contains `span`.
+    fn find_function(&mut self, file: FileId, span: Span) -> Option<(String, String)> {
+        let debug_file = self.file_map.get(&file)?;
+        let functions = self.functions.entry(file).or_insert_with(|| {
+            let (parsed_module, _) = parse_program(&debug_file.source);
+            let mut collector = FunctionSpanCollector::default();
+            parsed_module.accept(&mut collector);
+            collector.functions
+        });
+
+        let (name, function_span) = functions
+            .iter()
+            .filter(|(_, function_span)| function_span.contains(&span))
+            .min_by_key(|(_, function_span)| function_span.end() - function_span.start())?;
+        let line = debug_file.source[..function_span.start() as usize].matches('\n').count() + 1;
+        Some((name.clone(), format!("{}:{line}", debug_file.path.display())))
+    }
+}
+
+/// Collects the span of each function declared in a parsed file, with methods named after the type they're on.
+#[derive(Default)]
+struct FunctionSpanCollector {
+    impl_type: Option<String>,
+    functions: Vec<(String, Span)>,
+}
+
+impl Visitor for FunctionSpanCollector {
+    fn visit_noir_function(&mut self, function: &NoirFunction, span: Span) -> bool {
+        let name = match &self.impl_type {
+            Some(impl_type) => format!("{impl_type}::{}", function.name()),
+            None => function.name().to_string(),
+        };
+        self.functions.push((name, span));
+        false
+    }
+
+    fn visit_type_impl(&mut self, type_impl: &TypeImpl, _: Span) -> bool {
+        self.impl_type = Some(type_impl.object_type.to_string());
+        type_impl.accept_children(self);
+        self.impl_type = None;
+        false
+    }
+
+    fn visit_noir_trait_impl(&mut self, trait_impl: &NoirTraitImpl, _: Span) -> bool {
+        self.impl_type = Some(format!("<{} as {}>", trait_impl.object_type, trait_impl.trait_name));
+        trait_impl.accept_children(self);
+        self.impl_type = None;
+        false
+    }
+}
+
+/// Writes each ACIR function of the workspace's artifacts to `target_dir` in the textual ACIR assembly format,
+/// returning the paths of the files written.
+pub(super) fn emit_acir_asm(artifacts: &WorkspaceArtifacts, target_dir: &Path) -> Vec<PathBuf> {
+    emit_circuits(artifacts, target_dir, "acir", |_, circuit| print_circuit(circuit))
+}
+
+/// Writes a Graphviz graph of each circuit to the target directory, returning the paths written.
+pub(super) fn emit_acir_dot(artifacts: &WorkspaceArtifacts, target_dir: &Path) -> Vec<PathBuf> {
+    emit_circuits(artifacts, target_dir, "dot", circuit_to_dot)
+}
+
+/// Writes a Mermaid flowchart of each circuit to the target directory, returning the paths written.
+pub(super) fn emit_acir_mermaid(artifacts: &WorkspaceArtifacts, target_dir: &Path) -> Vec<PathBuf> {
+    emit_circuits(artifacts, target_dir, "mmd", circuit_to_mermaid)
+}
+
+/// Writes each circuit rendered by `render` to a file named after its package and function with the given extension.
+fn emit_circuits(
+    artifacts: &WorkspaceArtifacts,
+    target_dir: &Path,
+    extension: &str,
+    render: impl Fn(&str, &Circuit<FieldElement>) -> String,
+) -> Vec<PathBuf> {
+    artifacts
+        .functions()
+        .into_iter()
+        .map(|(package_name, function_name, circuit)| {
+            // Subsequent ACIR functions of a contract function are suffixed by their index in square brackets.
+            let function_name = function_name.replace('[', "-").replace(']', "");
+            let name = format!("{package_name}-{function_name}");
+            let path = target_dir.join(&name).with_extension(extension);
+            write_to_file(render(&name, circuit).as_bytes(), &path);
+            path
+        })
+        .collect()
+}
+
+/// Renders the circuit as a Graphviz digraph, with a node for each opcode and each witness.
+/// Edges lead from the witnesses an opcode reads to the opcode, and from the opcode to the witnesses it writes.
+fn circuit_to_dot(name: &str, circuit: &Circuit<FieldElement>) -> String {
+    let mut dot = format!("digraph \"{name}\" {{\n    rankdir=LR;\n");
+    for witness in circuit_witnesses(circuit) {
+        dot.push_str(&format!(
+            "    w{0} [label=\"w{0}\", shape=ellipse];\n",
+            witness.witness_index()
+        ));
+    }
+    for (index, opcode) in circuit.opcodes.iter().enumerate() {
+        dot.push_str(&format!(
+            "    op{index} [label=\"{}\", shape=box];\n",
+            opcode_label(index, opcode)
+        ));
+        let (reads, writes) = opcode_reads_and_writes(opcode);
+        for witness in reads {
+            dot.push_str(&format!("    w{} -> op{index};\n", witness.witness_index()));
+        }
+        for witness in writes {
+            dot.push_str(&format!("    op{index} -> w{};\n", witness.witness_index()));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Renders the circuit as a Mermaid flowchart, with the same nodes and edges as [`circuit_to_dot`].
+fn circuit_to_mermaid(_name: &str, circuit: &Circuit<FieldElement>) -> String {
+    let mut mermaid = "flowchart LR\n".to_string();
+    for witness in circuit_witnesses(circuit) {
+        mermaid.push_str(&format!("    w{0}((\"w{0}\"))\n", witness.witness_index()));
+    }
+    for (index, opcode) in circuit.opcodes.iter().enumerate() {
+        mermaid.push_str(&format!("    op{index}[\"{}\"]\n", opcode_label(index, opcode)));
+        let (reads, writes) = opcode_reads_and_writes(opcode);
+        for witness in reads {
+            mermaid.push_str(&format!("    w{} --> op{index}\n", witness.witness_index()));
+        }
+        for witness in writes {
+            mermaid.push_str(&format!("    op{index} --> w{}\n", witness.witness_index()));
+        }
+    }
+    mermaid
+}
+
+/// A short label for an opcode in a circuit graph: its index and what kind of opcode it is.
+fn opcode_label(index: usize, opcode: &Opcode<FieldElement>) -> String {
+    let kind = match opcode {
+        Opcode::AssertZero(_) => "ASSERT_ZERO".to_string(),
+        Opcode::BlackBoxFuncCall(call) => format!("BLACKBOX::{}", call.name()),
+        Opcode::MemoryOp { block_id, op, .. } => {
+            let operation = if op.operation.is_zero() { "READ" } else { "WRITE" };
+            format!("MEM::{operation} b{}", block_id.0)
+        }
+        Opcode::MemoryInit { block_id, .. } => format!("INIT b{}", block_id.0),
+        Opcode::BrilligCall { id, .. } => format!("BRILLIG_CALL {id}"),
+        Opcode::Call { id, .. } => format!("CALL {id}"),
+    };
+    format!("{index}: {kind}")
+}
+
+/// Returns the path and version of each artifact in `target_dir` which was compiled by a different version of Noir
+/// than `noir_version`, such as artifacts which were left over from a partial rebuild.
+///
+/// JSON files without a `noir_version` field, such as shard manifests, aren't artifacts and are skipped.
+pub(super) fn find_mismatched_artifact_versions(
+    target_dir: &Path,
+    noir_version: &str,
+) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = std::fs::read_dir(target_dir) else {
+        return Vec::new();
+    };
+    let mut mismatched: Vec<(PathBuf, String)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+        .filter_map(|path| {
+            let artifact: serde_json::Value =
+                serde_json::from_slice(&std::fs::read(&path).ok()?).ok()?;
+            let version = artifact.get("noir_version")?.as_str()?.to_string();
+            (version != noir_version).then_some((path, version))
+        })
+        .collect();
+    mismatched.sort();
+    mismatched
+}
+
+/// The name of the index of a workspace's artifacts, written to its target directory.
+const ARTIFACT_INDEX_FILE: &str = "index.json";
+
+#[derive(Debug, Serialize)]
+struct ArtifactIndexEntry {
+    package_name: String,
+    package_type: String,
+    /// The artifact's path, relative to the target directory if it's within it.
+    path: PathBuf,
+    noir_version: String,
+    /// A SHA-256 hash of the artifact file.
+    sha256: String,
+}
+
+/// Writes an index of the artifacts of the workspace's selected packages to its target directory,
+/// returning the path of the index.
+///
+/// Artifacts which were mapped to another path in the manifest are listed at that path.
+pub(super) fn write_artifact_index(workspace: &Workspace) -> Result<PathBuf, CliError> {
+    let target_dir = workspace.target_directory_path();
+    let mut entries = Vec::new();
+    for package in workspace {
+        let artifact = match package.package_type {
+            PackageType::Binary => {
+                let path = workspace.package_build_path(package).with_extension("json");
+                let program = read_program_from_file(&path)?;
+                Some((path, program.noir_version))
+            }
+            PackageType::Contract => find_workspace_contract(workspace, package)
+                .map(|(path, contract)| (path, contract.noir_version)),
+            PackageType::Library => None,
+        };
+        let Some((path, noir_version)) = artifact else {
+            continue;
+        };
+
+        entries.push(ArtifactIndexEntry {
+            package_name: package.name.to_string(),
+            package_type: package.package_type.to_string(),
+            sha256: artifact_checksum(&path)?,
+            path: path.strip_prefix(&target_dir).map(Path::to_path_buf).unwrap_or(path),
+            noir_version,
+        });
+    }
+
+    let index_path = target_dir.join(ARTIFACT_INDEX_FILE);
+    write_to_file(&serde_json::to_vec_pretty(&entries).unwrap(), &index_path);
+    Ok(index_path)
+}
+
+/// Returns a SHA-256 hash over the names, types and visibilities of the ABI's parameters and return value.
+///
+/// Error types are left out as they depend on the assertions made within the circuit rather than on its interface.
+fn abi_hash(abi: &Abi) -> String {
+    let interface = serde_json::to_vec(&(&abi.parameters, &abi.return_type))
+        .expect("ABI should be serializable");
+    format!("{:x}", Sha256::digest(interface))
+}
+
+/// Returns each ACIR function of a contract alongside its name.
+///
+/// Contract functions which are made up of several ACIR functions have each subsequent one suffixed by its index.
+pub(super) fn contract_circuits(
+    contract: &ContractArtifact,
+) -> Vec<(String, &Circuit<FieldElement>)> {
+    contract
+        .functions
+        .iter()
+        .flat_map(|function| {
+            function.bytecode.functions.iter().enumerate().map(move |(index, circuit)| {
+                let name = if index == 0 {
+                    function.name.clone()
+                } else {
+                    format!("{}[{index}]", function.name)
+                };
+                (name, circuit)
+            })
+        })
+        .collect()
+}
+
+/// The size and interface of a program or contract function, as compared against a baseline artifact.
+#[derive(Clone)]
+struct EntryPointSummary {
+    opcodes: usize,
+    abi_hash: String,
+}
+
+fn program_summary(program: &ProgramArtifact) -> BTreeMap<String, EntryPointSummary> {
+    let opcodes = program.bytecode.functions.iter().map(|circuit| circuit.opcodes.len()).sum();
+    let summary = EntryPointSummary { opcodes, abi_hash: abi_hash(&program.abi) };
+    BTreeMap::from([(program.names[0].clone(), summary)])
+}
+
+fn contract_summary(contract: &ContractArtifact) -> BTreeMap<String, EntryPointSummary> {
+    contract
+        .functions
+        .iter()
+        .map(|function| {
+            let opcodes =
+                function.bytecode.functions.iter().map(|circuit| circuit.opcodes.len()).sum();
+            (
+                function.name.clone(),
+                EntryPointSummary { opcodes, abi_hash: abi_hash(&function.abi) },
+            )
+        })
+        .collect()
+}
+
+/// The artifact which `--compare-with` compares the single program or contract of the workspace against.
+#[derive(Clone)]
+pub(super) struct Baseline {
+    path: PathBuf,
+    tolerance: f64,
+    entry_points: BTreeMap<String, EntryPointSummary>,
+}
+
+impl Baseline {
+    /// Reads the baseline artifact at `path`, erroring unless the workspace has exactly one binary or contract package
+    /// for it to be compared against.
+    pub(super) fn read(
+        workspace: &Workspace,
+        path: &Path,
+        tolerance: f64,
+    ) -> Result<Self, CliError> {
+        let packages: Vec<_> =
+            workspace.into_iter().filter(|package| !package.is_library()).collect();
+        let entry_points = match packages.as_slice() {
+            [package] if package.is_binary() => program_summary(&read_program_from_file(path)?),
+            [_] => contract_summary(&read_contract_from_file(path)?),
+            _ => {
+                return Err(CliError::Generic(
+                    "`--compare-with` requires exactly one binary or contract package to be compiled. Select one with `--package`".to_string(),
+                ))
+            }
+        };
+        Ok(Self { path: path.to_path_buf(), tolerance, entry_points })
+    }
+
+    pub(super) fn compare_program(&self, program: &ProgramArtifact) -> Result<(), String> {
+        self.compare(&program_summary(program))
+    }
+
+    pub(super) fn compare_contract(&self, contract: &ContractArtifact) -> Result<(), String> {
+        self.compare(&contract_summary(contract))
+    }
+
+    /// Prints how the ACIR opcode count of each entry point changed since the baseline.
+    ///
+    /// Errors if any entry point's opcode count grew by more than the tolerance, if the ABI changed,
+    /// or if entry points were added or removed.
+    fn compare(&self, current: &BTreeMap<String, EntryPointSummary>) -> Result<(), String> {
+        // The report is built up front so that it isn't interleaved with the output of other packages.
+        let mut report = Vec::new();
+        for (name, baseline_entry) in &self.entry_points {
+            match current.get(name) {
+                Some(entry) => report.push(format!(
+                    "{name}: {} -> {} ACIR opcodes ({:+.2}%)",
+                    baseline_entry.opcodes,
+                    entry.opcodes,
+                    opcode_change(baseline_entry.opcodes, entry.opcodes)
+                )),
+                None => report.push(format!("{name}: removed")),
+            }
+        }
+        for name in current.keys().filter(|name| !self.entry_points.contains_key(*name)) {
+            report.push(format!("{name}: added"));
+        }
+        human_println!("{}", report.join("\n"));
+
+        let regressions = find_regressions(current, &self.entry_points, self.tolerance);
+        if regressions.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Compiled artifact regressed against {}:\n{}",
+                self.path.display(),
+                regressions.join("\n")
+            ))
+        }
+    }
+}
+
+/// The percentage by which the number of opcodes changed from `baseline` to `current`.
+fn opcode_change(baseline: usize, current: usize) -> f64 {
+    if baseline == 0 {
+        if current == 0 {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    } else {
+        (current as f64 - baseline as f64) * 100.0 / baseline as f64
+    }
+}
+
+/// Describes each way in which the entry points of `current` regressed against those of `baseline`.
+fn find_regressions(
+    current: &BTreeMap<String, EntryPointSummary>,
+    baseline: &BTreeMap<String, EntryPointSummary>,
+    tolerance: f64,
+) -> Vec<String> {
+    let mut regressions = Vec::new();
+    for (name, baseline_entry) in baseline {
+        let Some(entry) = current.get(name) else {
+            regressions.push(format!("`{name}` was removed"));
+            continue;
+        };
+
+        let change = opcode_change(baseline_entry.opcodes, entry.opcodes);
+        if change > tolerance {
+            regressions.push(format!(
+                "`{name}` grew from {} to {} ACIR opcodes ({change:+.2}%), beyond the tolerance of {tolerance}%",
+                baseline_entry.opcodes, entry.opcodes
+            ));
+        }
+        if entry.abi_hash != baseline_entry.abi_hash {
+            regressions.push(format!("the ABI of `{name}` changed"));
+        }
+    }
+    for name in current.keys().filter(|name| !baseline.contains_key(*name)) {
+        regressions.push(format!("`{name}` was added"));
+    }
+    regressions
+}
+
+/// Counts the calls the circuit makes to each black box function which isn't permitted by `allowed` and `denied`.
+pub(super) fn count_disallowed_black_box_functions(
+    circuit: &Circuit<FieldElement>,
+    allowed: &[BlackBoxFunc],
+    denied: &[BlackBoxFunc],
+) -> BTreeMap<&'static str, usize> {
+    let mut counts = BTreeMap::new();
+    for opcode in &circuit.opcodes {
+        if let Opcode::BlackBoxFuncCall(call) = opcode {
+            let black_box = call.get_black_box_func();
+            if !is_black_box_function_allowed(black_box, allowed, denied) {
+                *counts.entry(black_box.name()).or_default() += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// A black box function is allowed unless it's denied, or there's a list of allowed functions which it isn't on.
+fn is_black_box_function_allowed(
+    black_box: BlackBoxFunc,
+    allowed: &[BlackBoxFunc],
+    denied: &[BlackBoxFunc],
+) -> bool {
+    !denied.contains(&black_box) && (allowed.is_empty() || allowed.contains(&black_box))
+}
+
+/// Parses the name of a black box function, as used within ACIR.
+pub(super) fn parse_black_box_func(name: &str) -> Result<BlackBoxFunc, String> {
+    BlackBoxFunc::lookup(name).ok_or_else(|| format!("unknown black box function `{name}`"))
+}
+
+/// Returns the set of witnesses referenced by the circuit, including its parameters and return values.
+pub(super) fn circuit_witnesses(circuit: &Circuit<FieldElement>) -> BTreeSet<Witness> {
+    let mut witnesses = circuit.circuit_arguments();
+    witnesses.extend(circuit.return_values.0.iter().copied());
+    for opcode in &circuit.opcodes {
+        insert_opcode_witnesses(&mut witnesses, opcode);
+    }
+    witnesses
+}
+
+/// Inserts every witness read or written by the opcode into `witnesses`.
+pub(super) fn insert_opcode_witnesses(
+    witnesses: &mut BTreeSet<Witness>,
+    opcode: &Opcode<FieldElement>,
+) {
+    let (reads, writes) = opcode_reads_and_writes(opcode);
+    witnesses.extend(reads);
+    witnesses.extend(writes);
+}
+
+/// Returns the witnesses read by the opcode and those written by it.
+///
+/// The witnesses of an `AssertZero` opcode are constrained rather than written, so they're all treated as read.
+fn opcode_reads_and_writes(
+    opcode: &Opcode<FieldElement>,
+) -> (BTreeSet<Witness>, BTreeSet<Witness>) {
+    let insert_expression = |witnesses: &mut BTreeSet<Witness>, expr: &Expression<_>| {
+        witnesses.extend(expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]));
+        witnesses.extend(expr.linear_combinations.iter().map(|(_, witness)| *witness));
+    };
+
+    let mut reads = BTreeSet::new();
+    let mut writes = BTreeSet::new();
+    match opcode {
+        Opcode::AssertZero(expr) => insert_expression(&mut reads, expr),
+        Opcode::BlackBoxFuncCall(call) => {
+            reads.extend(call.get_input_witnesses());
+            writes.extend(call.get_outputs_vec());
+        }
+        Opcode::MemoryOp { op, predicate, .. } => {
+            insert_expression(&mut reads, &op.index);
+            // A read writes its value into a witness, while a write takes it from one.
+            if op.operation.is_zero() {
+                insert_expression(&mut writes, &op.value);
+            } else {
+                insert_expression(&mut reads, &op.value);
+            }
+            if let Some(predicate) = predicate {
+                insert_expression(&mut reads, predicate);
+            }
+        }
+        Opcode::MemoryInit { init, .. } => reads.extend(init.iter().copied()),
+        Opcode::BrilligCall { inputs, outputs, predicate, .. } => {
+            for input in inputs {
+                match input {
+                    BrilligInputs::Single(expr) => insert_expression(&mut reads, expr),
+                    BrilligInputs::Array(exprs) => {
+                        for expr in exprs {
+                            insert_expression(&mut reads, expr);
+                        }
+                    }
+                    BrilligInputs::MemoryArray(_) => (),
+                }
+            }
+            for output in outputs {
+                match output {
+                    BrilligOutputs::Simple(witness) => {
+                        writes.insert(*witness);
+                    }
+                    BrilligOutputs::Array(outputs) => writes.extend(outputs.iter().copied()),
+                }
+            }
+            if let Some(predicate) = predicate {
+                insert_expression(&mut reads, predicate);
+            }
+        }
+        Opcode::Call { inputs, outputs, predicate, .. } => {
+            reads.extend(inputs.iter().copied());
+            writes.extend(outputs.iter().copied());
+            if let Some(predicate) = predicate {
+                insert_expression(&mut reads, predicate);
+            }
+        }
+    }
+    (reads, writes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{find_regressions, EntryPointSummary};
+
+    fn entry_points(opcodes: &[(&str, usize)]) -> BTreeMap<String, EntryPointSummary> {
+        opcodes
+            .iter()
+            .map(|(name, opcodes)| {
+                let summary = EntryPointSummary { opcodes: *opcodes, abi_hash: "abi".to_string() };
+                (name.to_string(), summary)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn growth_is_only_a_regression_beyond_the_tolerance() {
+        let baseline = entry_points(&[("main", 100)]);
+        let regressions = |opcodes, tolerance| {
+            find_regressions(&entry_points(&[("main", opcodes)]), &baseline, tolerance)
+        };
+
+        assert!(regressions(100, 0.0).is_empty());
+        assert!(regressions(90, 0.0).is_empty());
+        assert!(regressions(109, 10.0).is_empty());
+        assert!(regressions(110, 10.0).is_empty());
+        assert_eq!(
+            regressions(111, 10.0),
+            vec!["`main` grew from 100 to 111 ACIR opcodes (+11.00%), beyond the tolerance of 10%"]
+        );
+        assert_eq!(regressions(101, 0.0).len(), 1);
+    }
+
+    #[test]
+    fn growth_from_no_opcodes_is_always_a_regression() {
+        let baseline = entry_points(&[("main", 0)]);
+        assert!(find_regressions(&entry_points(&[("main", 0)]), &baseline, 0.0).is_empty());
+        assert_eq!(find_regressions(&entry_points(&[("main", 1)]), &baseline, 1000.0).len(), 1);
+    }
+
+    #[test]
+    fn added_and_removed_entry_points_are_regressions() {
+        let baseline = entry_points(&[("kept", 10), ("removed", 10)]);
+        let current = entry_points(&[("added", 10), ("kept", 10)]);
+        assert_eq!(
+            find_regressions(&current, &baseline, 100.0),
+            vec!["`removed` was removed", "`added` was added"]
+        );
+    }
+
+    #[test]
+    fn abi_changes_are_regressions() {
+        let baseline = entry_points(&[("main", 10)]);
+        let mut current = entry_points(&[("main", 10)]);
+        current.get_mut("main").unwrap().abi_hash = "changed".to_string();
+        assert_eq!(find_regressions(&current, &baseline, 0.0), vec!["the ABI of `main` changed"]);
+    }
+}
@@ -0,0 +1,269 @@
+//! Experimental splitting of a compiled program's circuit into contiguous groups of opcodes,
+//! for backends which prove very large circuits in pieces.
+//!
+//! Each shard is written as its own program artifact, alongside a manifest describing how the shards relate.
+//! Witnesses which are referenced by more than one shard are exposed as public parameters of every shard
+//! which references them, so that a backend can link the shards' proofs together by checking that the values agree.
+//!
+//! Limitations:
+//! - Only programs made up of a single ACIR function can be sharded, as calls between ACIR functions aren't split.
+//! - Memory blocks are not tracked across shards, so a `MemoryOp` must share a shard with its `MemoryInit`.
+//! - Debug information isn't remapped, so errors reported when executing a shard may point at the wrong location.
+//! - Recombining the shards' proofs isn't supported by every backend.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+use acvm::acir::circuit::{Circuit, Opcode, OpcodeLocation, Program, PublicInputs};
+use acvm::acir::native_types::Witness;
+use acvm::FieldElement;
+use nargo::package::Package;
+use nargo::workspace::Workspace;
+use noirc_artifacts::program::ProgramArtifact;
+use serde::Serialize;
+
+use crate::errors::CliError;
+
+use super::compile_report::insert_opcode_witnesses;
+use super::fs::program::{read_program_from_file, save_build_artifact_to_file};
+
+/// Describes how the shards of a program relate to each other.
+#[derive(Debug, Serialize)]
+struct ShardManifest {
+    package_name: String,
+    /// The number of opcodes in the original circuit.
+    opcode_count: usize,
+    shards: Vec<ShardEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct ShardEntry {
+    /// The name of the shard's artifact within the target directory.
+    artifact: String,
+    /// The range of opcodes of the original circuit which make up this shard.
+    opcodes: Range<usize>,
+    /// The witnesses this shard shares with other shards, mapped to the indices of those shards.
+    shared_witnesses: BTreeMap<u32, Vec<usize>>,
+}
+
+/// Splits the circuit of each binary package's artifact into `shard_count` shards.
+pub(super) fn shard_workspace_programs(
+    workspace: &Workspace,
+    shard_count: usize,
+    pretty: bool,
+) -> Result<(), CliError> {
+    for package in workspace.into_iter().filter(|package| package.is_binary()) {
+        let program = read_program_from_file(workspace.package_build_path(package))?;
+        shard_program(workspace, package, program, shard_count, pretty)?;
+    }
+    Ok(())
+}
+
+fn shard_program(
+    workspace: &Workspace,
+    package: &Package,
+    program: ProgramArtifact,
+    shard_count: usize,
+    pretty: bool,
+) -> Result<(), CliError> {
+    let [circuit] = program.bytecode.functions.as_slice() else {
+        return Err(CliError::Generic(format!(
+            "Cannot shard package `{}` as it is made up of more than one ACIR function",
+            package.name
+        )));
+    };
+
+    let ranges = shard_ranges(circuit.opcodes.len(), shard_count);
+    let shard_witnesses: Vec<BTreeSet<Witness>> = ranges
+        .iter()
+        .map(|range| {
+            let mut witnesses = BTreeSet::new();
+            for opcode in &circuit.opcodes[range.clone()] {
+                insert_opcode_witnesses(&mut witnesses, opcode);
+            }
+            witnesses
+        })
+        .collect();
+
+    let mut witness_shards: BTreeMap<Witness, Vec<usize>> = BTreeMap::new();
+    for (index, witnesses) in shard_witnesses.iter().enumerate() {
+        for witness in witnesses {
+            witness_shards.entry(*witness).or_default().push(index);
+        }
+    }
+
+    let target_dir = workspace.target_directory_path();
+    let mut shards = Vec::with_capacity(ranges.len());
+    for (index, (range, witnesses)) in ranges.into_iter().zip(&shard_witnesses).enumerate() {
+        let shared_witnesses: BTreeMap<u32, Vec<usize>> = witnesses
+            .iter()
+            .filter_map(|witness| {
+                let other_shards: Vec<usize> = witness_shards[witness]
+                    .iter()
+                    .copied()
+                    .filter(|shard_index| *shard_index != index)
+                    .collect();
+                (!other_shards.is_empty()).then_some((witness.witness_index(), other_shards))
+            })
+            .collect();
+
+        let shard_circuit = shard_circuit(circuit, range.clone(), witnesses, &shared_witnesses);
+        let mut shard_artifact = program.clone();
+        shard_artifact.bytecode = Program {
+            functions: vec![shard_circuit],
+            unconstrained_functions: program.bytecode.unconstrained_functions.clone(),
+        };
+
+        let artifact = format!("{}-shard-{index}", package.name);
+        save_build_artifact_to_file(&shard_artifact, &artifact, &target_dir, pretty);
+        shards.push(ShardEntry { artifact, opcodes: range, shared_witnesses });
+    }
+
+    let manifest = ShardManifest {
+        package_name: package.name.to_string(),
+        opcode_count: circuit.opcodes.len(),
+        shards,
+    };
+    save_build_artifact_to_file(&manifest, &format!("{}-shards", package.name), target_dir, pretty);
+
+    Ok(())
+}
+
+/// Splits `opcode_count` opcodes into at most `shard_count` contiguous ranges of near-equal length.
+fn shard_ranges(opcode_count: usize, shard_count: usize) -> Vec<Range<usize>> {
+    let shard_count = shard_count.clamp(1, opcode_count.max(1));
+    let shard_size = opcode_count / shard_count;
+    let remainder = opcode_count % shard_count;
+
+    let mut start = 0;
+    (0..shard_count)
+        .map(|index| {
+            let end = start + shard_size + usize::from(index < remainder);
+            let range = start..end;
+            start = end;
+            range
+        })
+        .collect()
+}
+
+/// Builds the circuit for a single shard, exposing any witnesses shared with other shards as public parameters.
+fn shard_circuit(
+    circuit: &Circuit<FieldElement>,
+    range: Range<usize>,
+    witnesses: &BTreeSet<Witness>,
+    shared_witnesses: &BTreeMap<u32, Vec<usize>>,
+) -> Circuit<FieldElement> {
+    let opcodes: Vec<Opcode<FieldElement>> = circuit.opcodes[range.clone()].to_vec();
+
+    let mut public_parameters: BTreeSet<Witness> =
+        circuit.public_parameters.0.intersection(witnesses).copied().collect();
+    public_parameters.extend(shared_witnesses.keys().map(|index| Witness(*index)));
+    let private_parameters = circuit
+        .private_parameters
+        .intersection(witnesses)
+        .filter(|witness| !public_parameters.contains(witness))
+        .copied()
+        .collect();
+    let return_values = circuit.return_values.0.intersection(witnesses).copied().collect();
+
+    let assert_messages = circuit
+        .assert_messages
+        .iter()
+        .filter_map(|(location, payload)| {
+            let location = match location {
+                OpcodeLocation::Acir(index) if range.contains(index) => {
+                    OpcodeLocation::Acir(index - range.start)
+                }
+                OpcodeLocation::Brillig { acir_index, brillig_index }
+                    if range.contains(acir_index) =>
+                {
+                    OpcodeLocation::Brillig {
+                        acir_index: acir_index - range.start,
+                        brillig_index: *brillig_index,
+                    }
+                }
+                _ => return None,
+            };
+            Some((location, payload.clone()))
+        })
+        .collect();
+
+    Circuit {
+        current_witness_index: circuit.current_witness_index,
+        opcodes,
+        expression_width: circuit.expression_width,
+        private_parameters,
+        public_parameters: PublicInputs(public_parameters),
+        return_values: PublicInputs(return_values),
+        assert_messages,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use acvm::acir::circuit::{Circuit, Opcode, PublicInputs};
+    use acvm::acir::native_types::{Expression, Witness};
+    use acvm::{AcirField, FieldElement};
+
+    use super::{shard_circuit, shard_ranges};
+
+    #[test]
+    fn every_opcode_is_in_exactly_one_shard() {
+        for opcode_count in 0..40 {
+            for shard_count in 0..12 {
+                let ranges = shard_ranges(opcode_count, shard_count);
+                assert!(!ranges.is_empty() && ranges.len() <= shard_count.max(1));
+
+                // The shards are contiguous and cover every opcode, so no opcode is in two of them.
+                let mut next_opcode = 0;
+                for range in &ranges {
+                    assert_eq!(range.start, next_opcode, "{ranges:?}");
+                    assert!(opcode_count == 0 || !range.is_empty(), "{ranges:?}");
+                    next_opcode = range.end;
+                }
+                assert_eq!(next_opcode, opcode_count);
+
+                let lengths: Vec<usize> = ranges.iter().map(ExactSizeIterator::len).collect();
+                let (min, max) = (lengths.iter().min().unwrap(), lengths.iter().max().unwrap());
+                assert!(max - min <= 1, "{ranges:?}");
+
+                // The same opcodes are assigned to the same shards every time.
+                assert_eq!(shard_ranges(opcode_count, shard_count), ranges);
+            }
+        }
+    }
+
+    /// An opcode asserting that witnesses `a` and `b` are equal.
+    fn assert_equal(a: u32, b: u32) -> Opcode<FieldElement> {
+        Opcode::AssertZero(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(a)),
+                (-FieldElement::one(), Witness(b)),
+            ],
+            q_c: FieldElement::zero(),
+        })
+    }
+
+    #[test]
+    fn shared_witnesses_become_public_parameters() {
+        let circuit = Circuit {
+            current_witness_index: 4,
+            opcodes: vec![assert_equal(1, 2), assert_equal(2, 3), assert_equal(3, 4)],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(4)])),
+            ..Circuit::default()
+        };
+
+        let witnesses = BTreeSet::from([Witness(2), Witness(3), Witness(4)]);
+        let shared_witnesses = BTreeMap::from([(2, vec![0])]);
+        let shard = shard_circuit(&circuit, 1..3, &witnesses, &shared_witnesses);
+
+        assert_eq!(shard.opcodes, circuit.opcodes[1..3]);
+        assert_eq!(shard.public_parameters, PublicInputs(BTreeSet::from([Witness(2)])));
+        assert!(shard.private_parameters.is_empty());
+        assert_eq!(shard.return_values, circuit.return_values);
+    }
+}
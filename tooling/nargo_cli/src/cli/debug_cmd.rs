@@ -66,7 +66,7 @@ pub(crate) fn run(args: DebugCommand, config: NargoConfig) -> Result<(), CliErro
     let target_dir = &workspace.target_directory_path();
 
     let Some(package) = workspace.into_iter().find(|p| p.is_binary()) else {
-        println!(
+        human_println!(
             "No matching binary packages found in workspace. Only binary packages can be debugged."
         );
         return Ok(());
@@ -134,6 +134,7 @@ pub(crate) fn compile_bin_package_for_debugging(
         &workspace_file_manager,
         compile_options.deny_warnings,
         compile_options.silence_warnings,
+        compile_options.message_format,
     )
 }
 
@@ -177,25 +178,25 @@ fn run_async(
     let runtime = Builder::new_current_thread().enable_all().build().unwrap();
 
     runtime.block_on(async {
-        println!("[{}] Starting debugger", package.name);
+        human_println!("[{}] Starting debugger", package.name);
         let (return_value, witness_stack) =
             debug_program_and_decode(program, package, prover_name)?;
 
         if let Some(solved_witness_stack) = witness_stack {
-            println!("[{}] Circuit witness successfully solved", package.name);
+            human_println!("[{}] Circuit witness successfully solved", package.name);
 
             if let Some(return_value) = return_value {
-                println!("[{}] Circuit output: {return_value:?}", package.name);
+                human_println!("[{}] Circuit output: {return_value:?}", package.name);
             }
 
             if let Some(witness_name) = witness_name {
                 let witness_path =
                     save_witness_to_dir(solved_witness_stack, witness_name, target_dir)?;
 
-                println!("[{}] Witness saved to {}", package.name, witness_path.display());
+                human_println!("[{}] Witness saved to {}", package.name, witness_path.display());
             }
         } else {
-            println!("Debugger execution halted.");
+            human_println!("Debugger execution halted.");
         }
 
         Ok(())
@@ -0,0 +1,47 @@
+use clap::Args;
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use serde::Serialize;
+
+use crate::errors::CliError;
+
+use super::{json_output, GIT_HASH, IS_DIRTY, NARGO_VERSION};
+
+/// Prints details of the toolchain and environment, for inclusion in bug reports
+#[derive(Debug, Clone, Args)]
+pub(crate) struct EnvCommand {
+    /// Output a JSON formatted report. Same as `--format json`.
+    #[clap(long)]
+    pub(super) json: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct EnvReport {
+    nargo_version: &'static str,
+    noirc_version: &'static str,
+    git_hash: &'static str,
+    is_dirty: bool,
+    os: &'static str,
+    arch: &'static str,
+}
+
+pub(crate) fn run(_args: EnvCommand) -> Result<(), CliError> {
+    let report = EnvReport {
+        nargo_version: NARGO_VERSION,
+        noirc_version: NOIR_ARTIFACT_VERSION_STRING,
+        git_hash: GIT_HASH,
+        is_dirty: IS_DIRTY == "true",
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+    };
+
+    if json_output() {
+        println!("{}", serde_json::to_string(&report).unwrap());
+    } else {
+        human_println!("nargo version: {}", report.nargo_version);
+        human_println!("noirc version: {}", report.noirc_version);
+        human_println!("git version hash: {} (is dirty: {})", report.git_hash, report.is_dirty);
+        human_println!("platform: {}-{}", report.os, report.arch);
+    }
+
+    Ok(())
+}
@@ -6,7 +6,7 @@ use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 
 use nargo::constants::PROVER_INPUT_FILE;
-use nargo::errors::try_to_diagnose_runtime_error;
+use nargo::errors::{try_to_diagnose_runtime_error, try_to_locate_runtime_error};
 use nargo::foreign_calls::DefaultForeignCallExecutor;
 use nargo::package::{CrateName, Package};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
@@ -14,6 +14,7 @@ use noirc_abi::input_parser::{Format, InputValue};
 use noirc_abi::InputMap;
 use noirc_artifacts::debug::DebugArtifact;
 use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_errors::reporter::format_location;
 
 use super::compile_cmd::compile_workspace_full;
 use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
@@ -80,15 +81,15 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
             Some(package.name.to_string()),
         )?;
 
-        println!("[{}] Circuit witness successfully solved", package.name);
+        human_println!("[{}] Circuit witness successfully solved", package.name);
         if let Some(return_value) = return_value {
-            println!("[{}] Circuit output: {return_value:?}", package.name);
+            human_println!("[{}] Circuit output: {return_value:?}", package.name);
         }
 
         let package_name = package.name.clone().into();
         let witness_name = args.witness_name.as_ref().unwrap_or(&package_name);
         let witness_path = save_witness_to_dir(witness_stack, witness_name, target_dir)?;
-        println!("[{}] Witness saved to {}", package.name, witness_path.display());
+        human_println!("[{}] Witness saved to {}", package.name, witness_path.display());
     }
     Ok(())
 }
@@ -148,7 +149,12 @@ pub(crate) fn execute_program(
                 diagnostic.report(&debug_artifact, false);
             }
 
-            Err(crate::errors::CliError::NargoError(err))
+            let location = try_to_locate_runtime_error(&err, &compiled_program.debug)
+                .and_then(|location| format_location(&debug_artifact, location));
+            match location {
+                Some(location) => Err(CliError::ExecutionFailedAt { error: err, location }),
+                None => Err(CliError::NargoError(err)),
+            }
         }
     }
 }
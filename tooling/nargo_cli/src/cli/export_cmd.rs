@@ -98,6 +98,7 @@ fn compile_exported_functions(
                 file_manager,
                 compile_options.deny_warnings,
                 compile_options.silence_warnings,
+                compile_options.message_format,
             )?;
 
             Ok((function_name, program))
@@ -106,7 +107,12 @@ fn compile_exported_functions(
 
     let export_dir = workspace.export_directory_path();
     for (function_name, program) in exported_programs {
-        save_program_to_file(&program.into(), &function_name.parse().unwrap(), &export_dir);
+        save_program_to_file(
+            &program.into(),
+            &function_name.parse().unwrap(),
+            &export_dir,
+            compile_options.pretty_artifacts,
+        );
     }
     Ok(())
 }
@@ -0,0 +1,83 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use acvm::acir::circuit::Program;
+use clap::Args;
+
+use crate::errors::CliError;
+
+use super::fs::program::{read_contract_from_file, read_program_from_file};
+use super::NargoConfig;
+
+/// Extracts the serialized ACIR bytecode from a compiled artifact
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExtractAcirCommand {
+    /// Path to the program or contract artifact to extract the bytecode from
+    artifact_path: PathBuf,
+
+    /// The name of the contract function whose bytecode should be extracted.
+    /// Required for contract artifacts.
+    #[clap(long)]
+    function: Option<String>,
+
+    /// Write the bytecode to this file rather than to stdout
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+
+    /// Encode the bytecode as base64, as it's stored within artifacts
+    #[clap(long)]
+    base64: bool,
+}
+
+pub(crate) fn run(args: ExtractAcirCommand, config: NargoConfig) -> Result<(), CliError> {
+    let artifact_path = config.program_dir.join(&args.artifact_path);
+
+    let program = match &args.function {
+        Some(function_name) => {
+            let contract = read_contract_from_file(&artifact_path)?;
+            let function = contract
+                .functions
+                .into_iter()
+                .find(|function| &function.name == function_name)
+                .ok_or_else(|| {
+                    CliError::Generic(format!(
+                        "Contract `{}` has no function named `{function_name}`",
+                        contract.name
+                    ))
+                })?;
+            function.bytecode
+        }
+        None => {
+            read_program_from_file(&artifact_path)
+                .map_err(|err| {
+                    CliError::Generic(format!(
+                    "{err}\nIf this is a contract artifact, select a function with `--function`"
+                ))
+                })?
+                .bytecode
+        }
+    };
+
+    let bytes = if args.base64 {
+        let encoded = Program::serialize_program_base64(&program, serde_json::value::Serializer)
+            .expect("Program should be serializable");
+        encoded.as_str().expect("Program should be serialized as a string").as_bytes().to_vec()
+    } else {
+        Program::serialize_program(&program)
+    };
+
+    match args.output {
+        Some(output_path) => {
+            std::fs::write(&output_path, bytes).map_err(|err| {
+                CliError::Generic(format!("Failed to write {}: {err}", output_path.display()))
+            })?;
+        }
+        None => {
+            std::io::stdout()
+                .write_all(&bytes)
+                .map_err(|err| CliError::Generic(format!("Failed to write to stdout: {err}")))?;
+        }
+    }
+
+    Ok(())
+}
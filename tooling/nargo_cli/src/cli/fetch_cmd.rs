@@ -0,0 +1,118 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use clap::Args;
+use nargo_toml::checksum_package_sources;
+use semver::VersionReq;
+
+use crate::errors::CliError;
+
+use super::registry::{fetch_package, registry_url, RegistryArgs};
+use super::NargoConfig;
+
+/// The directory within the home directory which fetched packages are cached in.
+const FETCH_CACHE_DIR: &str = "nargo/registry";
+
+/// Download a package from the registry to inspect it, without adding it as a dependency
+#[derive(Debug, Clone, Args)]
+pub(crate) struct FetchCommand {
+    /// The package to download, as `<name>@<version>`.
+    /// Without a version, the latest version which the current compiler can compile is downloaded.
+    package: String,
+
+    /// The directory to download the package into, instead of the cache in `~/nargo/registry`
+    #[clap(long)]
+    output_dir: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(super) registry: RegistryArgs,
+}
+
+pub(crate) fn run(args: FetchCommand, config: NargoConfig) -> Result<(), CliError> {
+    let (name, version) = match args.package.split_once('@') {
+        Some((name, version)) => (name, Some(version)),
+        None => (args.package.as_str(), None),
+    };
+    // A bare version asks for exactly that version, rather than any compatible one.
+    let requirement = version
+        .map(|version| {
+            let requirement = if semver::Version::parse(version).is_ok() {
+                format!("={version}")
+            } else {
+                version.to_string()
+            };
+            VersionReq::parse(&requirement).map_err(|err| {
+                CliError::Generic(format!("Invalid version requirement `{version}`: {err}"))
+            })
+        })
+        .transpose()?;
+
+    let package = fetch_package(name)?;
+    let Some((selected_version, registry_version)) =
+        package.latest_compatible_version(requirement.as_ref())
+    else {
+        let published: Vec<&str> =
+            package.versions.iter().map(|version| version.version.as_str()).collect();
+        return Err(CliError::Generic(format!(
+            "No version of `{name}` in the registry at {} matches {} and the current compiler version. Published versions: {}",
+            registry_url(),
+            version.unwrap_or("*"),
+            if published.is_empty() { "none".to_string() } else { published.join(", ") }
+        )));
+    };
+
+    let destination = match &args.output_dir {
+        Some(output_dir) => config.program_dir.join(output_dir),
+        None => dirs::home_dir()
+            .unwrap_or_default()
+            .join(FETCH_CACHE_DIR)
+            .join(format!("{name}-{selected_version}")),
+    };
+
+    if destination.exists() {
+        if args.output_dir.is_some() {
+            return Err(CliError::DestinationAlreadyExists(destination));
+        }
+        // A cached download is only reused if its sources still match what was published.
+    } else {
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| {
+                CliError::Generic(format!("Failed to create {}: {err}", parent.display()))
+            })?;
+        }
+        let status = Command::new("git")
+            .args(["-c", "advice.detachedHead=false", "clone", "--quiet", "--depth", "1"])
+            .arg("--branch")
+            .arg(&registry_version.tag)
+            .arg(&package.repository)
+            .arg(&destination)
+            .status()
+            .map_err(|err| CliError::Generic(format!("Failed to run `git clone`: {err}")))?;
+        if !status.success() {
+            return Err(CliError::Generic(format!(
+                "Failed to download `{name}` {selected_version} from {} at tag `{}`",
+                package.repository, registry_version.tag
+            )));
+        }
+    }
+
+    match &registry_version.checksum {
+        Some(expected) => {
+            let actual = checksum_package_sources(&destination);
+            if &actual != expected {
+                let _ = std::fs::remove_dir_all(&destination);
+                return Err(CliError::ChecksumMismatch {
+                    artifact_path: destination,
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+        None => eprintln!(
+            "Warning: the registry has no checksum for `{name}` {selected_version}, so its sources could not be verified"
+        ),
+    }
+
+    human_println!("Downloaded `{name}` {selected_version} to {}", destination.display());
+    Ok(())
+}
@@ -79,7 +79,7 @@ pub(crate) fn run(args: FormatCommand, config: NargoConfig) -> Result<(), CliErr
                         check_exit_code_one = true;
                     }
 
-                    println!("{diff}");
+                    human_println!("{diff}");
                 }
 
                 Ok(())
@@ -93,7 +93,7 @@ pub(crate) fn run(args: FormatCommand, config: NargoConfig) -> Result<(), CliErr
     if check_exit_code_one {
         std::process::exit(1);
     } else if check_mode {
-        println!("No formatting changes were detected");
+        human_println!("No formatting changes were detected");
     }
 
     Ok(())
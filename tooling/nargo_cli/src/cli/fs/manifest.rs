@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use toml_edit::Document;
+
+use crate::errors::CliError;
+
+/// Reads a `Nargo.toml` in a form which can be edited while preserving its formatting and comments.
+pub(crate) fn read_manifest_document(manifest_path: &Path) -> Result<Document, CliError> {
+    let contents = std::fs::read_to_string(manifest_path).map_err(|err| {
+        CliError::Generic(format!("Failed to read {}: {err}", manifest_path.display()))
+    })?;
+    contents.parse().map_err(|err| {
+        CliError::Generic(format!("Failed to parse {}: {err}", manifest_path.display()))
+    })
+}
+
+pub(crate) fn write_manifest_document(
+    manifest_path: &Path,
+    document: &Document,
+) -> Result<(), CliError> {
+    std::fs::write(manifest_path, document.to_string()).map_err(|err| {
+        CliError::Generic(format!("Failed to write {}: {err}", manifest_path.display()))
+    })
+}
+
+/// Returns the `[dependencies]` table of a package manifest, creating it if it doesn't exist yet.
+pub(crate) fn dependencies_table<'a>(
+    manifest_path: &Path,
+    document: &'a mut Document,
+) -> Result<&'a mut dyn toml_edit::TableLike, CliError> {
+    if !document.contains_key("package") {
+        return Err(CliError::Generic(format!(
+            "{} is not the manifest of a package. Run this command from within a workspace member",
+            manifest_path.display()
+        )));
+    }
+
+    document.entry("dependencies").or_insert(toml_edit::table()).as_table_like_mut().ok_or_else(
+        || {
+            CliError::Generic(format!(
+                "`dependencies` in {} is not a table",
+                manifest_path.display()
+            ))
+        },
+    )
+}
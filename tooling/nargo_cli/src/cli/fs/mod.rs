@@ -5,6 +5,7 @@ use std::{
 };
 
 pub(super) mod inputs;
+pub(super) mod manifest;
 pub(super) mod program;
 pub(super) mod witness;
 
@@ -28,3 +29,13 @@ pub(super) fn write_to_file(bytes: &[u8], path: &Path) -> String {
         Ok(_) => display.to_string(),
     }
 }
+
+/// Checks that files can be written to `dir`, creating it if it doesn't exist yet.
+///
+/// This writes and then removes an empty file, as permissions alone don't account for read-only mounts.
+pub(super) fn check_dir_writable(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe_path = dir.join(format!(".nargo-write-check-{}", std::process::id()));
+    File::create(&probe_path)?;
+    std::fs::remove_file(probe_path)
+}
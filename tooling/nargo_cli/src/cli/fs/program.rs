@@ -1,7 +1,12 @@
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use nargo::package::CrateName;
-use noirc_artifacts::{contract::ContractArtifact, program::ProgramArtifact};
+use nargo::package::{CrateName, Package};
+use nargo::workspace::Workspace;
+use noirc_artifacts::{
+    contract::{ContractAbiArtifact, ContractArtifact},
+    program::ProgramArtifact,
+};
 
 use crate::errors::FilesystemError;
 
@@ -11,31 +16,106 @@ pub(crate) fn save_program_to_file<P: AsRef<Path>>(
     program_artifact: &ProgramArtifact,
     crate_name: &CrateName,
     circuit_dir: P,
+    pretty: bool,
 ) -> PathBuf {
     let circuit_name: String = crate_name.into();
-    save_build_artifact_to_file(program_artifact, &circuit_name, circuit_dir)
+    save_build_artifact_to_file(program_artifact, &circuit_name, circuit_dir, pretty)
+}
+
+/// Saves the program artifact to `artifact_path`, as mapped in the manifest or within the target directory.
+pub(crate) fn save_program_to_path(
+    program_artifact: &ProgramArtifact,
+    artifact_path: &Path,
+    pretty: bool,
+) -> PathBuf {
+    save_build_artifact_to_path(program_artifact, artifact_path, pretty)
 }
 
 pub(crate) fn save_contract_to_file<P: AsRef<Path>>(
     compiled_contract: &ContractArtifact,
     circuit_name: &str,
     circuit_dir: P,
+    pretty: bool,
+) -> PathBuf {
+    save_build_artifact_to_file(compiled_contract, circuit_name, circuit_dir, pretty)
+}
+
+/// Saves the contract artifact to `artifact_path`, as mapped in the manifest.
+pub(crate) fn save_contract_to_path(
+    compiled_contract: &ContractArtifact,
+    artifact_path: &Path,
+    pretty: bool,
+) -> PathBuf {
+    save_build_artifact_to_path(compiled_contract, artifact_path, pretty)
+}
+
+/// Saves the contract's ABI to `abi_path`, which is expected to be next to the contract's artifact.
+pub(crate) fn save_contract_abi_to_path(
+    contract_abi: &ContractAbiArtifact,
+    abi_path: &Path,
+    pretty: bool,
 ) -> PathBuf {
-    save_build_artifact_to_file(compiled_contract, circuit_name, circuit_dir)
+    save_build_artifact_to_path(contract_abi, abi_path, pretty)
 }
 
-fn save_build_artifact_to_file<P: AsRef<Path>, T: ?Sized + serde::Serialize>(
+pub(crate) fn save_build_artifact_to_file<P: AsRef<Path>, T: ?Sized + serde::Serialize>(
     build_artifact: &T,
     artifact_name: &str,
     circuit_dir: P,
+    pretty: bool,
 ) -> PathBuf {
     create_named_dir(circuit_dir.as_ref(), "target");
     let circuit_path = circuit_dir.as_ref().join(artifact_name).with_extension("json");
-    write_to_file(&serde_json::to_vec(build_artifact).unwrap(), &circuit_path);
+    write_build_artifact(build_artifact, &circuit_path, pretty);
 
     circuit_path
 }
 
+fn save_build_artifact_to_path<T: ?Sized + serde::Serialize>(
+    build_artifact: &T,
+    artifact_path: &Path,
+    pretty: bool,
+) -> PathBuf {
+    if let Some(artifact_dir) = artifact_path.parent() {
+        create_named_dir(artifact_dir, "artifact");
+    }
+    let artifact_path = artifact_path.with_extension("json");
+    write_build_artifact(build_artifact, &artifact_path, pretty);
+
+    artifact_path
+}
+
+/// Writes the artifact to a temporary file next to `artifact_path` before moving it into place,
+/// so that packages compiled in parallel never leave a partially written artifact behind,
+/// even if the manifest maps several of them to the same path.
+///
+/// An existing artifact with the same contents is left untouched.
+fn write_build_artifact<T: ?Sized + serde::Serialize>(
+    build_artifact: &T,
+    artifact_path: &Path,
+    pretty: bool,
+) {
+    static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let artifact_bytes = if pretty {
+        serde_json::to_vec_pretty(build_artifact).unwrap()
+    } else {
+        serde_json::to_vec(build_artifact).unwrap()
+    };
+    if std::fs::read(artifact_path).map_or(false, |existing_bytes| existing_bytes == artifact_bytes)
+    {
+        return;
+    }
+    let temp_file_id = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let temp_path =
+        artifact_path.with_extension(format!("json.{}-{temp_file_id}.tmp", std::process::id()));
+    write_to_file(&artifact_bytes, &temp_path);
+    if let Err(err) = std::fs::rename(&temp_path, artifact_path) {
+        let _ = std::fs::remove_file(&temp_path);
+        panic!("couldn't move {} into place: {err}", artifact_path.display());
+    }
+}
+
 pub(crate) fn read_program_from_file<P: AsRef<Path>>(
     circuit_path: P,
 ) -> Result<ProgramArtifact, FilesystemError> {
@@ -48,3 +128,70 @@ pub(crate) fn read_program_from_file<P: AsRef<Path>>(
 
     Ok(program)
 }
+
+pub(crate) fn read_contract_from_file<P: AsRef<Path>>(
+    circuit_path: P,
+) -> Result<ContractArtifact, FilesystemError> {
+    let file_path = circuit_path.as_ref().with_extension("json");
+
+    let input_string =
+        std::fs::read(&file_path).map_err(|_| FilesystemError::PathNotValid(file_path))?;
+    let contract = serde_json::from_slice(&input_string)
+        .map_err(|err| FilesystemError::ProgramSerializationError(err.to_string()))?;
+
+    Ok(contract)
+}
+
+/// Reads the contract artifact saved for the package named `crate_name` in `circuit_dir`, if there is one.
+pub(crate) fn read_package_contract_from_dir<P: AsRef<Path>>(
+    crate_name: &CrateName,
+    circuit_dir: P,
+) -> Option<ContractArtifact> {
+    find_package_contract_in_dir(crate_name, circuit_dir).map(|(_, contract)| contract)
+}
+
+/// Finds the contract artifact saved for the package named `crate_name` in `circuit_dir`,
+/// returning its path along with the artifact.
+fn find_package_contract_in_dir<P: AsRef<Path>>(
+    crate_name: &CrateName,
+    circuit_dir: P,
+) -> Option<(PathBuf, ContractArtifact)> {
+    // Contract artifacts are named after both the package and the contract, and the latter isn't known
+    // until the package has been compiled. Package names cannot contain hyphens so the prefix is unambiguous.
+    let artifact_prefix = format!("{crate_name}-");
+    std::fs::read_dir(circuit_dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().map_or(false, |ext| ext == "json")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map_or(false, |name| name.starts_with(&artifact_prefix))
+        })
+        .find_map(|path| read_contract_from_file(&path).ok().map(|contract| (path, contract)))
+}
+
+/// Reads the contract artifact of `package`, from the path mapped in the manifest if there is one
+/// or otherwise from the workspace's target directory.
+pub(crate) fn read_workspace_contract(
+    workspace: &Workspace,
+    package: &Package,
+) -> Option<ContractArtifact> {
+    find_workspace_contract(workspace, package).map(|(_, contract)| contract)
+}
+
+/// Same as [`read_workspace_contract`], but also returns the path the artifact was read from.
+pub(crate) fn find_workspace_contract(
+    workspace: &Workspace,
+    package: &Package,
+) -> Option<(PathBuf, ContractArtifact)> {
+    match workspace.package_artifact_path(package) {
+        Some(artifact_path) => {
+            let artifact_path = artifact_path.with_extension("json");
+            read_contract_from_file(&artifact_path).ok().map(|contract| (artifact_path, contract))
+        }
+        None => find_package_contract_in_dir(&package.name, workspace.target_directory_path()),
+    }
+}
@@ -10,7 +10,7 @@ use nargo::{
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::input_parser::Format;
 use noirc_artifacts::program::ProgramArtifact;
-use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_driver::{CompileOptions, MessageFormat, NOIR_ARTIFACT_VERSION_STRING};
 use prettytable::{row, table, Row};
 use rayon::prelude::*;
 use serde::Serialize;
@@ -20,7 +20,7 @@ use crate::{cli::fs::inputs::read_inputs_from_file, errors::CliError};
 use super::{
     compile_cmd::{compile_workspace_full, get_target_width},
     fs::program::read_program_from_file,
-    NargoConfig,
+    json_output, NargoConfig,
 };
 
 /// Provides detailed information on each of a program's function (represented by a single circuit)
@@ -39,9 +39,9 @@ pub(crate) struct InfoCommand {
     #[clap(long, conflicts_with = "package")]
     workspace: bool,
 
-    /// Output a JSON formatted report. Changes to this format are not currently considered breaking.
+    /// Output a JSON formatted report. Same as `--format json`. Changes to this format are not currently considered breaking.
     #[clap(long, hide = true)]
-    json: bool,
+    pub(super) json: bool,
 
     #[clap(long)]
     profile_execution: bool,
@@ -55,6 +55,11 @@ pub(crate) struct InfoCommand {
 }
 
 pub(crate) fn run(mut args: InfoCommand, config: NargoConfig) -> Result<(), CliError> {
+    if json_output() {
+        args.json = true;
+        args.compile_options.message_format = MessageFormat::Json;
+    }
+
     let toml_path = get_package_manifest(&config.program_dir)?;
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
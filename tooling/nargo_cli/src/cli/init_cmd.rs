@@ -2,10 +2,11 @@ use crate::errors::CliError;
 
 use super::fs::{create_named_dir, write_to_file};
 use super::NargoConfig;
-use clap::Args;
-use nargo::constants::{PKG_FILE, SRC_DIR};
+use clap::{Args, ValueEnum};
+use nargo::constants::{PKG_FILE, PROVER_INPUT_FILE, SRC_DIR};
 use nargo::package::{CrateName, PackageType};
 use std::path::PathBuf;
+use std::process::Command;
 
 /// Create a Noir project in the current directory.
 #[derive(Debug, Clone, Args)]
@@ -25,9 +26,33 @@ pub(crate) struct InitCommand {
     /// Use a contract template
     #[arg(long, conflicts_with = "lib", conflicts_with = "bin")]
     pub(crate) contract: bool,
+
+    /// Author of the package, may be repeated [default: `user.name <user.email>` from git config]
+    #[arg(long)]
+    pub(crate) author: Vec<String>,
+
+    /// The example program to start a binary package from
+    #[arg(long, value_enum, default_value_t, conflicts_with = "lib", conflicts_with = "contract")]
+    pub(crate) example: BinaryExample,
+}
+
+/// The example programs which a binary package can be created from.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub(crate) enum BinaryExample {
+    /// Asserts that two inputs differ
+    #[default]
+    Basic,
+    /// Checks knowledge of the preimage of a hash
+    Hash,
+    /// Checks the inclusion of a leaf in a small Merkle tree
+    Merkle,
 }
 
 const BIN_EXAMPLE: &str = include_str!("./noir_template_files/binary.nr");
+const BIN_HASH_EXAMPLE: &str = include_str!("./noir_template_files/binary_hash.nr");
+const BIN_HASH_PROVER: &str = include_str!("./noir_template_files/binary_hash_prover.toml");
+const BIN_MERKLE_EXAMPLE: &str = include_str!("./noir_template_files/binary_merkle.nr");
+const BIN_MERKLE_PROVER: &str = include_str!("./noir_template_files/binary_merkle_prover.toml");
 const CONTRACT_EXAMPLE: &str = include_str!("./noir_template_files/contract.nr");
 const LIB_EXAMPLE: &str = include_str!("./noir_template_files/library.nr");
 
@@ -47,7 +72,7 @@ pub(crate) fn run(args: InitCommand, config: NargoConfig) -> Result<(), CliError
     } else {
         PackageType::Binary
     };
-    initialize_project(config.program_dir, package_name, package_type);
+    initialize_project(config.program_dir, package_name, package_type, args.author, args.example);
     Ok(())
 }
 
@@ -56,15 +81,25 @@ pub(crate) fn initialize_project(
     package_dir: PathBuf,
     package_name: CrateName,
     package_type: PackageType,
+    authors: Vec<String>,
+    example: BinaryExample,
 ) {
     let src_dir = package_dir.join(SRC_DIR);
     create_named_dir(&src_dir, "src");
 
+    let authors = if authors.is_empty() {
+        // Keep an empty placeholder for the user to fill in if git doesn't know who they are.
+        vec![git_config_author().unwrap_or_default()]
+    } else {
+        authors
+    };
+    let authors = toml::Value::from(authors);
+
     let toml_contents = format!(
         r#"[package]
 name = "{package_name}"
 type = "{package_type}"
-authors = [""]
+authors = {authors}
 
 [dependencies]"#
     );
@@ -72,11 +107,44 @@ authors = [""]
     write_to_file(toml_contents.as_bytes(), &package_dir.join(PKG_FILE));
     // This uses the `match` syntax instead of `if` so we get a compile error when we add new package types (which likely need new template files)
     match package_type {
-        PackageType::Binary => write_to_file(BIN_EXAMPLE.as_bytes(), &src_dir.join("main.nr")),
+        PackageType::Binary => {
+            let (main, prover) = match example {
+                BinaryExample::Basic => (BIN_EXAMPLE, None),
+                BinaryExample::Hash => (BIN_HASH_EXAMPLE, Some(BIN_HASH_PROVER)),
+                BinaryExample::Merkle => (BIN_MERKLE_EXAMPLE, Some(BIN_MERKLE_PROVER)),
+            };
+            if let Some(prover) = prover {
+                write_to_file(
+                    prover.as_bytes(),
+                    &package_dir.join(PROVER_INPUT_FILE).with_extension("toml"),
+                );
+            }
+            write_to_file(main.as_bytes(), &src_dir.join("main.nr"))
+        }
         PackageType::Contract => {
             write_to_file(CONTRACT_EXAMPLE.as_bytes(), &src_dir.join("main.nr"))
         }
         PackageType::Library => write_to_file(LIB_EXAMPLE.as_bytes(), &src_dir.join("lib.nr")),
     };
-    println!("Project successfully created! It is located at {}", package_dir.display());
+    human_println!("Project successfully created! It is located at {}", package_dir.display());
+}
+
+/// Reads the author from the user's git config, formatted as `user.name <user.email>`.
+///
+/// This is best-effort so any failure to run git or a missing `user.name` results in `None`.
+fn git_config_author() -> Option<String> {
+    let read_git_config = |key: &str| {
+        let output = Command::new("git").args(["config", "--get", key]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    };
+
+    let name = read_git_config("user.name")?;
+    match read_git_config("user.email") {
+        Some(email) => Some(format!("{name} <{email}>")),
+        None => Some(name),
+    }
 }
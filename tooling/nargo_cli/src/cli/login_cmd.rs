@@ -0,0 +1,35 @@
+use std::io::{BufRead, Write};
+
+use clap::Args;
+
+use crate::errors::CliError;
+
+use super::registry::{registry_url, save_registry_token, validate_token, RegistryArgs};
+use super::NargoConfig;
+
+/// Store a token for authenticating with the package registry
+#[derive(Debug, Clone, Args)]
+pub(crate) struct LoginCommand {
+    #[clap(flatten)]
+    pub(super) registry: RegistryArgs,
+}
+
+pub(crate) fn run(_args: LoginCommand, _config: NargoConfig) -> Result<(), CliError> {
+    print!("Please paste the API token from {} below\n> ", registry_url());
+    std::io::stdout().flush().ok();
+
+    let mut token = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut token)
+        .map_err(|err| CliError::Generic(format!("Failed to read the token: {err}")))?;
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(CliError::Generic("No token was given".to_string()));
+    }
+
+    validate_token(token)?;
+    let credentials_path = save_registry_token(token)?;
+    human_println!("Login token saved to {}", credentials_path.display());
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use nargo::{
+    package::{Dependency, Package},
+    workspace::Workspace,
+};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use serde::Serialize;
+
+use crate::errors::CliError;
+
+use super::{json_output, NargoConfig};
+
+/// Prints the resolved workspace: its packages, their source files, dependencies and artifact paths
+#[derive(Debug, Clone, Args)]
+pub(crate) struct MetadataCommand {
+    /// Output the workspace metadata as JSON. Same as `--format json`
+    #[clap(long)]
+    pub(super) json: bool,
+}
+
+pub(crate) fn run(_args: MetadataCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        PackageSelection::All,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    let metadata = WorkspaceMetadata::new(&workspace)?;
+
+    if json_output() {
+        println!("{}", serde_json::to_string(&metadata).unwrap());
+        return Ok(());
+    }
+
+    human_println!("Workspace: {}", metadata.root_dir.display());
+    human_println!("Target directory: {}", metadata.target_dir.display());
+    for package in metadata.packages {
+        human_println!();
+        let version = package.version.map(|version| format!(" {version}")).unwrap_or_default();
+        human_println!("{}{version} ({})", package.name, package.package_type);
+        human_println!("    root: {}", package.root_dir.display());
+        human_println!("    entry: {}", package.entry_path.display());
+        if let Some(artifact_path) = package.artifact_path {
+            human_println!("    artifact: {}", artifact_path.display());
+        }
+        human_println!("    sources:");
+        for source in package.sources {
+            human_println!("        {}", source.display());
+        }
+        if !package.dependencies.is_empty() {
+            human_println!("    dependencies:");
+            for dependency in package.dependencies {
+                human_println!(
+                    "        {} ({}, {}) {}",
+                    dependency.name,
+                    dependency.package,
+                    dependency.kind,
+                    dependency.root_dir.display()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct WorkspaceMetadata {
+    root_dir: PathBuf,
+    target_dir: PathBuf,
+    packages: Vec<PackageMetadata>,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageMetadata {
+    name: String,
+    version: Option<String>,
+    #[serde(rename = "type")]
+    package_type: String,
+    root_dir: PathBuf,
+    entry_path: PathBuf,
+    /// Noir source files of the package, relative to its root directory.
+    sources: Vec<PathBuf>,
+    dependencies: Vec<DependencyMetadata>,
+    /// Libraries have no artifact.
+    artifact_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize)]
+struct DependencyMetadata {
+    /// The name the dependency is imported under.
+    name: String,
+    /// The name of the dependency's package.
+    package: String,
+    /// Either `local` or `remote`.
+    kind: &'static str,
+    root_dir: PathBuf,
+}
+
+impl WorkspaceMetadata {
+    fn new(workspace: &Workspace) -> Result<Self, CliError> {
+        let packages = workspace
+            .members
+            .iter()
+            .map(|package| PackageMetadata::new(workspace, package))
+            .collect::<Result<_, _>>()?;
+
+        Ok(WorkspaceMetadata {
+            root_dir: workspace.root_dir.clone(),
+            target_dir: workspace.target_directory_path(),
+            packages,
+        })
+    }
+}
+
+impl PackageMetadata {
+    fn new(workspace: &Workspace, package: &Package) -> Result<Self, CliError> {
+        let source_dir = package.entry_path.parent().unwrap_or(&package.root_dir);
+        let mut sources = Vec::new();
+        collect_noir_files(source_dir, &mut sources)
+            .map_err(|error| CliError::Generic(error.to_string()))?;
+        let mut sources: Vec<PathBuf> = sources
+            .into_iter()
+            .map(|path| path.strip_prefix(&package.root_dir).map(Path::to_path_buf).unwrap_or(path))
+            .collect();
+        sources.sort();
+
+        let dependencies = package
+            .dependencies
+            .iter()
+            .map(|(name, dependency)| {
+                let (kind, dependency_package) = match dependency {
+                    Dependency::Local { package } => ("local", package),
+                    Dependency::Remote { package } => ("remote", package),
+                };
+                DependencyMetadata {
+                    name: name.to_string(),
+                    package: dependency_package.name.to_string(),
+                    kind,
+                    root_dir: dependency_package.root_dir.clone(),
+                }
+            })
+            .collect();
+
+        let artifact_path = (!package.is_library()).then(|| workspace.package_build_path(package));
+
+        Ok(PackageMetadata {
+            name: package.name.to_string(),
+            version: package.version.clone(),
+            package_type: package.package_type.to_string(),
+            root_dir: package.root_dir.clone(),
+            entry_path: package.entry_path.clone(),
+            sources,
+            dependencies,
+            artifact_path,
+        })
+    }
+}
+
+fn collect_noir_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if dir.is_dir() {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                collect_noir_files(&path, files)?;
+            } else if path.extension().map_or(false, |extension| extension == "nr") {
+                files.push(path);
+            }
+        }
+    }
+    Ok(())
+}
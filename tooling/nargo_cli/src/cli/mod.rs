@@ -1,26 +1,63 @@
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use const_format::formatcp;
 use nargo_toml::find_package_root;
 use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use serde::Serialize;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use color_eyre::eyre;
 
+/// Prints human-readable text to stdout, or to stderr when `--format json` was passed
+/// so that stdout is left to the command's JSON output.
+macro_rules! human_println {
+    ($($arg:tt)*) => {
+        if $crate::cli::json_output() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
 mod fs;
 
+mod add_cmd;
+mod bench_cmd;
 mod check_cmd;
+mod checksum_cmd;
+mod circuit_info_cmd;
+mod compile_cache;
 mod compile_cmd;
+mod compile_replay;
+mod compile_report;
+mod compile_shards;
 mod dap_cmd;
 mod debug_cmd;
+mod env_cmd;
 mod execute_cmd;
 mod export_cmd;
+mod extract_acir_cmd;
+mod fetch_cmd;
 mod fmt_cmd;
 mod generate_completion_script_cmd;
 mod info_cmd;
 mod init_cmd;
+mod login_cmd;
 mod lsp_cmd;
+mod metadata_cmd;
 mod new_cmd;
+mod package_cmd;
+mod public_api_cmd;
+mod registry;
+mod remove_cmd;
+mod search_cmd;
 mod test_cmd;
+mod unused_deps_cmd;
+mod update_cmd;
+mod vendor_cmd;
+mod verify_checksum_cmd;
+mod yank_cmd;
 
 const GIT_HASH: &str = env!("GIT_COMMIT");
 const IS_DIRTY: &str = env!("GIT_DIRTY");
@@ -50,6 +87,38 @@ pub(crate) struct NargoConfig {
     // REMINDER: Also change this flag in the LSP test lens if renamed
     #[arg(long, hide = true, global = true, default_value = "./")]
     program_dir: PathBuf,
+
+    /// Resolve the workspace from its manifests rather than reusing a cached resolution
+    #[arg(long, global = true)]
+    no_resolve_cache: bool,
+
+    /// The format of the command's output. With `json`, diagnostics, reports and a final status are written
+    /// to stdout as JSON objects, one per line, while any human-readable text is written to stderr.
+    #[arg(long, global = true, value_enum, default_value_t)]
+    format: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// True if `--format json` was passed, in which case commands should write JSON to stdout.
+pub(crate) fn json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// The final line written to stdout with `--format json`, reporting whether the command succeeded.
+#[derive(Debug, Serialize)]
+struct JsonStatus {
+    /// Either `success` or `failure`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
 }
 
 #[non_exhaustive]
@@ -64,15 +133,63 @@ enum NargoCommand {
     Execute(execute_cmd::ExecuteCommand),
     #[command(hide = true)] // Hidden while the feature is being built out
     Export(export_cmd::ExportCommand),
+    ExtractAcir(extract_acir_cmd::ExtractAcirCommand),
     Debug(debug_cmd::DebugCommand),
     Test(test_cmd::TestCommand),
+    Bench(bench_cmd::BenchCommand),
     Info(info_cmd::InfoCommand),
+    Metadata(metadata_cmd::MetadataCommand),
+    CircuitInfo(circuit_info_cmd::CircuitInfoCommand),
+    Env(env_cmd::EnvCommand),
+    Checksum(checksum_cmd::ChecksumCommand),
+    VerifyChecksum(verify_checksum_cmd::VerifyChecksumCommand),
     Lsp(lsp_cmd::LspCommand),
+    Vendor(vendor_cmd::VendorCommand),
+    Package(package_cmd::PackageCommand),
+    Add(add_cmd::AddCommand),
+    Remove(remove_cmd::RemoveCommand),
+    Update(update_cmd::UpdateCommand),
+    Search(search_cmd::SearchCommand),
+    Login(login_cmd::LoginCommand),
+    Fetch(fetch_cmd::FetchCommand),
+    Yank(yank_cmd::YankCommand),
+    Unyank(yank_cmd::UnyankCommand),
+    UnusedDeps(unused_deps_cmd::UnusedDepsCommand),
+    PublicApi(public_api_cmd::PublicApiCommand),
     #[command(hide = true)]
     Dap(dap_cmd::DapCommand),
     GenerateCompletionScript(generate_completion_script_cmd::GenerateCompletionScriptCommand),
 }
 
+impl NargoCommand {
+    /// True if the command's own `--json` flag was passed, which is an alias of `--format json`.
+    fn json_flag(&self) -> bool {
+        match self {
+            NargoCommand::Compile(args) => args.json,
+            NargoCommand::Info(args) => args.json,
+            NargoCommand::Metadata(args) => args.json,
+            NargoCommand::CircuitInfo(args) => args.json,
+            NargoCommand::Env(args) => args.json,
+            NargoCommand::Search(args) => args.json,
+            NargoCommand::PublicApi(args) => args.json,
+            _ => false,
+        }
+    }
+
+    /// The registry options of the commands which talk to the package registry.
+    fn registry_args(&self) -> Option<&registry::RegistryArgs> {
+        match self {
+            NargoCommand::Add(args) => Some(&args.registry),
+            NargoCommand::Search(args) => Some(&args.registry),
+            NargoCommand::Login(args) => Some(&args.registry),
+            NargoCommand::Fetch(args) => Some(&args.registry),
+            NargoCommand::Yank(args) => Some(&args.registry),
+            NargoCommand::Unyank(args) => Some(&args.registry),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(not(feature = "codegen-docs"))]
 pub(crate) fn start_cli() -> eyre::Result<()> {
     let NargoCli { command, mut config } = NargoCli::parse();
@@ -82,8 +199,18 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         config.program_dir = std::env::current_dir().unwrap().join(config.program_dir);
     }
 
+    nargo_toml::set_resolve_cache_enabled(!config.no_resolve_cache);
+    JSON_OUTPUT
+        .store(config.format == OutputFormat::Json || command.json_flag(), Ordering::Relaxed);
+
+    if let Some(registry_args) = command.registry_args() {
+        registry_args.configure()?;
+    }
+
     // Search through parent directories to find package root if necessary.
     match &command {
+        // There's no package root to find when the manifest is read from stdin.
+        NargoCommand::Compile(args) if args.reads_manifest_from_stdin() => (),
         NargoCommand::Check(..)
         | NargoCommand::Fmt(..)
         | NargoCommand::Compile(..)
@@ -91,17 +218,36 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         | NargoCommand::Export(..)
         | NargoCommand::Debug(..)
         | NargoCommand::Test(..)
-        | NargoCommand::Info(..) => {
+        | NargoCommand::Bench(..)
+        | NargoCommand::Info(..)
+        | NargoCommand::Metadata(..)
+        | NargoCommand::Vendor(..)
+        | NargoCommand::Package(..)
+        | NargoCommand::Add(..)
+        | NargoCommand::Remove(..)
+        | NargoCommand::Update(..)
+        | NargoCommand::UnusedDeps(..)
+        | NargoCommand::PublicApi(..) => {
             config.program_dir = find_package_root(&config.program_dir)?;
         }
         NargoCommand::New(..)
         | NargoCommand::Init(..)
         | NargoCommand::Lsp(..)
         | NargoCommand::Dap(..)
+        | NargoCommand::Env(..)
+        | NargoCommand::ExtractAcir(..)
+        | NargoCommand::CircuitInfo(..)
+        | NargoCommand::Search(..)
+        | NargoCommand::Login(..)
+        | NargoCommand::Fetch(..)
+        | NargoCommand::Yank(..)
+        | NargoCommand::Unyank(..)
+        | NargoCommand::Checksum(..)
+        | NargoCommand::VerifyChecksum(..)
         | NargoCommand::GenerateCompletionScript(..) => (),
     }
 
-    match command {
+    let result = match command {
         NargoCommand::New(args) => new_cmd::run(args, config),
         NargoCommand::Init(args) => init_cmd::run(args, config),
         NargoCommand::Check(args) => check_cmd::run(args, config),
@@ -109,13 +255,41 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::Debug(args) => debug_cmd::run(args, config),
         NargoCommand::Execute(args) => execute_cmd::run(args, config),
         NargoCommand::Export(args) => export_cmd::run(args, config),
+        NargoCommand::ExtractAcir(args) => extract_acir_cmd::run(args, config),
         NargoCommand::Test(args) => test_cmd::run(args, config),
+        NargoCommand::Bench(args) => bench_cmd::run(args, config),
         NargoCommand::Info(args) => info_cmd::run(args, config),
+        NargoCommand::Metadata(args) => metadata_cmd::run(args, config),
+        NargoCommand::CircuitInfo(args) => circuit_info_cmd::run(args, config),
+        NargoCommand::Env(args) => env_cmd::run(args),
+        NargoCommand::Checksum(args) => checksum_cmd::run(args, config),
+        NargoCommand::VerifyChecksum(args) => verify_checksum_cmd::run(args, config),
         NargoCommand::Lsp(args) => lsp_cmd::run(args, config),
+        NargoCommand::Vendor(args) => vendor_cmd::run(args, config),
+        NargoCommand::Package(args) => package_cmd::run(args, config),
+        NargoCommand::Add(args) => add_cmd::run(args, config),
+        NargoCommand::Remove(args) => remove_cmd::run(args, config),
+        NargoCommand::Update(args) => update_cmd::run(args, config),
+        NargoCommand::Search(args) => search_cmd::run(args, config),
+        NargoCommand::Login(args) => login_cmd::run(args, config),
+        NargoCommand::Fetch(args) => fetch_cmd::run(args, config),
+        NargoCommand::Yank(args) => yank_cmd::run(args, config),
+        NargoCommand::Unyank(args) => yank_cmd::run_unyank(args, config),
+        NargoCommand::UnusedDeps(args) => unused_deps_cmd::run(args, config),
+        NargoCommand::PublicApi(args) => public_api_cmd::run(args, config),
         NargoCommand::Dap(args) => dap_cmd::run(args, config),
         NargoCommand::Fmt(args) => fmt_cmd::run(args, config),
         NargoCommand::GenerateCompletionScript(args) => generate_completion_script_cmd::run(args),
-    }?;
+    };
+
+    if json_output() {
+        let status = match &result {
+            Ok(()) => JsonStatus { status: "success", error: None },
+            Err(error) => JsonStatus { status: "failure", error: Some(error.to_string()) },
+        };
+        println!("{}", serde_json::to_string(&status).unwrap());
+    }
+    result?;
 
     Ok(())
 }
@@ -130,6 +304,19 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
 #[cfg(test)]
 mod tests {
     use clap::Parser;
+
+    #[test]
+    fn test_parse_short_optimization_level() {
+        let cmd = "nargo --program-dir . compile -O1";
+        let res = super::NargoCli::try_parse_from(cmd.split_ascii_whitespace());
+        assert!(res.is_ok(), "should parse `-O1` as an optimization level");
+
+        let cmd = "nargo --program-dir . compile -O3";
+        let res = super::NargoCli::try_parse_from(cmd.split_ascii_whitespace());
+        let err = res.expect_err("should fail because of invalid optimization level");
+        assert!(err.to_string().contains("opt-level"));
+    }
+
     #[test]
     fn test_parse_invalid_expression_width() {
         let cmd = "nargo --program-dir . compile --expression-width 1";
@@ -141,4 +328,16 @@ mod tests {
             .to_string()
             .contains(acvm::compiler::MIN_EXPRESSION_WIDTH.to_string().as_str()));
     }
+
+    #[test]
+    fn test_parse_registry_only_for_registry_commands() {
+        let cmd = "nargo search foo --registry https://registry.test --registry-retries 0";
+        let res = super::NargoCli::try_parse_from(cmd.split_ascii_whitespace());
+        assert!(res.is_ok(), "should accept `--registry` for a registry command");
+
+        let cmd = "nargo compile --registry https://registry.test";
+        let res = super::NargoCli::try_parse_from(cmd.split_ascii_whitespace());
+        let err = res.expect_err("should fail because `compile` doesn't use the registry");
+        assert!(err.to_string().contains("--registry"));
+    }
 }
@@ -1,6 +1,9 @@
 use crate::errors::CliError;
 
-use super::{init_cmd::initialize_project, NargoConfig};
+use super::{
+    init_cmd::{initialize_project, BinaryExample},
+    NargoConfig,
+};
 use clap::Args;
 use nargo::package::{CrateName, PackageType};
 use std::path::PathBuf;
@@ -26,6 +29,14 @@ pub(crate) struct NewCommand {
     /// Use a contract template
     #[arg(long, conflicts_with = "lib", conflicts_with = "bin")]
     pub(crate) contract: bool,
+
+    /// Author of the package, may be repeated [default: `user.name <user.email>` from git config]
+    #[arg(long)]
+    pub(crate) author: Vec<String>,
+
+    /// The example program to start a binary package from
+    #[arg(long, value_enum, default_value_t, conflicts_with = "lib", conflicts_with = "contract")]
+    pub(crate) example: BinaryExample,
 }
 
 pub(crate) fn run(args: NewCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -49,6 +60,6 @@ pub(crate) fn run(args: NewCommand, config: NargoConfig) -> Result<(), CliError>
     } else {
         PackageType::Binary
     };
-    initialize_project(package_dir, package_name, package_type);
+    initialize_project(package_dir, package_name, package_type, args.author, args.example);
     Ok(())
 }
@@ -0,0 +1,235 @@
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use nargo::package::{CrateName, Package};
+use nargo::workspace::Workspace;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use serde::Serialize;
+
+use crate::errors::CliError;
+
+use super::checksum_cmd::{artifact_checksum, default_checksum_path};
+use super::fs::write_to_file;
+use super::NargoConfig;
+
+/// The name of the file within a packed package which lists the package's files.
+const PACKAGE_MANIFEST: &str = "Package.json";
+
+/// Packs the sources of a package into a compressed archive in `target/package`
+#[derive(Debug, Clone, Args)]
+pub(crate) struct PackageCommand {
+    /// The name of the package to pack
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Pack all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageManifest {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// The paths of the packed files, relative to the package's root directory.
+    files: Vec<PathBuf>,
+}
+
+pub(crate) fn run(args: PackageCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+    )?;
+
+    for package in &workspace {
+        let files = collect_package_files(package).map_err(|err| {
+            CliError::Generic(format!("Failed to collect the files of `{}`: {err}", package.name))
+        })?;
+
+        let archive_path = pack_package(&workspace, package, files)?;
+        human_println!("[{}] Packed to {}", package.name, archive_path.display());
+
+        // The checksum is saved next to the archive, in the same format as `nargo checksum`,
+        // so that the archive can be checked with `nargo verify-checksum` once uploaded.
+        let checksum = artifact_checksum(&archive_path)?;
+        let file_name = archive_path.file_name().unwrap_or_default().to_string_lossy();
+        write_to_file(
+            format!("{checksum}  {file_name}\n").as_bytes(),
+            &default_checksum_path(&archive_path),
+        );
+        human_println!("[{}] SHA-256: {checksum}", package.name);
+    }
+
+    Ok(())
+}
+
+fn package_archive_path(workspace: &Workspace, package: &Package) -> PathBuf {
+    workspace.target_directory_path().join("package").join(String::from(&package.name))
+}
+
+/// Writes a zstd compressed tarball of `files` along with the package's `Nargo.toml` and a manifest listing them.
+fn pack_package(
+    workspace: &Workspace,
+    package: &Package,
+    files: Vec<PathBuf>,
+) -> Result<PathBuf, CliError> {
+    let archive_path = package_archive_path(workspace, package);
+    let write_error = |err: std::io::Error| {
+        CliError::Generic(format!("Failed to write {}: {err}", archive_path.display()))
+    };
+
+    let manifest = PackageManifest {
+        name: package.name.to_string(),
+        version: package.version.clone(),
+        files: files.clone(),
+    };
+    let manifest_json =
+        serde_json::to_vec_pretty(&manifest).expect("package manifest should be serializable");
+
+    if let Some(parent) = archive_path.parent() {
+        std::fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let archive_file = std::fs::File::create(&archive_path).map_err(write_error)?;
+    let encoder = zstd::Encoder::new(archive_file, 0).map_err(write_error)?;
+    let mut builder = tar::Builder::new(encoder);
+    builder.mode(tar::HeaderMode::Deterministic);
+
+    // `Nargo.toml` is always the first entry, whether or not `include` mentions it.
+    builder
+        .append_path_with_name(package.root_dir.join("Nargo.toml"), "Nargo.toml")
+        .map_err(write_error)?;
+    for file in &files {
+        builder.append_path_with_name(package.root_dir.join(file), file).map_err(write_error)?;
+    }
+
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, PACKAGE_MANIFEST, manifest_json.as_slice())
+        .map_err(write_error)?;
+
+    let encoder = builder.into_inner().map_err(write_error)?;
+    encoder.finish().map_err(write_error)?;
+
+    Ok(archive_path)
+}
+
+/// Returns the paths, relative to the package's root directory, of the files to pack,
+/// honoring the `include` and `exclude` lists of its manifest.
+///
+/// Build artifacts, git metadata and the manifest itself, which is always packed, are left out.
+fn collect_package_files(package: &Package) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    collect_files(&package.root_dir, Path::new(""), &mut files)?;
+
+    files.retain(|file| {
+        let included = package
+            .include
+            .as_ref()
+            .map_or(true, |include| include.iter().any(|path| file.starts_with(path)));
+        let excluded = package.exclude.iter().any(|path| file.starts_with(path));
+        included && !excluded && file != Path::new("Nargo.toml")
+    });
+    Ok(files)
+}
+
+fn collect_files(
+    root_dir: &Path,
+    relative_dir: &Path,
+    files: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    let mut entries =
+        std::fs::read_dir(root_dir.join(relative_dir))?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let file_name = entry.file_name();
+        if file_name == ".git" || file_name == "target" {
+            continue;
+        }
+
+        let path = relative_dir.join(&file_name);
+        if entry.file_type()?.is_dir() {
+            collect_files(root_dir, &path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use nargo::package::Package;
+    use nargo_toml::{resolve_workspace_from_toml, PackageSelection};
+
+    use super::collect_package_files;
+
+    fn package_with_manifest(root_dir: &std::path::Path, manifest: &str) -> Package {
+        for file in ["src/main.nr", "src/utils.nr", "tests/test.nr", "Prover.toml", "target/a.json"]
+        {
+            let path = root_dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        std::fs::write(root_dir.join("Nargo.toml"), manifest).unwrap();
+
+        let workspace =
+            resolve_workspace_from_toml(&root_dir.join("Nargo.toml"), PackageSelection::All, None)
+                .unwrap();
+        workspace.members[0].clone()
+    }
+
+    #[test]
+    fn honors_include_and_exclude() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = package_with_manifest(
+            dir.path(),
+            r#"
+            [package]
+            name = "foo"
+            type = "bin"
+            include = ["src", "tests"]
+            exclude = ["src/utils.nr"]
+            "#,
+        );
+
+        let files = collect_package_files(&package).unwrap();
+        assert_eq!(files, vec![PathBuf::from("src/main.nr"), PathBuf::from("tests/test.nr")]);
+    }
+
+    #[test]
+    fn skips_build_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = package_with_manifest(
+            dir.path(),
+            r#"
+            [package]
+            name = "foo"
+            type = "bin"
+            "#,
+        );
+
+        let files = collect_package_files(&package).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("Prover.toml"),
+                PathBuf::from("src/main.nr"),
+                PathBuf::from("src/utils.nr"),
+                PathBuf::from("tests/test.nr"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use clap::Args;
+use nargo::{
+    insert_all_files_for_workspace_into_file_manager, package::CrateName, parse_all,
+    prepare_package,
+};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use noirc_frontend::{
+    ast::ItemVisibility, graph::CrateId, hir::Context, hir_def::stmt::HirPattern,
+    node_interner::FuncId, Type,
+};
+use serde::Serialize;
+
+use crate::errors::CliError;
+
+use super::check_cmd::check_crate_and_report_errors;
+use super::{json_output, NargoConfig};
+
+/// Lists the public functions of each package in the workspace, along with their signatures
+#[derive(Debug, Clone, Args)]
+pub(crate) struct PublicApiCommand {
+    /// The name of the package to list
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// List all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// Output a JSON formatted report. Same as `--format json`
+    #[clap(long)]
+    pub(super) json: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+#[derive(Debug, Serialize)]
+struct PackagePublicApi {
+    package_name: String,
+    functions: Vec<PublicFunction>,
+}
+
+#[derive(Debug, Serialize)]
+struct PublicFunction {
+    path: String,
+    signature: String,
+}
+
+pub(crate) fn run(args: PublicApiCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    let mut workspace_file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
+    let mut reports = Vec::new();
+    for package in &workspace {
+        let (mut context, crate_id) =
+            prepare_package(&workspace_file_manager, &parsed_files, package);
+        check_crate_and_report_errors(&mut context, crate_id, &args.compile_options)?;
+
+        reports.push(PackagePublicApi {
+            package_name: package.name.to_string(),
+            functions: public_functions(&context, crate_id),
+        });
+    }
+
+    if json_output() {
+        println!("{}", serde_json::to_string(&reports).unwrap());
+    } else {
+        for report in reports {
+            human_println!("[{}]", report.package_name);
+            for function in report.functions {
+                human_println!("    {}", function.signature);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the `pub` functions declared directly in any module of the crate, sorted by path.
+///
+/// Methods defined in `impl` blocks aren't module items, so they aren't included.
+fn public_functions(context: &Context, crate_id: CrateId) -> Vec<PublicFunction> {
+    let def_map = context.def_map(&crate_id).expect("The local crate should be analyzed already");
+
+    let mut functions: Vec<PublicFunction> = def_map
+        .modules()
+        .iter()
+        .flat_map(|(_, module)| module.value_definitions())
+        .filter_map(|definition| definition.as_function())
+        .filter(|func_id| {
+            context.def_interner.function_modifiers(func_id).visibility == ItemVisibility::Public
+        })
+        .map(|func_id| {
+            let path = context.fully_qualified_function_name(&crate_id, &func_id);
+            let signature = function_signature(context, func_id, &path);
+            PublicFunction { path, signature }
+        })
+        .collect();
+
+    functions.sort_by(|a, b| a.path.cmp(&b.path));
+    functions
+}
+
+fn function_signature(context: &Context, func_id: FuncId, path: &str) -> String {
+    let interner = &context.def_interner;
+    let meta = interner.function_meta(&func_id);
+    let modifiers = interner.function_modifiers(&func_id);
+
+    let mut signature = String::from("pub ");
+    if modifiers.is_comptime {
+        signature.push_str("comptime ");
+    }
+    if modifiers.is_unconstrained {
+        signature.push_str("unconstrained ");
+    }
+    signature.push_str("fn ");
+    signature.push_str(path);
+
+    if !meta.direct_generics.is_empty() {
+        let generics: Vec<&str> =
+            meta.direct_generics.iter().map(|generic| generic.name.as_str()).collect();
+        signature.push_str(&format!("<{}>", generics.join(", ")));
+    }
+
+    let parameters: Vec<String> = meta
+        .parameters
+        .0
+        .iter()
+        .map(|(pattern, typ, _)| format!("{}: {typ}", pattern_name(context, pattern)))
+        .collect();
+    signature.push_str(&format!("({})", parameters.join(", ")));
+
+    let return_type = meta.return_type();
+    if *return_type != Type::Unit {
+        signature.push_str(&format!(" -> {return_type}"));
+    }
+
+    signature
+}
+
+fn pattern_name(context: &Context, pattern: &HirPattern) -> String {
+    match pattern {
+        HirPattern::Identifier(ident) => context.def_interner.definition_name(ident.id).to_owned(),
+        HirPattern::Mutable(pattern, _) => format!("mut {}", pattern_name(context, pattern)),
+        HirPattern::Tuple(patterns, _) => {
+            let names: Vec<String> =
+                patterns.iter().map(|pattern| pattern_name(context, pattern)).collect();
+            format!("({})", names.join(", "))
+        }
+        HirPattern::Struct(typ, fields, _) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, pattern)| {
+                    format!("{}: {}", name.0.contents, pattern_name(context, pattern))
+                })
+                .collect();
+            format!("{typ} {{ {} }}", fields.join(", "))
+        }
+    }
+}
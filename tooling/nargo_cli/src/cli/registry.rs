@@ -0,0 +1,623 @@
+//! A client for the package registry, which is at [DEFAULT_REGISTRY_URL] unless configured otherwise
+//! through `--registry` or the `registry` key of the global config file.
+//!
+//! The registry doesn't host any sources itself: it maps the name and version of each published package
+//! to the git repository and tag which it can be fetched from, which is how dependencies are declared in `Nargo.toml`.
+//! Nargo has no `publish` command for the same reason: there are no sources to upload, as versions are registered
+//! from their git tags. The token stored by `nargo login` authenticates `nargo yank` and `nargo unyank`, which change
+//! which versions are selected, and is sent along with lookups so that private packages can be found.
+//!
+//! Requests are sent through a [Transport], which outside of tests is [Curl]: it runs `curl` in the same way that
+//! git dependencies are fetched by running `git`. Requests which fail with a connection error, a server error
+//! or a rate limit are retried with exponential backoff.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::Args;
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CliError;
+
+pub(super) const DEFAULT_REGISTRY_URL: &str = "https://npkg.walnut.dev";
+
+static REGISTRY_URL: OnceLock<String> = OnceLock::new();
+
+static REGISTRY_RETRIES: AtomicU32 = AtomicU32::new(3);
+
+/// The delay before the first retry of a failed request, which doubles on each further retry.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// The environment variable which a registry token can be given in, taking precedence over `nargo login`.
+const TOKEN_ENV_VAR: &str = "NARGO_REGISTRY_TOKEN";
+
+const CREDENTIALS_FILE: &str = "credentials.toml";
+
+/// The global config file, holding settings which apply to every workspace.
+const CONFIG_FILE: &str = "config.toml";
+
+/// The options of the commands which talk to the package registry.
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RegistryArgs {
+    /// The URL of the package registry, instead of the one set by the `registry` key of `~/.config/nargo/config.toml`
+    /// or the default registry
+    #[arg(long, value_name = "URL")]
+    registry: Option<String>,
+
+    /// How many times to retry a registry request which fails with a connection error or a server error
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    registry_retries: u32,
+}
+
+impl RegistryArgs {
+    /// Configures the registry used for the rest of the process.
+    ///
+    /// This is only done for the commands which use the registry, so that a malformed global config file
+    /// doesn't get in the way of anything else.
+    pub(super) fn configure(&self) -> Result<(), CliError> {
+        set_registry_url(self.registry.clone())?;
+        set_registry_retries(self.registry_retries);
+        Ok(())
+    }
+}
+
+/// A package as described by the registry.
+#[derive(Debug, Deserialize)]
+pub(super) struct RegistryPackage {
+    pub(super) name: String,
+    /// The URL of the git repository which the package is published from.
+    pub(super) repository: String,
+    pub(super) versions: Vec<RegistryVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct RegistryVersion {
+    pub(super) version: String,
+    /// The git tag which this version is published at.
+    pub(super) tag: String,
+    /// The `compiler_version` requirement from the package's manifest at this version.
+    #[serde(default)]
+    pub(super) compiler_version: Option<String>,
+    /// The checksum of the package's sources at this version, computed in the same way as for `Nargo.lock`.
+    #[serde(default)]
+    pub(super) checksum: Option<String>,
+    /// Whether the version has been yanked, so that it's no longer selected for new dependencies.
+    #[serde(default)]
+    pub(super) yanked: bool,
+}
+
+impl RegistryPackage {
+    /// Finds the latest version which satisfies `requirement`, if one is given, and which can be compiled
+    /// by the current compiler. Pre-releases are only selected when `requirement` asks for one,
+    /// and yanked versions are never selected.
+    pub(super) fn latest_compatible_version(
+        &self,
+        requirement: Option<&VersionReq>,
+    ) -> Option<(Version, &RegistryVersion)> {
+        let compiler_version = current_compiler_version();
+        self.versions
+            .iter()
+            .filter(|version| !version.yanked)
+            .filter_map(|version| Some((Version::parse(&version.version).ok()?, version)))
+            .filter(|(version, _)| match requirement {
+                Some(requirement) => requirement.matches(version),
+                None => version.pre.is_empty(),
+            })
+            .filter(|(_, version)| {
+                version.compiler_version.as_deref().map_or(true, |compiler_requirement| {
+                    VersionReq::parse(compiler_requirement)
+                        .map_or(false, |requirement| requirement.matches(&compiler_version))
+                })
+            })
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+    }
+}
+
+/// Fetches the registry's description of the package called `name`.
+pub(super) fn fetch_package(name: &str) -> Result<RegistryPackage, CliError> {
+    fetch_package_with(&Curl, name)
+}
+
+fn fetch_package_with(transport: &impl Transport, name: &str) -> Result<RegistryPackage, CliError> {
+    let registry_url = registry_url();
+    let response = registry_get(transport, &format!("packages/{name}"), &[]).map_err(|err| {
+        CliError::Generic(format!(
+            "Failed to find package `{name}` in the registry at {registry_url}: {err}"
+        ))
+    })?;
+
+    serde_json::from_slice(&response).map_err(|err| {
+        CliError::Generic(format!("The registry returned an invalid response for `{name}`: {err}"))
+    })
+}
+
+/// A package matching a search of the registry.
+#[derive(Debug, Deserialize, Serialize)]
+pub(super) struct SearchResult {
+    pub(super) name: String,
+    /// The latest published version of the package.
+    pub(super) version: String,
+    #[serde(default)]
+    pub(super) description: Option<String>,
+    /// How many times the package has been downloaded, across all versions.
+    #[serde(default)]
+    pub(super) downloads: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    packages: Vec<SearchResult>,
+}
+
+/// Searches the registry for packages matching `query`, returning at most `limit` of them.
+pub(super) fn search_packages(query: &str, limit: usize) -> Result<Vec<SearchResult>, CliError> {
+    let registry_url = registry_url();
+    let response = registry_get(&Curl, "search", &[("q", query), ("limit", &limit.to_string())])
+        .map_err(|err| {
+            CliError::Generic(format!("Failed to search the registry at {registry_url}: {err}"))
+        })?;
+
+    let response: SearchResponse = serde_json::from_slice(&response).map_err(|err| {
+        CliError::Generic(format!("The registry returned an invalid search response: {err}"))
+    })?;
+    Ok(response.packages)
+}
+
+/// Checks that the registry accepts `token`, by requesting the account which it belongs to.
+pub(super) fn validate_token(token: &str) -> Result<(), CliError> {
+    validate_token_with(&Curl, token)
+}
+
+fn validate_token_with(transport: &impl Transport, token: &str) -> Result<(), CliError> {
+    let registry_url = registry_url();
+    let response = registry_request(transport, "GET", "me", &[], Some(token)).map_err(|err| {
+        CliError::Generic(format!("Failed to reach the registry at {registry_url}: {err}"))
+    })?;
+    match response.status {
+        200..=299 => Ok(()),
+        401 | 403 => Err(CliError::RegistryAuthenticationFailed(registry_url.to_string())),
+        status => Err(CliError::Generic(format!(
+            "The registry at {registry_url} responded with status {status} when checking the token"
+        ))),
+    }
+}
+
+/// Marks `version` of the package called `name` as yanked, or as no longer yanked if `yanked` is false.
+/// The request is authenticated with `token`, or else with the token from [registry_token].
+///
+/// Dependencies which already resolved to a yanked version keep working, as they're pinned
+/// to a git tag rather than resolved through the registry again.
+pub(super) fn set_yanked(
+    name: &str,
+    version: &str,
+    yanked: bool,
+    token: Option<String>,
+) -> Result<(), CliError> {
+    set_yanked_with(&Curl, name, version, yanked, token)
+}
+
+fn set_yanked_with(
+    transport: &impl Transport,
+    name: &str,
+    version: &str,
+    yanked: bool,
+    token: Option<String>,
+) -> Result<(), CliError> {
+    let registry_url = registry_url();
+    let Some(token) = token.or_else(registry_token) else {
+        return Err(CliError::Generic(format!(
+            "Yanking requires authenticating with the registry. Run `nargo login` or set `{TOKEN_ENV_VAR}`"
+        )));
+    };
+    let (method, action) = if yanked { ("DELETE", "yank") } else { ("PUT", "unyank") };
+    let path = format!("packages/{name}/{version}/yank");
+    let response =
+        registry_request(transport, method, &path, &[], Some(&token)).map_err(|err| {
+            CliError::Generic(format!("Failed to reach the registry at {registry_url}: {err}"))
+        })?;
+    match response.status {
+        200..=299 => Ok(()),
+        401 | 403 => Err(CliError::RegistryAuthenticationFailed(registry_url.to_string())),
+        404 => Err(CliError::Generic(format!(
+            "The registry at {registry_url} has no version {version} of `{name}`"
+        ))),
+        status => Err(CliError::Generic(format!(
+            "Failed to {action} `{name}` {version}: the registry responded with status {status}"
+        ))),
+    }
+}
+
+/// The directory holding nargo's global config file and the credentials stored by `nargo login`.
+fn config_dir() -> PathBuf {
+    dirs::home_dir().unwrap_or_default().join(".config").join("nargo")
+}
+
+/// The path of the file which `nargo login` stores the registry token in.
+pub(super) fn credentials_path() -> PathBuf {
+    config_dir().join(CREDENTIALS_FILE)
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct GlobalConfig {
+    /// The URL of the registry to use instead of [DEFAULT_REGISTRY_URL].
+    registry: Option<String>,
+}
+
+/// Sets the URL of the registry used for the rest of the process: `url` if one is given, or else the `registry`
+/// key of the global config file, or else [DEFAULT_REGISTRY_URL].
+///
+/// Errors if the URL isn't a well-formed HTTP(S) URL, or if the global config file can't be parsed.
+fn set_registry_url(url: Option<String>) -> Result<(), CliError> {
+    let url = match url {
+        Some(url) => url,
+        None => {
+            let config_path = config_dir().join(CONFIG_FILE);
+            let config = match std::fs::read_to_string(&config_path) {
+                Ok(contents) => toml::from_str::<GlobalConfig>(&contents).map_err(|err| {
+                    CliError::Generic(format!("Failed to parse {}: {err}", config_path.display()))
+                })?,
+                Err(_) => GlobalConfig::default(),
+            };
+            config.registry.unwrap_or_else(|| DEFAULT_REGISTRY_URL.to_string())
+        }
+    };
+
+    let parsed = url::Url::parse(&url)
+        .map_err(|err| CliError::Generic(format!("Invalid registry URL `{url}`: {err}")))?;
+    if !matches!(parsed.scheme(), "http" | "https") || !parsed.has_host() {
+        return Err(CliError::Generic(format!(
+            "Invalid registry URL `{url}`: expected an http or https URL with a host"
+        )));
+    }
+
+    // Paths within the API are joined onto the URL, so any trailing slash is dropped.
+    let url = url.trim_end_matches('/').to_string();
+    REGISTRY_URL.set(url).expect("the registry URL is only set once");
+    Ok(())
+}
+
+/// The URL of the registry, as set by [set_registry_url].
+pub(super) fn registry_url() -> &'static str {
+    REGISTRY_URL.get().map_or(DEFAULT_REGISTRY_URL, String::as_str)
+}
+
+/// Sets how many times a registry request which fails transiently is retried for the rest of the process.
+fn set_registry_retries(retries: u32) {
+    REGISTRY_RETRIES.store(retries, Ordering::Relaxed);
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Credentials {
+    token: String,
+}
+
+/// The token to authenticate with the registry with, taken from the `NARGO_REGISTRY_TOKEN`
+/// environment variable or else from the credentials stored by `nargo login`.
+pub(super) fn registry_token() -> Option<String> {
+    if let Ok(token) = std::env::var(TOKEN_ENV_VAR) {
+        return Some(token).filter(|token| !token.is_empty());
+    }
+    let contents = std::fs::read_to_string(credentials_path()).ok()?;
+    toml::from_str::<Credentials>(&contents).ok().map(|credentials| credentials.token)
+}
+
+/// Stores `token` for later commands, readable only by the current user.
+pub(super) fn save_registry_token(token: &str) -> Result<PathBuf, CliError> {
+    let path = credentials_path();
+    let write_error = |err: std::io::Error| {
+        CliError::Generic(format!("Failed to write {}: {err}", path.display()))
+    };
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(write_error)?;
+    }
+    let contents = toml::to_string(&Credentials { token: token.to_string() })
+        .expect("credentials are always serializable");
+    std::fs::write(&path, contents).map_err(write_error)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+            .map_err(write_error)?;
+    }
+    Ok(path)
+}
+
+/// Sends a GET request for `path` within the registry's API, with `query` URL-encoded into the query string,
+/// and returns the body of a successful response. The request is authenticated if a token is configured.
+fn registry_get(
+    transport: &impl Transport,
+    path: &str,
+    query: &[(&str, &str)],
+) -> Result<Vec<u8>, String> {
+    let response = registry_request(transport, "GET", path, query, registry_token().as_deref())?;
+    if !(200..300).contains(&response.status) {
+        return Err(format!("the registry responded with status {}", response.status));
+    }
+    Ok(response.body)
+}
+
+#[derive(Debug)]
+struct RegistryResponse {
+    status: u16,
+    body: Vec<u8>,
+}
+
+/// Sends single requests to the registry, and waits between the retries of a failed request.
+trait Transport {
+    /// Sends a single `method` request to `url`, authenticated with `token` if one is given.
+    fn send(
+        &self,
+        method: &str,
+        url: &str,
+        token: Option<&str>,
+    ) -> Result<RegistryResponse, RequestError>;
+
+    /// Waits for `duration` before a failed request is retried.
+    fn wait(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Sends a `method` request for `path` within the registry's API, authenticated with `token` if one is given.
+///
+/// Connection errors, server errors and `429 Too Many Requests` responses are retried up to the number of times
+/// set by [set_registry_retries], waiting twice as long before each retry plus some jitter. Other client errors
+/// are returned straight away, as retrying them wouldn't change the outcome.
+fn registry_request(
+    transport: &impl Transport,
+    method: &str,
+    path: &str,
+    query: &[(&str, &str)],
+    token: Option<&str>,
+) -> Result<RegistryResponse, String> {
+    let url = request_url(registry_url(), path, query);
+    let retries = REGISTRY_RETRIES.load(Ordering::Relaxed);
+    let mut delay = INITIAL_RETRY_DELAY;
+    for attempt in 1.. {
+        let failure = match transport.send(method, &url, token) {
+            Ok(response) if response.status == 429 || response.status >= 500 => {
+                format!("the registry responded with status {}", response.status)
+            }
+            Err(RequestError::Connection(error)) => error,
+            result => return result.map_err(RequestError::into_message),
+        };
+        if attempt > retries {
+            return Err(failure);
+        }
+
+        let delay_with_jitter = delay + jitter(delay / 2);
+        eprintln!(
+            "Registry request failed ({failure}), retrying in {:.1}s ({attempt}/{retries})",
+            delay_with_jitter.as_secs_f64()
+        );
+        transport.wait(delay_with_jitter);
+        delay *= 2;
+    }
+    unreachable!("requests are retried a bounded number of times")
+}
+
+/// The URL of `path` within the API of the registry at `registry_url`, with `query` URL-encoded into the query string.
+fn request_url(registry_url: &str, path: &str, query: &[(&str, &str)]) -> String {
+    let url = format!("{registry_url}/api/v1/{path}");
+    if query.is_empty() {
+        return url;
+    }
+    url::Url::parse_with_params(&url, query).map_or(url, String::from)
+}
+
+/// A random duration of at most `max`, so that clients which failed together don't all retry together.
+fn jitter(max: Duration) -> Duration {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().subsec_nanos();
+    max.mul_f64(f64::from(nanos) / 1e9)
+}
+
+enum RequestError {
+    /// The request couldn't be sent, such as when `curl` couldn't be run.
+    Spawn(String),
+    /// The request was sent but didn't complete, such as when the registry can't be reached.
+    Connection(String),
+}
+
+impl RequestError {
+    fn into_message(self) -> String {
+        match self {
+            RequestError::Spawn(message) | RequestError::Connection(message) => message,
+        }
+    }
+}
+
+/// Sends requests by running `curl`.
+struct Curl;
+
+impl Transport for Curl {
+    /// The token is passed to `curl` through stdin rather than as an argument, so that it isn't visible to other processes.
+    fn send(
+        &self,
+        method: &str,
+        url: &str,
+        token: Option<&str>,
+    ) -> Result<RegistryResponse, RequestError> {
+        let mut command = Command::new("curl");
+        command.args(["--silent", "--show-error", "--location", "--write-out", "\n%{http_code}"]);
+        command.args(["--request", method]);
+        if token.is_some() {
+            command.args(["--header", "@-"]).stdin(Stdio::piped());
+        }
+        command.arg(url).stdout(Stdio::piped()).stderr(Stdio::piped());
+
+        let spawn_error = |err: std::io::Error| {
+            RequestError::Spawn(format!("failed to run `curl` to query {url}: {err}"))
+        };
+        let mut child = command.spawn().map_err(spawn_error)?;
+        if let (Some(token), Some(mut stdin)) = (token, child.stdin.take()) {
+            writeln!(stdin, "Authorization: Bearer {token}").map_err(|err| {
+                RequestError::Spawn(format!("failed to pass the token to `curl`: {err}"))
+            })?;
+        }
+        let output = child.wait_with_output().map_err(spawn_error)?;
+
+        if !output.status.success() {
+            return Err(RequestError::Connection(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        // The status code is written on its own line after the body.
+        let mut body = output.stdout;
+        let status_start = body.iter().rposition(|byte| *byte == b'\n').unwrap_or(0);
+        let status = String::from_utf8_lossy(&body[status_start..]).trim().parse().unwrap_or(0);
+        body.truncate(status_start);
+        Ok(RegistryResponse { status, body })
+    }
+}
+
+/// The version of the running compiler, without any pre-release or build metadata,
+/// so that it's matched against requirements in the same way as in `Nargo.toml`.
+fn current_compiler_version() -> Version {
+    let mut version = Version::parse(NOIR_ARTIFACT_VERSION_STRING)
+        .expect("The compiler version is not a valid semver version");
+    version.pre = semver::Prerelease::EMPTY;
+    version.build = semver::BuildMetadata::EMPTY;
+    version
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::time::Duration;
+
+    use crate::errors::CliError;
+
+    use super::{
+        fetch_package_with, registry_request, request_url, set_yanked_with, validate_token_with,
+        RegistryResponse, RequestError, Transport, DEFAULT_REGISTRY_URL, INITIAL_RETRY_DELAY,
+    };
+
+    /// Answers requests with canned responses, recording the requests and the waits between them.
+    #[derive(Default)]
+    struct StubTransport {
+        responses: RefCell<VecDeque<Result<RegistryResponse, RequestError>>>,
+        requests: RefCell<Vec<(String, String)>>,
+        waits: RefCell<Vec<Duration>>,
+    }
+
+    impl StubTransport {
+        fn new(statuses: impl IntoIterator<Item = Result<u16, RequestError>>) -> Self {
+            let responses = statuses
+                .into_iter()
+                .map(|status| status.map(|status| RegistryResponse { status, body: Vec::new() }))
+                .collect();
+            StubTransport { responses: RefCell::new(responses), ..Default::default() }
+        }
+    }
+
+    impl Transport for StubTransport {
+        fn send(
+            &self,
+            method: &str,
+            url: &str,
+            _token: Option<&str>,
+        ) -> Result<RegistryResponse, RequestError> {
+            self.requests.borrow_mut().push((method.to_string(), url.to_string()));
+            self.responses.borrow_mut().pop_front().expect("unexpected request")
+        }
+
+        fn wait(&self, duration: Duration) {
+            self.waits.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn query_is_url_encoded() {
+        assert_eq!(
+            request_url("https://registry.test", "search", &[("q", "hash & sign"), ("limit", "5")]),
+            "https://registry.test/api/v1/search?q=hash+%26+sign&limit=5"
+        );
+        assert_eq!(
+            request_url("https://registry.test", "packages/foo", &[]),
+            "https://registry.test/api/v1/packages/foo"
+        );
+    }
+
+    #[test]
+    fn transient_failures_are_retried_with_backoff() {
+        let transport = StubTransport::new([
+            Err(RequestError::Connection("connection refused".to_string())),
+            Ok(503),
+            Ok(429),
+            Ok(200),
+        ]);
+
+        let response = registry_request(&transport, "GET", "me", &[], None).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(transport.requests.borrow().len(), 4);
+
+        let waits = transport.waits.borrow();
+        assert_eq!(waits.len(), 3);
+        let mut delay = INITIAL_RETRY_DELAY;
+        for wait in waits.iter() {
+            assert!(*wait >= delay && *wait <= delay + delay / 2, "{wait:?} for {delay:?}");
+            delay *= 2;
+        }
+    }
+
+    #[test]
+    fn retries_are_bounded() {
+        let transport = StubTransport::new([Ok(500), Ok(502), Ok(503), Ok(504)]);
+
+        let error = registry_request(&transport, "GET", "me", &[], None).unwrap_err();
+        assert_eq!(error, "the registry responded with status 504");
+        assert_eq!(transport.requests.borrow().len(), 4);
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        let transport = StubTransport::new([Ok(404)]);
+        let response = registry_request(&transport, "GET", "me", &[], None).unwrap();
+        assert_eq!(response.status, 404);
+
+        let transport = StubTransport::new([Err(RequestError::Spawn("no curl".to_string()))]);
+        let error = registry_request(&transport, "GET", "me", &[], None).unwrap_err();
+        assert_eq!(error, "no curl");
+        assert!(transport.waits.borrow().is_empty());
+    }
+
+    #[test]
+    fn rejected_token_is_an_authentication_error() {
+        let transport = StubTransport::new([Ok(401)]);
+        let error = validate_token_with(&transport, "token").unwrap_err();
+        assert!(matches!(error, CliError::RegistryAuthenticationFailed(_)));
+    }
+
+    #[test]
+    fn yanking_sends_the_version_to_yank() {
+        let transport = StubTransport::new([Ok(200), Ok(404)]);
+        set_yanked_with(&transport, "foo", "1.0.0", true, Some("token".to_string())).unwrap();
+        let error = set_yanked_with(&transport, "foo", "2.0.0", false, Some("token".to_string()))
+            .unwrap_err();
+
+        assert!(error.to_string().contains("has no version 2.0.0 of `foo`"), "{error}");
+        let requests = transport.requests.borrow();
+        let yank_url = format!("{DEFAULT_REGISTRY_URL}/api/v1/packages/foo/1.0.0/yank");
+        let unyank_url = format!("{DEFAULT_REGISTRY_URL}/api/v1/packages/foo/2.0.0/yank");
+        assert_eq!(
+            *requests,
+            vec![("DELETE".to_string(), yank_url), ("PUT".to_string(), unyank_url)]
+        );
+    }
+
+    #[test]
+    fn missing_package_is_reported() {
+        let transport = StubTransport::new([Ok(404)]);
+        let error = fetch_package_with(&transport, "foo").unwrap_err();
+        assert!(error.to_string().contains("Failed to find package `foo`"), "{error}");
+    }
+}
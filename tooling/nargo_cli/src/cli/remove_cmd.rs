@@ -0,0 +1,36 @@
+use clap::Args;
+use nargo_toml::get_package_manifest;
+
+use crate::errors::CliError;
+
+use super::fs::manifest::{dependencies_table, read_manifest_document, write_manifest_document};
+use super::NargoConfig;
+
+/// Remove dependencies from the package's Nargo.toml
+#[derive(Debug, Clone, Args)]
+pub(crate) struct RemoveCommand {
+    /// The names of the dependencies to remove
+    #[clap(required = true)]
+    dependencies: Vec<String>,
+}
+
+pub(crate) fn run(args: RemoveCommand, config: NargoConfig) -> Result<(), CliError> {
+    let manifest_path = get_package_manifest(&config.program_dir)?;
+    let mut document = read_manifest_document(&manifest_path)?;
+    let dependencies = dependencies_table(&manifest_path, &mut document)?;
+
+    // Check every dependency before removing any, so that a typo doesn't leave the manifest half-edited.
+    if let Some(missing) = args.dependencies.iter().find(|name| !dependencies.contains_key(name)) {
+        return Err(CliError::Generic(format!(
+            "The dependency `{missing}` could not be found in {}",
+            manifest_path.display()
+        )));
+    }
+
+    for name in &args.dependencies {
+        dependencies.remove(name);
+        human_println!("Removing `{name}` from {}", manifest_path.display());
+    }
+
+    write_manifest_document(&manifest_path, &document)
+}
@@ -0,0 +1,53 @@
+use clap::Args;
+use prettytable::{row, table};
+
+use crate::errors::CliError;
+
+use super::registry::{search_packages, RegistryArgs};
+use super::{json_output, NargoConfig};
+
+/// Search the package registry for packages
+#[derive(Debug, Clone, Args)]
+pub(crate) struct SearchCommand {
+    /// The text to search package names and descriptions for
+    query: String,
+
+    /// The maximum number of packages to list
+    #[clap(long, default_value_t = 10)]
+    limit: usize,
+
+    /// Output the matching packages as JSON. Same as `--format json`
+    #[clap(long)]
+    pub(super) json: bool,
+
+    #[clap(flatten)]
+    pub(super) registry: RegistryArgs,
+}
+
+pub(crate) fn run(args: SearchCommand, _config: NargoConfig) -> Result<(), CliError> {
+    let packages = search_packages(&args.query, args.limit)?;
+
+    if json_output() {
+        println!("{}", serde_json::to_string(&packages).unwrap());
+        return Ok(());
+    }
+
+    if packages.is_empty() {
+        human_println!("No packages found matching `{}`", args.query);
+        return Ok(());
+    }
+
+    let mut table = table!([Fm->"Package", Fm->"Version", Fm->"Downloads", Fm->"Description"]);
+    for package in packages {
+        let downloads = package.downloads.map(|downloads| downloads.to_string());
+        table.add_row(row![
+            Fc->package.name,
+            package.version,
+            downloads.unwrap_or_default(),
+            package.description.unwrap_or_default()
+        ]);
+    }
+    table.printstd();
+
+    Ok(())
+}
@@ -9,16 +9,20 @@ use nargo::{
     ops::TestStatus,
     package::{CrateName, Package},
     parse_all, prepare_package,
+    workspace::Workspace,
 };
-use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_driver::{check_crate, CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+use nargo_toml::{
+    check_lock_file, get_package_manifest, resolve_workspace_from_toml, PackageSelection,
+};
+use noirc_driver::{check_crate, CompileOptions, MessageFormat, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_frontend::hir::{FunctionNameMatch, ParsedFiles};
 use rayon::prelude::{IntoParallelIterator, ParallelBridge, ParallelIterator};
+use serde::Serialize;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use crate::{cli::check_cmd::check_crate_and_report_errors, errors::CliError};
 
-use super::NargoConfig;
+use super::{json_output, NargoConfig};
 
 /// Run the tests for this program
 #[derive(Debug, Clone, Args)]
@@ -35,6 +39,10 @@ pub(crate) struct TestCommand {
     #[clap(long)]
     exact: bool,
 
+    /// Don't run tests whose names contain this string. May be given more than once.
+    #[clap(long, value_name = "PATTERN")]
+    skip: Vec<String>,
+
     /// The name of the package to test
     #[clap(long, conflicts_with = "workspace")]
     package: Option<CrateName>,
@@ -49,9 +57,21 @@ pub(crate) struct TestCommand {
     /// JSON RPC url to solve oracle calls
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    /// Number of tests to run in parallel. Defaults to the number of logical CPUs.
+    #[clap(long, short = 'j', value_name = "N", conflicts_with = "no_parallel")]
+    jobs: Option<usize>,
+
+    /// Run tests one at a time
+    #[clap(long)]
+    no_parallel: bool,
 }
 
-pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError> {
+pub(crate) fn run(mut args: TestCommand, config: NargoConfig) -> Result<(), CliError> {
+    if json_output() {
+        args.compile_options.message_format = MessageFormat::Json;
+    }
+
     let toml_path = get_package_manifest(&config.program_dir)?;
     let default_selection =
         if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
@@ -61,10 +81,7 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
         selection,
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
     )?;
-
-    let mut workspace_file_manager = workspace.new_file_manager();
-    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
-    let parsed_files = parse_all(&workspace_file_manager);
+    check_lock_file(&workspace)?;
 
     let pattern = match &args.test_name {
         Some(name) => {
@@ -77,9 +94,49 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
         None => FunctionNameMatch::Anything,
     };
 
+    let jobs = if args.no_parallel { Some(1) } else { args.jobs };
+
+    run_workspace_tests(
+        &workspace,
+        pattern,
+        &args.skip,
+        args.show_output,
+        args.oracle_resolver.as_deref(),
+        jobs,
+        &args.compile_options,
+    )
+}
+
+/// Runs the tests matching `pattern` in each selected package of the workspace, printing a report for each package.
+/// Tests whose names contain any of the `skip` patterns aren't run.
+/// Up to `jobs` tests are run at once, defaulting to the number of logical CPUs.
+///
+/// Errors if any test fails, or if a specific test was requested but no tests matched it.
+pub(super) fn run_workspace_tests(
+    workspace: &Workspace,
+    pattern: FunctionNameMatch,
+    skip: &[String],
+    show_output: bool,
+    oracle_resolver: Option<&str>,
+    jobs: Option<usize>,
+    compile_options: &CompileOptions,
+) -> Result<(), CliError> {
+    if jobs == Some(0) {
+        return Err(CliError::Generic("The number of jobs must be at least 1".to_string()));
+    }
+
+    let mut workspace_file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager(workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
     // Configure a thread pool with a larger stack size to prevent overflowing stack in large programs.
     // Default is 2MB.
-    let pool = rayon::ThreadPoolBuilder::new().stack_size(4 * 1024 * 1024).build().unwrap();
+    // A thread count of 0 lets rayon use one thread per logical CPU.
+    let pool = rayon::ThreadPoolBuilder::new()
+        .stack_size(4 * 1024 * 1024)
+        .num_threads(jobs.unwrap_or(0))
+        .build()
+        .unwrap();
     let test_reports: Vec<Vec<(String, TestStatus)>> = pool.install(|| {
         workspace
             .into_iter()
@@ -90,17 +147,34 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
                     &parsed_files,
                     package,
                     pattern,
-                    args.show_output,
-                    args.oracle_resolver.as_deref(),
+                    skip,
+                    show_output,
+                    oracle_resolver,
                     Some(workspace.root_dir.clone()),
                     Some(package.name.to_string()),
-                    &args.compile_options,
+                    compile_options,
                 )
             })
             .collect::<Result<_, _>>()
     })?;
     let test_report: Vec<(String, TestStatus)> = test_reports.into_iter().flatten().collect();
 
+    if json_output() {
+        let results: Vec<TestResult> = test_report
+            .iter()
+            .map(|(name, status)| {
+                let status = match status {
+                    TestStatus::Pass => "pass",
+                    TestStatus::Fail { .. } => "fail",
+                    TestStatus::Skipped => "skipped",
+                    TestStatus::CompileError(_) => "compile_error",
+                };
+                TestResult { name, status }
+            })
+            .collect();
+        println!("{}", serde_json::to_string(&TestReport { tests: results }).unwrap());
+    }
+
     if test_report.is_empty() {
         match &pattern {
             FunctionNameMatch::Exact(pattern) => {
@@ -123,25 +197,46 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
     }
 }
 
+/// The results of the tests which were run, as written to stdout with `--format json`.
+#[derive(Debug, Serialize)]
+struct TestReport<'a> {
+    tests: Vec<TestResult<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct TestResult<'a> {
+    name: &'a str,
+    /// One of `pass`, `fail`, `skipped` or `compile_error`.
+    status: &'static str,
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default>(
     file_manager: &FileManager,
     parsed_files: &ParsedFiles,
     package: &Package,
     fn_name: FunctionNameMatch,
+    skip: &[String],
     show_output: bool,
     foreign_call_resolver_url: Option<&str>,
     root_path: Option<PathBuf>,
     package_name: Option<String>,
     compile_options: &CompileOptions,
 ) -> Result<Vec<(String, TestStatus)>, CliError> {
-    let test_functions =
-        get_tests_in_package(file_manager, parsed_files, package, fn_name, compile_options)?;
+    let (test_functions, count_available) =
+        get_tests_in_package(file_manager, parsed_files, package, fn_name, skip, compile_options)?;
 
     let count_all = test_functions.len();
 
-    let plural = if count_all == 1 { "" } else { "s" };
-    println!("[{}] Running {count_all} test function{plural}", package.name);
+    let plural = if count_available == 1 { "" } else { "s" };
+    if count_all == count_available {
+        human_println!("[{}] Running {count_all} test function{plural}", package.name);
+    } else {
+        human_println!(
+            "[{}] Running {count_all} of {count_available} test function{plural}",
+            package.name
+        );
+    }
 
     let test_report: Vec<(String, TestStatus)> = test_functions
         .into_par_iter()
@@ -203,21 +298,30 @@ fn run_test<S: BlackBoxFunctionSolver<FieldElement> + Default>(
     )
 }
 
+/// Returns the names of the package's tests which match `fn_name` and none of the `skip` patterns,
+/// along with the number of tests in the package.
 fn get_tests_in_package(
     file_manager: &FileManager,
     parsed_files: &ParsedFiles,
     package: &Package,
     fn_name: FunctionNameMatch,
+    skip: &[String],
     options: &CompileOptions,
-) -> Result<Vec<String>, CliError> {
+) -> Result<(Vec<String>, usize), CliError> {
     let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
     check_crate_and_report_errors(&mut context, crate_id, options)?;
 
-    Ok(context
+    let count_available = context
+        .get_all_test_functions_in_crate_matching(&crate_id, FunctionNameMatch::Anything)
+        .len();
+    let test_names = context
         .get_all_test_functions_in_crate_matching(&crate_id, fn_name)
         .into_iter()
         .map(|(test_name, _)| test_name)
-        .collect())
+        .filter(|test_name| !skip.iter().any(|pattern| test_name.contains(pattern.as_str())))
+        .collect();
+
+    Ok((test_names, count_available))
 }
 
 fn display_test_report(
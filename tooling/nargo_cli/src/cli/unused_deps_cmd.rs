@@ -0,0 +1,134 @@
+use std::io::Write;
+use std::path::Path;
+
+use clap::Args;
+use nargo::{
+    insert_all_files_for_workspace_into_file_manager, package::CrateName, parse_all,
+    prepare_package,
+};
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+
+use crate::errors::CliError;
+
+use super::check_cmd::check_crate_and_report_errors;
+use super::NargoConfig;
+
+/// Reports dependencies which are declared in a package's manifest but never used in its source
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UnusedDepsCommand {
+    /// The name of the package to lint
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Lint all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    /// Remove the unused dependencies from each package's `Nargo.toml`, after confirmation
+    #[clap(long)]
+    fix: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(args: UnusedDepsCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    let mut workspace_file_manager = workspace.new_file_manager();
+    insert_all_files_for_workspace_into_file_manager(&workspace, &mut workspace_file_manager);
+    let parsed_files = parse_all(&workspace_file_manager);
+
+    for package in &workspace {
+        let (mut context, crate_id) =
+            prepare_package(&workspace_file_manager, &parsed_files, package);
+        check_crate_and_report_errors(&mut context, crate_id, &args.compile_options)?;
+
+        // Only dependencies declared in the manifest are reported, as the standard library
+        // and any custom prelude are linked implicitly.
+        let unused_dependencies: Vec<String> = context.crate_graph[crate_id]
+            .dependencies
+            .iter()
+            .filter(|dependency| package.dependencies.contains_key(&dependency.name))
+            .filter(|dependency| {
+                !context.usage_tracker.is_dependency_used(crate_id, dependency.crate_id)
+            })
+            .map(|dependency| dependency.as_name())
+            .collect();
+
+        if unused_dependencies.is_empty() {
+            human_println!("[{}] No unused dependencies", package.name);
+            continue;
+        }
+
+        human_println!("[{}] Unused dependencies:", package.name);
+        for name in &unused_dependencies {
+            human_println!("    {name}");
+        }
+
+        if args.fix {
+            let manifest_path = package.root_dir.join("Nargo.toml");
+            if confirm(&format!("Remove them from {}?", manifest_path.display()))? {
+                remove_dependencies(&manifest_path, &unused_dependencies)?;
+                human_println!(
+                    "[{}] Removed {} dependencies",
+                    package.name,
+                    unused_dependencies.len()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn confirm(prompt: &str) -> Result<bool, CliError> {
+    print!("{prompt} [y/N] ");
+    std::io::stdout().flush().map_err(|err| CliError::Generic(err.to_string()))?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).map_err(|err| CliError::Generic(err.to_string()))?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Removes the entries for `names` from the `[dependencies]` table of the manifest.
+///
+/// Entries are removed line by line so that the rest of the manifest keeps its formatting.
+/// Dependencies declared as their own `[dependencies.name]` table aren't supported.
+fn remove_dependencies(manifest_path: &Path, names: &[String]) -> Result<(), CliError> {
+    let manifest = std::fs::read_to_string(manifest_path).map_err(|err| {
+        CliError::Generic(format!("Failed to read {}: {err}", manifest_path.display()))
+    })?;
+
+    let mut in_dependencies = false;
+    let mut lines = Vec::new();
+    for line in manifest.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_dependencies = trimmed == "[dependencies]";
+        } else if in_dependencies {
+            let key = trimmed.split('=').next().unwrap_or_default().trim().trim_matches('"');
+            if names.iter().any(|name| name == key) {
+                continue;
+            }
+        }
+        lines.push(line);
+    }
+
+    let mut updated_manifest = lines.join("\n");
+    if manifest.ends_with('\n') {
+        updated_manifest.push('\n');
+    }
+    std::fs::write(manifest_path, updated_manifest).map_err(|err| {
+        CliError::Generic(format!("Failed to write {}: {err}", manifest_path.display()))
+    })
+}
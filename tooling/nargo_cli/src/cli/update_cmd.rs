@@ -0,0 +1,18 @@
+use clap::Args;
+use nargo::constants::LOCK_FILE;
+use nargo_toml::{get_package_manifest, update_lock_file};
+
+use crate::errors::CliError;
+
+use super::NargoConfig;
+
+/// Fetch the workspace's git dependencies again and rewrite its Nargo.lock
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UpdateCommand;
+
+pub(crate) fn run(_args: UpdateCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let workspace = update_lock_file(&toml_path)?;
+    human_println!("Updated {}", workspace.root_dir.join(LOCK_FILE).display());
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use clap::Args;
+use nargo::package::{Dependency, Package};
+use nargo::workspace::Workspace;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use serde::Serialize;
+
+use crate::errors::CliError;
+
+use super::fs::write_to_file;
+use super::NargoConfig;
+
+const VENDOR_DIR: &str = "vendor";
+const VENDOR_MANIFEST: &str = "Vendor.toml";
+
+/// Copies the git dependencies of the workspace into a local `vendor` directory
+#[derive(Debug, Clone, Args)]
+pub(crate) struct VendorCommand {
+    /// Replace the contents of an existing `vendor` directory
+    #[clap(long)]
+    sync: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct VendorManifest {
+    package: Vec<VendoredPackage>,
+}
+
+#[derive(Debug, Serialize)]
+struct VendoredPackage {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// The directory within `vendor` which the package was copied to.
+    path: String,
+    /// The directory which the package was resolved to before being vendored.
+    source: PathBuf,
+}
+
+pub(crate) fn run(args: VendorCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        PackageSelection::All,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_owned()),
+    )?;
+
+    let vendor_dir = workspace.root_dir.join(VENDOR_DIR);
+    if vendor_dir.exists() {
+        if !args.sync {
+            return Err(CliError::DestinationAlreadyExists(vendor_dir));
+        }
+        std::fs::remove_dir_all(&vendor_dir).map_err(|err| {
+            CliError::Generic(format!("Failed to remove {}: {err}", vendor_dir.display()))
+        })?;
+    }
+
+    // Git dependencies are keyed by the directory they were cloned into, so that a package
+    // depended upon by several members is only vendored once.
+    let mut remote_packages: BTreeMap<PathBuf, &Package> = BTreeMap::new();
+    for member in workspace.members.iter() {
+        collect_remote_packages(member, &mut remote_packages);
+    }
+
+    let mut vendored: BTreeMap<PathBuf, String> = BTreeMap::new();
+    let mut manifest = VendorManifest { package: Vec::new() };
+    for (source, package) in remote_packages {
+        let name: String = package.name.clone().into();
+        if manifest.package.iter().any(|vendored| vendored.path == name) {
+            return Err(CliError::Generic(format!(
+                "Cannot vendor two different sources for the package `{name}`"
+            )));
+        }
+
+        copy_package_dir(&source, &vendor_dir.join(&name)).map_err(|err| {
+            CliError::Generic(format!("Failed to vendor package `{name}`: {err}"))
+        })?;
+        vendored.insert(source.clone(), name.clone());
+        manifest.package.push(VendoredPackage {
+            name: name.clone(),
+            version: package.version.clone(),
+            path: name,
+            source,
+        });
+    }
+
+    let manifest_toml = toml::to_string(&manifest).expect("vendor manifest should be serializable");
+    write_to_file(manifest_toml.as_bytes(), &vendor_dir.join(VENDOR_MANIFEST));
+
+    print_dependency_overrides(&workspace, &vendored);
+
+    Ok(())
+}
+
+/// Collects every git dependency reachable from `package`, including through local dependencies.
+fn collect_remote_packages<'a>(
+    package: &'a Package,
+    remote_packages: &mut BTreeMap<PathBuf, &'a Package>,
+) {
+    for dependency in package.dependencies.values() {
+        match dependency {
+            Dependency::Remote { package } => {
+                remote_packages.insert(package.root_dir.clone(), package);
+                collect_remote_packages(package, remote_packages);
+            }
+            Dependency::Local { package } => collect_remote_packages(package, remote_packages),
+        }
+    }
+}
+
+/// Recursively copies the package's sources, skipping any build artifacts and git metadata.
+fn copy_package_dir(source: &Path, destination: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(destination)?;
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        if file_name == ".git" || file_name == "target" {
+            continue;
+        }
+
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            copy_package_dir(&path, &destination.join(&file_name))?;
+        } else {
+            std::fs::copy(&path, destination.join(&file_name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Prints the `[dependencies]` entries each workspace member needs in order to resolve its
+/// git dependencies from the `vendor` directory.
+fn print_dependency_overrides(workspace: &Workspace, vendored: &BTreeMap<PathBuf, String>) {
+    for member in workspace.members.iter() {
+        let overrides: Vec<(String, &String)> = member
+            .dependencies
+            .iter()
+            .filter_map(|(name, dependency)| match dependency {
+                Dependency::Remote { package } => {
+                    Some((name.into(), vendored.get(&package.root_dir)?))
+                }
+                Dependency::Local { .. } => None,
+            })
+            .collect();
+        if overrides.is_empty() {
+            continue;
+        }
+
+        // Members are nested within the workspace, so the vendor directory can be reached by walking back up.
+        let vendor_dir = match member.root_dir.strip_prefix(&workspace.root_dir) {
+            Ok(relative_dir) => relative_dir
+                .components()
+                .filter(|component| matches!(component, Component::Normal(_)))
+                .map(|_| PathBuf::from(".."))
+                .fold(PathBuf::new(), |path, parent| path.join(parent))
+                .join(VENDOR_DIR),
+            Err(_) => workspace.root_dir.join(VENDOR_DIR),
+        };
+
+        human_println!("[dependencies] for {}:", member.root_dir.join("Nargo.toml").display());
+        for (name, vendored_name) in overrides {
+            let path = vendor_dir.join(vendored_name);
+            human_println!("{name} = {{ path = \"{}\" }}", path.display());
+        }
+        human_println!();
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::errors::{CliError, FilesystemError};
+
+use super::checksum_cmd::{artifact_checksum, default_checksum_path};
+use super::NargoConfig;
+
+/// Verifies a compiled artifact against its detached checksum file
+#[derive(Debug, Clone, Args)]
+pub(crate) struct VerifyChecksumCommand {
+    /// Path to the artifact to verify
+    artifact_path: PathBuf,
+
+    /// Path to the checksum file, if it isn't alongside the artifact
+    #[clap(long)]
+    checksum_file: Option<PathBuf>,
+}
+
+pub(crate) fn run(args: VerifyChecksumCommand, config: NargoConfig) -> Result<(), CliError> {
+    let artifact_path = config.program_dir.join(&args.artifact_path);
+    let checksum_path = match &args.checksum_file {
+        Some(checksum_file) => config.program_dir.join(checksum_file),
+        None => default_checksum_path(&artifact_path),
+    };
+
+    let checksum_file = std::fs::read_to_string(&checksum_path)
+        .map_err(|_| FilesystemError::PathNotValid(checksum_path.clone()))?;
+    let expected = checksum_file.split_whitespace().next().ok_or_else(|| {
+        CliError::Generic(format!("Checksum file {} is empty", checksum_path.display()))
+    })?;
+
+    let actual = artifact_checksum(&artifact_path)?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(CliError::ChecksumMismatch {
+            artifact_path,
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+
+    human_println!("Checksum of {} is valid", artifact_path.display());
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use clap::Args;
+
+use crate::errors::CliError;
+
+use super::registry::{set_yanked, RegistryArgs};
+use super::NargoConfig;
+
+/// Stop a published version of a package from being selected for new dependencies
+#[derive(Debug, Clone, Args)]
+pub(crate) struct YankCommand {
+    /// The name of the package
+    name: String,
+
+    /// The version to yank
+    version: String,
+
+    /// The token to authenticate with the registry with.
+    /// Defaults to `NARGO_REGISTRY_TOKEN`, or else to the token stored by `nargo login`.
+    #[clap(long)]
+    token: Option<String>,
+
+    #[clap(flatten)]
+    pub(super) registry: RegistryArgs,
+}
+
+/// Allow a yanked version of a package to be selected for new dependencies again
+#[derive(Debug, Clone, Args)]
+pub(crate) struct UnyankCommand {
+    /// The name of the package
+    name: String,
+
+    /// The version to unyank
+    version: String,
+
+    /// The token to authenticate with the registry with.
+    /// Defaults to `NARGO_REGISTRY_TOKEN`, or else to the token stored by `nargo login`.
+    #[clap(long)]
+    token: Option<String>,
+
+    #[clap(flatten)]
+    pub(super) registry: RegistryArgs,
+}
+
+pub(crate) fn run(args: YankCommand, _config: NargoConfig) -> Result<(), CliError> {
+    set_yanked(&args.name, &args.version, true, args.token)?;
+    human_println!("Yanked `{}` {}", args.name, args.version);
+    Ok(())
+}
+
+pub(crate) fn run_unyank(args: UnyankCommand, _config: NargoConfig) -> Result<(), CliError> {
+    set_yanked(&args.name, &args.version, false, args.token)?;
+    human_println!("Unyanked `{}` {}", args.name, args.version);
+    Ok(())
+}
@@ -38,6 +38,25 @@ pub(crate) enum CliError {
     #[error("Invalid package name {0}. Did you mean to use `--name`?")]
     InvalidPackageName(String),
 
+    #[error("Error: checksum of {} does not match. Expected {expected} but found {actual}", .artifact_path.display())]
+    ChecksumMismatch { artifact_path: PathBuf, expected: String, actual: String },
+
+    #[error("Error: cannot write artifacts to {}: {1}", .0.display())]
+    TargetDirectoryNotWritable(PathBuf, std::io::Error),
+
+    #[error("Error: the registry at {0} rejected the token. Run `nargo login` with a valid token, or check `NARGO_REGISTRY_TOKEN`")]
+    RegistryAuthenticationFailed(String),
+
+    #[error("Error: compilation did not finish within {0} seconds")]
+    CompileTimeout(u64),
+
+    #[error("Error: recompiling {} from its embedded sources did not reproduce it", .0.display())]
+    ReplayMismatch(PathBuf),
+
+    /// Compilation succeeded with warnings while `--exit-code-on-warnings` was given.
+    #[error("Error: compilation reported {warning_count} warning(s)")]
+    WarningsReported { warning_count: usize, exit_code: i32 },
+
     /// ABI encoding/decoding error
     #[error(transparent)]
     AbiError(#[from] AbiError),
@@ -52,6 +71,10 @@ pub(crate) enum CliError {
     #[error(transparent)]
     DapError(#[from] DapError),
 
+    /// Execution error which could be traced back to the source
+    #[error("{error}\n  --> {location}")]
+    ExecutionFailedAt { error: NargoError<FieldElement>, location: String },
+
     /// Error from Nargo
     #[error(transparent)]
     NargoError(#[from] NargoError<FieldElement>),
@@ -64,3 +87,13 @@ pub(crate) enum CliError {
     #[error(transparent)]
     CompileError(#[from] CompileError),
 }
+
+impl CliError {
+    /// The code which nargo exits with when a command fails with this error.
+    pub(crate) fn exit_code(&self) -> i32 {
+        match self {
+            CliError::WarningsReported { exit_code, .. } => *exit_code,
+            _ => 1,
+        }
+    }
+}
@@ -44,6 +44,8 @@ fn main() {
 
     if let Err(report) = cli::start_cli() {
         eprintln!("{report}");
-        std::process::exit(1);
+        let exit_code =
+            report.downcast_ref::<errors::CliError>().map_or(1, errors::CliError::exit_code);
+        std::process::exit(exit_code);
     }
 }
@@ -0,0 +1,97 @@
+//! Checks that `nargo add` and `nargo remove` edit a package's Nargo.toml without disturbing the rest of it.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+const MANIFEST: &str = r#"# The manifest of the test package
+[package]
+name = "hello_world"
+type = "bin"
+authors = [""]
+
+[dependencies]
+# Kept in alphabetical order
+existing = { path = "../existing" } # a local dependency
+"#;
+
+fn nargo(program_dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(program_dir);
+    cmd
+}
+
+fn new_package(test_dir: &assert_fs::TempDir) -> assert_fs::fixture::ChildPath {
+    let package_dir = test_dir.child("hello_world");
+    std::fs::create_dir_all(package_dir.child("src").path()).unwrap();
+    package_dir.child("Nargo.toml").write_str(MANIFEST).unwrap();
+    package_dir.child("src").child("main.nr").write_str("fn main() {}").unwrap();
+    package_dir
+}
+
+fn read_manifest(package_dir: &Path) -> String {
+    std::fs::read_to_string(package_dir.join("Nargo.toml")).unwrap()
+}
+
+#[test]
+fn add_then_remove_keeps_formatting_and_comments() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let package_dir = new_package(&test_dir);
+
+    nargo(package_dir.path()).args(["add", "foo", "--path", "../foo"]).assert().success();
+    // Without `--tag`, the version given with the dependency is the tag.
+    nargo(package_dir.path())
+        .args(["add", "bar@v1.0.0", "--git", "https://example.com/bar"])
+        .assert()
+        .success();
+    nargo(package_dir.path())
+        .args(["add", "baz@v1.0.0", "--git", "https://example.com/baz", "--tag", "v2.0.0"])
+        .args(["--directory", "crates/baz"])
+        .assert()
+        .success();
+
+    let manifest = read_manifest(package_dir.path());
+    assert!(manifest.starts_with(MANIFEST), "{manifest}");
+    assert!(manifest.contains(r#"foo = { path = "../foo" }"#), "{manifest}");
+    assert!(manifest.contains(r#"bar = { git = "https://example.com/bar", tag = "v1.0.0" }"#));
+    assert!(manifest.contains(
+        r#"baz = { git = "https://example.com/baz", tag = "v2.0.0", directory = "crates/baz" }"#
+    ));
+
+    nargo(package_dir.path()).args(["remove", "foo", "bar", "baz"]).assert().success();
+    assert_eq!(read_manifest(package_dir.path()), MANIFEST);
+}
+
+#[test]
+fn remove_is_all_or_nothing() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let package_dir = new_package(&test_dir);
+
+    nargo(package_dir.path())
+        .args(["remove", "existing", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("The dependency `missing` could not be found"));
+    assert_eq!(read_manifest(package_dir.path()), MANIFEST);
+}
+
+#[test]
+fn invalid_versions_are_rejected() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let package_dir = new_package(&test_dir);
+
+    nargo(package_dir.path())
+        .args(["add", "foo@1.0.0", "--path", "../foo"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("A version can't be given for a path dependency"));
+    nargo(package_dir.path())
+        .args(["add", "bar", "--git", "https://example.com/bar"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("A git dependency needs a tag"));
+    assert_eq!(read_manifest(package_dir.path()), MANIFEST);
+}
@@ -0,0 +1,55 @@
+//! Checks that repeated compilations reuse up-to-date artifacts without losing any requested output.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+use assert_fs::prelude::PathChild;
+
+fn nargo(program_dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(program_dir);
+    cmd
+}
+
+fn new_project(test_dir: &assert_fs::TempDir, name: &str) -> std::path::PathBuf {
+    nargo(test_dir.path()).arg("new").arg(name).assert().success();
+    test_dir.child(name).path().to_path_buf()
+}
+
+#[test]
+fn recompiling_still_prints_requested_output() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let project_dir = new_project(&test_dir, "hello_world");
+
+    nargo(&project_dir).arg("compile").assert().success();
+
+    // The artifact is up to date, but the SSA and ACIR are only printed while compiling.
+    nargo(&project_dir)
+        .args(["compile", "--show-ssa", "--print-acir"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("After Initial SSA:"))
+        .stdout(predicate::str::contains("Compiled ACIR for main"));
+}
+
+#[test]
+fn recompiling_with_different_artifact_options_rewrites_the_artifact() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let project_dir = new_project(&test_dir, "hello_world");
+    let artifact_path = project_dir.join("target/hello_world.json");
+    let read_artifact = || std::fs::read_to_string(&artifact_path).unwrap();
+
+    nargo(&project_dir).arg("compile").assert().success();
+    let compact = read_artifact();
+
+    nargo(&project_dir).args(["compile", "--pretty"]).assert().success();
+    let pretty = read_artifact();
+    assert_ne!(pretty, compact);
+    assert!(pretty.lines().count() > 1);
+
+    // Dropping the flag again goes back to the compact artifact.
+    nargo(&project_dir).arg("compile").assert().success();
+    assert_eq!(read_artifact(), compact);
+}
@@ -0,0 +1,64 @@
+//! Checks that artifacts which fail the checks requested of `nargo compile` are never saved.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+fn nargo(program_dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(program_dir);
+    cmd
+}
+
+#[test]
+fn disallowed_black_box_functions_leave_the_previous_artifact() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    nargo(test_dir.path()).arg("new").arg("hello_world").assert().success();
+    let project_dir = test_dir.child("hello_world");
+    let artifact_path = project_dir.child("target/hello_world.json");
+
+    nargo(project_dir.path()).arg("compile").assert().success();
+    let previous = std::fs::read(&artifact_path).unwrap();
+
+    // A `u8` parameter is range constrained.
+    project_dir.child("src/main.nr").write_str("fn main(x: u8) {\n    assert(x != 3);\n}").unwrap();
+    nargo(project_dir.path())
+        .args(["compile", "--deny-blackbox", "range"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("uses the black box function `range`"));
+    assert_eq!(std::fs::read(&artifact_path).unwrap(), previous);
+
+    // An artifact which is already up to date is checked too.
+    nargo(project_dir.path()).arg("compile").assert().success();
+    nargo(project_dir.path()).args(["compile", "--deny-blackbox", "range"]).assert().failure();
+}
+
+#[test]
+fn regressions_against_a_baseline_leave_the_previous_artifact() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    nargo(test_dir.path()).arg("new").arg("hello_world").assert().success();
+    let project_dir = test_dir.child("hello_world");
+    let artifact_path = project_dir.child("target/hello_world.json");
+
+    nargo(project_dir.path()).arg("compile").assert().success();
+    let baseline = std::fs::read(&artifact_path).unwrap();
+    let baseline_path = test_dir.child("baseline.json");
+    std::fs::write(&baseline_path, &baseline).unwrap();
+
+    project_dir
+        .child("src/main.nr")
+        .write_str("fn main(x: Field, y: pub Field) {\n    assert(x * x != y);\n    assert(x * y != 3);\n}")
+        .unwrap();
+    nargo(project_dir.path())
+        .arg("compile")
+        .arg("--compare-with")
+        .arg(baseline_path.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("regressed against"));
+    assert_eq!(std::fs::read(&artifact_path).unwrap(), baseline);
+}
@@ -0,0 +1,62 @@
+//! Checks that `nargo compile --replay` reproduces an artifact from the sources embedded in it.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+fn nargo(program_dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(program_dir);
+    cmd
+}
+
+/// Creates and compiles a new binary project, returning its directory.
+fn compiled_project(test_dir: &assert_fs::TempDir) -> PathBuf {
+    nargo(test_dir.path()).arg("new").arg("hello_world").assert().success();
+    let project_dir = test_dir.child("hello_world").path().to_path_buf();
+    nargo(&project_dir).arg("compile").assert().success();
+    project_dir
+}
+
+#[test]
+fn replay_reproduces_the_artifact() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let project_dir = compiled_project(&test_dir);
+    let artifact_path = project_dir.join("target/hello_world.json");
+    let recorded = std::fs::read(&artifact_path).unwrap();
+
+    nargo(&project_dir)
+        .args(["compile", "--replay", "target/hello_world.json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Reproduced"));
+
+    // Replaying only checks the artifact, leaving it exactly as it was recorded.
+    assert_eq!(std::fs::read(&artifact_path).unwrap(), recorded);
+}
+
+#[test]
+fn replay_reports_a_changed_artifact() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let project_dir = compiled_project(&test_dir);
+    let artifact_path = project_dir.join("target/hello_world.json");
+
+    // Changing the embedded source changes the program which the artifact is replayed into.
+    let mut artifact: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&artifact_path).unwrap()).unwrap();
+    for file in artifact["file_map"].as_object_mut().unwrap().values_mut() {
+        let source = file["source"].as_str().unwrap().replace("x != y", "x == y");
+        file["source"] = source.into();
+    }
+    test_dir.child("hello_world/target/hello_world.json").write_str(&artifact.to_string()).unwrap();
+
+    nargo(&project_dir)
+        .args(["compile", "--replay", "target/hello_world.json"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("differs"))
+        .stderr(predicate::str::contains("did not reproduce it"));
+}
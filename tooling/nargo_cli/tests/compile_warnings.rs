@@ -0,0 +1,47 @@
+//! Checks the exit code of `nargo compile --exit-code-on-warnings`.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+#[test]
+fn warnings_exit_with_the_given_code() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(test_dir.path()).arg("new").arg("hello_world").assert().success();
+
+    let project_dir = test_dir.child("hello_world");
+    let nargo = || {
+        let mut cmd = Command::cargo_bin("nargo").unwrap();
+        cmd.current_dir(project_dir.path()).args(["compile", "--exit-code-on-warnings", "3"]);
+        cmd
+    };
+
+    nargo().assert().success();
+
+    project_dir
+        .child("src/main.nr")
+        .write_str("fn main(x: Field, y: pub Field) {\n    let unused = 1;\n    assert(x != y);\n}")
+        .unwrap();
+    nargo()
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("unused variable"))
+        .stderr(predicate::str::contains("warning(s)"));
+}
+
+#[test]
+fn exit_code_must_report_failure() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(test_dir.path()).arg("new").arg("hello_world").assert().success();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(test_dir.child("hello_world").path())
+        .args(["compile", "--exit-code-on-warnings", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--exit-code-on-warnings"));
+}
@@ -0,0 +1,49 @@
+//! Checks that with `--format json`, or a command's own `--json` flag, stdout only holds JSON.
+
+use assert_cmd::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+fn nargo(program_dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(program_dir);
+    cmd
+}
+
+/// Runs `nargo` with `args` and parses every line of its stdout as a JSON object.
+fn json_lines(program_dir: &Path, args: &[&str]) -> Vec<serde_json::Value> {
+    let output = nargo(program_dir).args(args).assert().success().get_output().stdout.clone();
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line).unwrap_or_else(|err| panic!("not JSON: {line:?} ({err})"))
+        })
+        .collect()
+}
+
+#[test]
+fn human_output_is_kept_off_stdout() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    nargo(test_dir.path()).arg("new").arg("hello_world").assert().success();
+    let project_dir = test_dir.child("hello_world");
+    project_dir.child("Prover.toml").write_str("x = 1\ny = 2").unwrap();
+
+    let lines = json_lines(project_dir.path(), &["--format", "json", "execute"]);
+    assert_eq!(lines.last().unwrap()["status"], "success");
+}
+
+#[test]
+fn json_flags_are_aliases_of_the_global_format() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    nargo(test_dir.path()).arg("new").arg("hello_world").assert().success();
+    let project_dir = test_dir.child("hello_world");
+
+    for command in ["env", "metadata"] {
+        let lines = json_lines(project_dir.path(), &[command, "--json"]);
+        assert_eq!(lines.len(), 2, "`{command} --json` should write a report and a status");
+        assert_eq!(lines[1]["status"], "success");
+    }
+}
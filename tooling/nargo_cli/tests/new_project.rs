@@ -0,0 +1,21 @@
+//! Checks the manifest written by `nargo new`.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use assert_fs::prelude::{PathAssert, PathChild};
+
+#[test]
+fn authors_are_written_to_the_manifest() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(test_dir.path()).args(["new", "hello_world"]);
+    cmd.args(["--author", "Alice <alice@example.com>", "--author", "Bob"]);
+    cmd.assert().success();
+
+    test_dir
+        .child("hello_world/Nargo.toml")
+        .assert(predicate::str::contains(r#"authors = ["Alice <alice@example.com>", "Bob"]"#));
+}
@@ -0,0 +1,50 @@
+//! Checks that a package's custom prelude is in scope wherever the package is compiled.
+
+use assert_cmd::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+use assert_fs::prelude::PathChild;
+
+fn nargo(program_dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(program_dir);
+    cmd
+}
+
+fn write_file(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn dependencies_keep_their_own_prelude() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+
+    let lib_dir = test_dir.child("lib");
+    write_file(
+        lib_dir.child("Nargo.toml").path(),
+        "[package]\nname = \"lib\"\ntype = \"lib\"\nprelude = \"src/prelude.nr\"",
+    );
+    write_file(
+        lib_dir.child("src/prelude.nr").path(),
+        "pub fn double(x: Field) -> Field { x * 2 }",
+    );
+    write_file(
+        lib_dir.child("src/lib.nr").path(),
+        "pub fn quadruple(x: Field) -> Field { double(double(x)) }",
+    );
+
+    let bin_dir = test_dir.child("bin");
+    write_file(
+        bin_dir.child("Nargo.toml").path(),
+        "[package]\nname = \"bin\"\ntype = \"bin\"\n\n[dependencies]\nlib = { path = \"../lib\" }",
+    );
+    write_file(
+        bin_dir.child("src/main.nr").path(),
+        "fn main(x: Field) { assert(lib::quadruple(x) != 3); }",
+    );
+
+    nargo(lib_dir.path()).arg("check").assert().success();
+    nargo(bin_dir.path()).arg("compile").assert().success();
+}
@@ -2,14 +2,14 @@
 #![allow(clippy::items_after_test_module)]
 use clap::Parser;
 use fm::FileManager;
-use noirc_driver::{check_crate, file_manager_with_stdlib, CompileOptions};
+use noirc_driver::{check_crate, file_manager_with_stdlib, CompileOptions, MessageFormat};
 use noirc_frontend::hir::FunctionNameMatch;
 use std::io::Write;
 use std::{collections::BTreeMap, path::PathBuf};
 
 use nargo::{
     ops::{report_errors, run_test, TestStatus},
-    package::{Package, PackageType},
+    package::{CompileSettings, Package, PackageType},
     parse_all, prepare_package,
 };
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
@@ -63,13 +63,20 @@ fn run_stdlib_tests(force_brillig: bool, inliner_aggressiveness: i64) {
         name: "stdlib".parse().unwrap(),
         dependencies: BTreeMap::new(),
         expression_width: None,
+        stdlib_revision: None,
+        prelude_path: None,
+        entry_function: None,
+        lints: BTreeMap::new(),
+        include: None,
+        exclude: Vec::new(),
+        compile: CompileSettings::default(),
     };
 
     let (mut context, dummy_crate_id) =
         prepare_package(&file_manager, &parsed_files, &dummy_package);
 
     let result = check_crate(&mut context, dummy_crate_id, &Default::default());
-    report_errors(result, &context.file_manager, true, false)
+    report_errors(result, &context.file_manager, true, false, MessageFormat::Human)
         .expect("Error encountered while compiling standard library");
 
     // We can now search within the stdlib for any test functions to compile.
@@ -0,0 +1,100 @@
+//! Checks that `nargo vendor` copies git dependencies into the workspace and prints the paths to depend on them by.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+use assert_fs::prelude::{PathAssert, PathChild};
+
+/// Runs `nargo` in `program_dir` with `home_dir` as the home directory, so that git dependencies
+/// are resolved from the checkouts within `home_dir/nargo` instead of being cloned.
+fn nargo(program_dir: &Path, home_dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(program_dir).env("HOME", home_dir);
+    cmd
+}
+
+fn write_file(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+/// Writes a workspace whose only member, in `crates/app`, depends on a git dependency,
+/// along with the checkout which the dependency resolves to.
+fn workspace_with_git_dependency(
+    test_dir: &assert_fs::TempDir,
+) -> (assert_fs::fixture::ChildPath, assert_fs::fixture::ChildPath) {
+    let checkout_dir = test_dir.child("home/nargo/example.com/libv1.0.0");
+    write_file(
+        checkout_dir.child("Nargo.toml").path(),
+        "[package]\nname = \"lib\"\ntype = \"lib\"",
+    );
+    write_file(checkout_dir.child("src/lib.nr").path(), "pub fn foo() {}");
+    write_file(checkout_dir.child("target/lib.json").path(), "{}");
+    write_file(checkout_dir.child(".git/HEAD").path(), "ref: refs/heads/main");
+
+    let workspace_dir = test_dir.child("workspace");
+    write_file(workspace_dir.child("Nargo.toml").path(), "[workspace]\nmembers = [\"crates/app\"]");
+    write_file(
+        workspace_dir.child("crates/app/Nargo.toml").path(),
+        r#"
+        [package]
+        name = "app"
+        type = "bin"
+
+        [dependencies]
+        lib = { git = "https://example.com/lib", tag = "v1.0.0" }
+        "#,
+    );
+    write_file(workspace_dir.child("crates/app/src/main.nr").path(), "fn main() {}");
+
+    (workspace_dir, checkout_dir)
+}
+
+#[test]
+fn vendors_git_dependencies() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let home_dir = test_dir.child("home");
+    let (workspace_dir, _) = workspace_with_git_dependency(&test_dir);
+
+    // The path is relative to the member's directory, two levels below the workspace root.
+    nargo(workspace_dir.path(), home_dir.path())
+        .arg("vendor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#"lib = { path = "../../vendor/lib" }"#));
+
+    let vendored_dir = workspace_dir.child("vendor/lib");
+    vendored_dir.child("Nargo.toml").assert(predicate::path::is_file());
+    vendored_dir.child("src/lib.nr").assert("pub fn foo() {}");
+    vendored_dir.child("target").assert(predicate::path::missing());
+    vendored_dir.child(".git").assert(predicate::path::missing());
+    workspace_dir.child("vendor/Vendor.toml").assert(predicate::str::contains(r#"path = "lib""#));
+}
+
+#[test]
+fn rerunning_requires_sync() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+    let home_dir = test_dir.child("home");
+    let (workspace_dir, checkout_dir) = workspace_with_git_dependency(&test_dir);
+
+    nargo(workspace_dir.path(), home_dir.path()).arg("vendor").assert().success();
+
+    let vendored_dir = workspace_dir.child("vendor/lib");
+    write_file(vendored_dir.child("src/stale.nr").path(), "");
+    write_file(checkout_dir.child("src/lib.nr").path(), "pub fn bar() {}");
+
+    // An existing `vendor` directory is left alone...
+    nargo(workspace_dir.path(), home_dir.path())
+        .arg("vendor")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+    vendored_dir.child("src/lib.nr").assert("pub fn foo() {}");
+
+    // ...unless it's replaced with `--sync`, which drops anything which is no longer in the sources.
+    nargo(workspace_dir.path(), home_dir.path()).args(["vendor", "--sync"]).assert().success();
+    vendored_dir.child("src/lib.nr").assert("pub fn bar() {}");
+    vendored_dir.child("src/stale.nr").assert(predicate::path::missing());
+}
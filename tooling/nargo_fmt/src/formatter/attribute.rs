@@ -50,7 +50,8 @@ impl<'a> Formatter<'a> {
             | FunctionAttribute::Builtin(_)
             | FunctionAttribute::Oracle(_) => self.format_one_arg_attribute(),
             FunctionAttribute::Test(test_scope) => self.format_test_attribute(test_scope),
-            FunctionAttribute::Fold
+            FunctionAttribute::Bench
+            | FunctionAttribute::Fold
             | FunctionAttribute::NoPredicates
             | FunctionAttribute::InlineAlways => self.format_no_args_attribute(),
         }
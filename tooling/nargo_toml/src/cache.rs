@@ -0,0 +1,213 @@
+//! An on-disk cache of resolved workspaces, so that running several commands back to back
+//! doesn't parse every manifest and resolve every dependency again.
+//!
+//! A cached workspace is only reused if none of the manifests it was resolved from have changed since,
+//! and the entry and prelude files which resolution checked for are all still there. A dependency's
+//! checkout going missing is caught by its manifest going missing along with it.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use nargo::{
+    constants::{PKG_FILE, TARGET_DIR},
+    package::{Dependency, Package},
+    workspace::Workspace,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::PackageSelection;
+
+const CACHE_DIR: &str = ".workspace-cache";
+
+static RESOLVE_CACHE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables caching resolved workspaces for the rest of the process.
+///
+/// The cache is disabled by default, so that only tools which opt in write to the target directory.
+pub fn set_resolve_cache_enabled(enabled: bool) {
+    RESOLVE_CACHE_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub(crate) fn resolve_cache_enabled() -> bool {
+    RESOLVE_CACHE_ENABLED.load(Ordering::Relaxed)
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedWorkspace {
+    /// The hash of each manifest the workspace was resolved from.
+    manifests: BTreeMap<PathBuf, String>,
+    /// The entry and prelude files of every package, which must still exist for the workspace to resolve.
+    required_files: Vec<PathBuf>,
+    workspace: Workspace,
+}
+
+/// Returns the workspace cached at `cache_path`, if there is one, its manifests haven't changed
+/// and its required files still exist.
+pub(crate) fn read_cached_workspace(cache_path: &Path) -> Option<Workspace> {
+    let cached: CachedWorkspace = serde_json::from_slice(&std::fs::read(cache_path).ok()?).ok()?;
+    let unchanged = cached
+        .manifests
+        .iter()
+        .all(|(manifest_path, hash)| hash_file(manifest_path).as_ref() == Some(hash));
+    let required_files_exist = cached.required_files.iter().all(|path| path.is_file());
+    (unchanged && required_files_exist).then_some(cached.workspace)
+}
+
+/// Caches `workspace`, resolved from `toml_path`, at `cache_path`. Failing to write the cache isn't an error.
+pub(crate) fn write_cached_workspace(cache_path: &Path, toml_path: &Path, workspace: &Workspace) {
+    let packages = all_packages(&workspace.members);
+
+    let mut manifests = BTreeMap::new();
+    let manifest_paths = std::iter::once(toml_path.to_path_buf())
+        .chain(std::iter::once(workspace.root_dir.join(PKG_FILE)))
+        .chain(packages.iter().map(|package| package.root_dir.join(PKG_FILE)));
+    for manifest_path in manifest_paths {
+        let Some(hash) = hash_file(&manifest_path) else {
+            // A manifest which can't be read can't be checked for changes either.
+            return;
+        };
+        manifests.insert(manifest_path, hash);
+    }
+
+    let required_files = packages
+        .iter()
+        .flat_map(|package| std::iter::once(&package.entry_path).chain(&package.prelude_path))
+        .cloned()
+        .collect();
+
+    let cached = CachedWorkspace { manifests, required_files, workspace: workspace.clone() };
+    if let (Some(cache_dir), Ok(contents)) = (cache_path.parent(), serde_json::to_vec(&cached)) {
+        let _ = std::fs::create_dir_all(cache_dir);
+        let _ = std::fs::write(cache_path, contents);
+    }
+}
+
+/// Returns each package along with all of their dependencies, without duplicates.
+fn all_packages(packages: &[Package]) -> Vec<&Package> {
+    let mut all_packages: Vec<&Package> = Vec::new();
+    let mut pending: Vec<&Package> = packages.iter().collect();
+    while let Some(package) = pending.pop() {
+        if all_packages.iter().any(|seen| seen.root_dir == package.root_dir) {
+            continue;
+        }
+        all_packages.push(package);
+        pending.extend(package.dependencies.values().map(|dependency| match dependency {
+            Dependency::Local { package } | Dependency::Remote { package } => package,
+        }));
+    }
+    all_packages
+}
+
+/// Returns where the workspace resolved with these arguments is cached, within the target directory
+/// next to `toml_path`.
+pub(crate) fn cache_path(
+    toml_path: &Path,
+    package_selection: &PackageSelection,
+    max_depth: Option<usize>,
+) -> PathBuf {
+    let key = format!("{}|{package_selection:?}|{max_depth:?}", toml_path.display());
+    let file_name = format!("{:x}.json", Sha256::digest(key.as_bytes()));
+    let manifest_dir = toml_path.parent().unwrap_or(Path::new("."));
+    manifest_dir.join(TARGET_DIR).join(CACHE_DIR).join(file_name)
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    let contents = std::fs::read(path).ok()?;
+    Some(format!("{:x}", Sha256::digest(contents)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use nargo::workspace::Workspace;
+
+    use crate::{resolve_workspace_from_toml, PackageSelection};
+
+    use super::{read_cached_workspace, write_cached_workspace};
+
+    /// Writes a binary package with a prelude, depending on a library next to it, and caches its workspace.
+    fn cached_workspace(dir: &Path) -> (std::path::PathBuf, Workspace) {
+        let files = [
+            (
+                "bin/Nargo.toml",
+                r#"
+                [package]
+                name = "bin"
+                type = "bin"
+                prelude = "src/prelude.nr"
+
+                [dependencies]
+                lib = { path = "../lib" }
+                "#,
+            ),
+            ("bin/src/main.nr", "fn main() {}"),
+            ("bin/src/prelude.nr", ""),
+            ("lib/Nargo.toml", "[package]\nname = \"lib\"\ntype = \"lib\""),
+            ("lib/src/lib.nr", ""),
+        ];
+        for (path, contents) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        let toml_path = dir.join("bin/Nargo.toml");
+        let workspace =
+            resolve_workspace_from_toml(&toml_path, PackageSelection::All, None).unwrap();
+        let cache_path = dir.join("cache.json");
+        write_cached_workspace(&cache_path, &toml_path, &workspace);
+        (cache_path, workspace)
+    }
+
+    #[test]
+    fn unchanged_workspace_is_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cache_path, workspace) = cached_workspace(dir.path());
+
+        let cached = read_cached_workspace(&cache_path).expect("workspace should be cached");
+        assert_eq!(cached.members[0].name, workspace.members[0].name);
+    }
+
+    #[test]
+    fn edited_manifest_invalidates_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cache_path, _) = cached_workspace(dir.path());
+
+        std::fs::write(
+            dir.path().join("lib/Nargo.toml"),
+            "[package]\nname = \"lib\"\ntype = \"bin\"",
+        )
+        .unwrap();
+        assert!(read_cached_workspace(&cache_path).is_none());
+    }
+
+    #[test]
+    fn missing_prelude_invalidates_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cache_path, _) = cached_workspace(dir.path());
+
+        std::fs::remove_file(dir.path().join("bin/src/prelude.nr")).unwrap();
+        assert!(read_cached_workspace(&cache_path).is_none());
+    }
+
+    #[test]
+    fn missing_dependency_entry_file_invalidates_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cache_path, _) = cached_workspace(dir.path());
+
+        std::fs::remove_file(dir.path().join("lib/src/lib.nr")).unwrap();
+        assert!(read_cached_workspace(&cache_path).is_none());
+    }
+
+    #[test]
+    fn missing_dependency_directory_invalidates_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cache_path, _) = cached_workspace(dir.path());
+
+        std::fs::remove_dir_all(dir.path().join("lib")).unwrap();
+        assert!(read_cached_workspace(&cache_path).is_none());
+    }
+}
@@ -34,6 +34,9 @@ pub enum ManifestError {
     #[error("Cannot find file {entry} which was specified as the `entry` field in {toml}")]
     MissingEntryFile { toml: PathBuf, entry: PathBuf },
 
+    #[error("Cannot find file {prelude} which was specified as the `prelude` field in {toml}")]
+    MissingPreludeFile { toml: PathBuf, prelude: PathBuf },
+
     #[error(
         r#"Cannot find file {entry} which is defaulted due to specifying `type = "{package_type}"` in {toml}"#
     )]
@@ -73,8 +76,31 @@ pub enum ManifestError {
     #[error("Cyclic package dependency found when processing {cycle}")]
     CyclicDependency { cycle: String },
 
+    #[error("Dependency chain is deeper than the maximum depth of {max_depth}: {chain}")]
+    DependencyDepthExceeded { max_depth: usize, chain: String },
+
+    #[error(
+        "Package `{name}` in the `[artifacts]` section of {toml} is not a member of the workspace"
+    )]
+    UnknownArtifactPackage { toml: PathBuf, name: String },
+
+    #[error("Package `{name}` in the `[artifacts]` section of {toml} is a library and produces no artifact")]
+    LibraryArtifactPath { toml: PathBuf, name: CrateName },
+
+    #[error("Packages `{first}` and `{second}` would both write their artifact to {path}. Change the `[artifacts]` section of {toml}")]
+    ArtifactPathCollision { toml: PathBuf, path: PathBuf, first: CrateName, second: CrateName },
+
     #[error("Failed to parse expression width with the following error: {0}")]
     ParseExpressionWidth(String),
+
+    #[error("{} is badly formed, could not parse.\n\n {1}", .0.display())]
+    MalformedLockFile(PathBuf, toml::de::Error),
+
+    #[error("Failed to write {}: {1}", .0.display())]
+    LockFileWriteFailed(PathBuf, std::io::Error),
+
+    #[error("Dependency `{name}` no longer matches {}. Run `nargo update` to fetch it again and update the lock file", lock_file.display())]
+    LockFileMismatch { lock_file: PathBuf, name: String },
 }
 
 #[allow(clippy::enum_variant_names)]
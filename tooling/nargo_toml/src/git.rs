@@ -1,4 +1,18 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// The checkouts which have been cloned by this process, as opposed to reused from an earlier run.
+static FETCHED_CHECKOUTS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Records that the checkout at `checkout` was cloned by this process.
+pub(crate) fn record_fetched(checkout: PathBuf) {
+    FETCHED_CHECKOUTS.lock().unwrap().push(checkout);
+}
+
+/// Whether `dir` is within a checkout which was cloned by this process.
+pub(crate) fn was_fetched(dir: &Path) -> bool {
+    FETCHED_CHECKOUTS.lock().unwrap().iter().any(|checkout| dir.starts_with(checkout))
+}
 
 /// Creates a unique folder name for a GitHub repo
 /// by using its URL and tag
@@ -9,7 +23,7 @@ fn resolve_folder_name(base: &url::Url, tag: &str) -> String {
     folder_name
 }
 
-fn nargo_crates() -> PathBuf {
+pub(crate) fn nargo_crates() -> PathBuf {
     dirs::home_dir().unwrap().join("nargo")
 }
 
@@ -50,6 +64,7 @@ pub(crate) fn clone_git_repo(url: &str, tag: &str) -> Result<PathBuf, String> {
         .arg(&loc)
         .status()
         .expect("git clone command failed to start");
+    record_fetched(loc.clone());
 
     Ok(loc)
 }
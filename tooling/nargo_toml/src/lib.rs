@@ -11,19 +11,23 @@ use std::{
 use errors::SemverError;
 use fm::{NormalizePath, FILE_EXTENSION};
 use nargo::{
-    package::{Dependency, Package, PackageType},
+    package::{CompileSettings, Dependency, LintLevel, Package, PackageType},
     workspace::Workspace,
 };
 use noirc_driver::parse_expression_width;
 use noirc_frontend::graph::CrateName;
 use serde::Deserialize;
 
+mod cache;
 mod errors;
 mod git;
+mod lock;
 mod semver;
 
+pub use cache::set_resolve_cache_enabled;
 pub use errors::ManifestError;
 use git::clone_git_repo;
+pub use lock::{check_lock_file, checksum_package_sources, update_lock_file};
 
 /// Searches for a `Nargo.toml` file in the current directory and all parent directories.
 /// For example, if the current directory is `/workspace/package/src`, then this function
@@ -118,6 +122,9 @@ struct PackageConfig {
     package: PackageMetadata,
     #[serde(default)]
     dependencies: BTreeMap<String, DependencyConfig>,
+    /// Maps lint names to the level their warnings are reported at: `allow`, `warn` or `deny`.
+    #[serde(default)]
+    lints: BTreeMap<String, LintLevel>,
 }
 
 impl PackageConfig {
@@ -125,6 +132,7 @@ impl PackageConfig {
         &self,
         root_dir: &Path,
         processed: &mut Vec<String>,
+        max_depth: Option<usize>,
     ) -> Result<Package, ManifestError> {
         let name: CrateName = if let Some(name) = &self.package.name {
             name.parse().map_err(|_| ManifestError::InvalidPackageName {
@@ -141,7 +149,7 @@ impl PackageConfig {
                 toml: root_dir.join("Nargo.toml"),
                 name: name.into(),
             })?;
-            let resolved_dep = dep_config.resolve_to_dependency(root_dir, processed)?;
+            let resolved_dep = dep_config.resolve_to_dependency(root_dir, processed, max_depth)?;
 
             dependencies.insert(name, resolved_dep);
         }
@@ -190,6 +198,19 @@ impl PackageConfig {
             }
         };
 
+        let prelude_path = if let Some(prelude) = &self.package.prelude {
+            let prelude_path = root_dir.join(prelude);
+            if !prelude_path.is_file() {
+                return Err(ManifestError::MissingPreludeFile {
+                    toml: root_dir.join("Nargo.toml"),
+                    prelude: prelude_path,
+                });
+            }
+            Some(prelude_path)
+        } else {
+            None
+        };
+
         // If there is a package version, ensure that it is semver compatible
         if let Some(version) = &self.package.version {
             semver::parse_semver_compatible_version(version).map_err(|err| {
@@ -219,6 +240,13 @@ impl PackageConfig {
             name,
             dependencies,
             expression_width,
+            stdlib_revision: self.package.stdlib_rev.clone(),
+            prelude_path,
+            entry_function: self.package.entry_function.clone(),
+            lints: self.lints.clone(),
+            include: self.package.include.clone(),
+            exclude: self.package.exclude.clone(),
+            compile: self.package.compile.clone(),
         })
     }
 }
@@ -231,11 +259,17 @@ enum Config {
     Package {
         #[serde(flatten)]
         package_config: PackageConfig,
+        /// Maps the package's name to an explicit output path for its artifact.
+        #[serde(default)]
+        artifacts: BTreeMap<String, PathBuf>,
     },
     /// Represents a `Nargo.toml` with workspace fields.
     Workspace {
         #[serde(alias = "workspace")]
         workspace_config: WorkspaceConfig,
+        /// Maps the names of member packages to explicit output paths for their artifacts.
+        #[serde(default)]
+        artifacts: BTreeMap<String, PathBuf>,
     },
 }
 
@@ -268,6 +302,9 @@ struct WorkspaceConfig {
     members: Vec<PathBuf>,
     /// Specifies the default crate to interact with in the context (similarly to how we have nargo as the default crate in this repository).
     default_member: Option<PathBuf>,
+    /// Defaults for compiler flags, which members' `[package.compile]` sections can override.
+    #[serde(default)]
+    compile: CompileSettings,
 }
 
 #[allow(dead_code)]
@@ -288,6 +325,23 @@ struct PackageMetadata {
     compiler_version: Option<String>,
     license: Option<String>,
     expression_width: Option<String>,
+    // Pins the standard library to a specific git revision of the Noir repository.
+    #[serde(alias = "stdlib-rev")]
+    stdlib_rev: Option<String>,
+    // A Noir file whose public items are imported into every module of the package.
+    prelude: Option<PathBuf>,
+    // The function to compile as the program's entry point instead of `main`.
+    // `entry` already names the package's entry file, so this uses a separate key.
+    #[serde(alias = "entry-function")]
+    entry_function: Option<String>,
+    // The files or directories which `nargo package` restricts the package to.
+    include: Option<Vec<PathBuf>>,
+    // The files or directories which `nargo package` leaves out of the package.
+    #[serde(default)]
+    exclude: Vec<PathBuf>,
+    // Defaults for compiler flags, overriding those of the workspace's `[workspace.compile]` section.
+    #[serde(default)]
+    compile: CompileSettings,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -304,6 +358,7 @@ impl DependencyConfig {
         &self,
         pkg_root: &Path,
         processed: &mut Vec<String>,
+        max_depth: Option<usize>,
     ) -> Result<Dependency, ManifestError> {
         let dep = match self {
             Self::Github { git, tag, directory } => {
@@ -321,13 +376,13 @@ impl DependencyConfig {
                     dir_path
                 };
                 let toml_path = project_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
+                let package = resolve_package_from_toml(&toml_path, processed, max_depth)?;
                 Dependency::Remote { package }
             }
             Self::Path { path } => {
                 let dir_path = pkg_root.join(path);
                 let toml_path = dir_path.join("Nargo.toml");
-                let package = resolve_package_from_toml(&toml_path, processed)?;
+                let package = resolve_package_from_toml(&toml_path, processed, max_depth)?;
                 Dependency::Local { package }
             }
         };
@@ -345,30 +400,45 @@ impl DependencyConfig {
 fn toml_to_workspace(
     nargo_toml: NargoToml,
     package_selection: PackageSelection,
+    max_depth: Option<usize>,
 ) -> Result<Workspace, ManifestError> {
     let mut resolved = Vec::new();
-    let workspace = match nargo_toml.config {
-        Config::Package { package_config } => {
-            let member = package_config.resolve_to_package(&nargo_toml.root_dir, &mut resolved)?;
+    let toml_path = nargo_toml.root_dir.join("Nargo.toml");
+    let (workspace, artifacts) = match nargo_toml.config {
+        Config::Package { package_config, artifacts } => {
+            // Track the root package so that its dependencies are resolved at the same depth as a workspace member's.
+            resolved.push(nargo_toml.root_dir.join("Nargo.toml").to_string_lossy().into_owned());
+            let member = package_config.resolve_to_package(
+                &nargo_toml.root_dir,
+                &mut resolved,
+                max_depth,
+            )?;
             match &package_selection {
                 PackageSelection::Selected(selected_name) if selected_name != &member.name => {
                     return Err(ManifestError::MissingSelectedPackage(member.name))
                 }
-                _ => Workspace {
-                    root_dir: nargo_toml.root_dir,
-                    selected_package_index: Some(0),
-                    members: vec![member],
-                    is_assumed: false,
-                },
+                _ => (
+                    Workspace {
+                        root_dir: nargo_toml.root_dir,
+                        selected_package_index: Some(0),
+                        compile: member.compile.clone(),
+                        members: vec![member],
+                        is_assumed: false,
+                        artifact_paths: BTreeMap::new(),
+                    },
+                    artifacts,
+                ),
             }
         }
-        Config::Workspace { workspace_config } => {
+        Config::Workspace { workspace_config, artifacts } => {
             let mut members = Vec::new();
             let mut selected_package_index = None;
             for (index, member_path) in workspace_config.members.into_iter().enumerate() {
                 let package_root_dir = nargo_toml.root_dir.join(&member_path);
                 let package_toml_path = package_root_dir.join("Nargo.toml");
-                let member = resolve_package_from_toml(&package_toml_path, &mut resolved)?;
+                let mut member =
+                    resolve_package_from_toml(&package_toml_path, &mut resolved, max_depth)?;
+                member.compile = member.compile.or(&workspace_config.compile);
 
                 match &package_selection {
                     PackageSelection::Selected(selected_name) => {
@@ -406,14 +476,67 @@ fn toml_to_workspace(
                 PackageSelection::All => (),
             }
 
-            Workspace {
-                root_dir: nargo_toml.root_dir,
-                members,
-                selected_package_index,
-                is_assumed: false,
-            }
+            (
+                Workspace {
+                    root_dir: nargo_toml.root_dir,
+                    members,
+                    selected_package_index,
+                    is_assumed: false,
+                    artifact_paths: BTreeMap::new(),
+                    compile: workspace_config.compile,
+                },
+                artifacts,
+            )
         }
     };
+    let workspace = resolve_artifact_paths(workspace, artifacts, &toml_path)?;
+
+    Ok(workspace)
+}
+
+/// Validates the `[artifacts]` section of the workspace's manifest and records the mapped paths on the workspace.
+///
+/// Mapped paths are relative to the workspace's root directory and always point to a JSON file.
+fn resolve_artifact_paths(
+    mut workspace: Workspace,
+    artifacts: BTreeMap<String, PathBuf>,
+    toml_path: &Path,
+) -> Result<Workspace, ManifestError> {
+    for (name, path) in artifacts {
+        let Some(member) = workspace.members.iter().find(|member| member.name.to_string() == name)
+        else {
+            return Err(ManifestError::UnknownArtifactPackage {
+                toml: toml_path.to_path_buf(),
+                name,
+            });
+        };
+        if member.is_library() {
+            return Err(ManifestError::LibraryArtifactPath {
+                toml: toml_path.to_path_buf(),
+                name: member.name.clone(),
+            });
+        }
+        let path = workspace.root_dir.join(path).with_extension("json").normalize();
+        workspace.artifact_paths.insert(member.name.clone(), path);
+    }
+
+    // Mapped paths may not collide with each other, nor with the default path of an unmapped program.
+    let mut artifact_paths: BTreeMap<PathBuf, &CrateName> = BTreeMap::new();
+    for member in workspace.members.iter().filter(|member| !member.is_library()) {
+        let path = match workspace.package_artifact_path(member) {
+            Some(path) => path.clone(),
+            None if member.is_binary() => workspace.default_package_build_path(member),
+            None => continue,
+        };
+        if let Some(other) = artifact_paths.insert(path.clone(), &member.name) {
+            return Err(ManifestError::ArtifactPathCollision {
+                toml: toml_path.to_path_buf(),
+                path,
+                first: other.clone(),
+                second: member.name.clone(),
+            });
+        }
+    }
 
     Ok(workspace)
 }
@@ -433,6 +556,7 @@ fn read_toml(toml_path: &Path) -> Result<NargoToml, ManifestError> {
 fn resolve_package_from_toml(
     toml_path: &Path,
     processed: &mut Vec<String>,
+    max_depth: Option<usize>,
 ) -> Result<Package, ManifestError> {
     // Checks for cyclic dependencies
     let str_path = toml_path.to_str().expect("ICE - path is empty");
@@ -448,6 +572,17 @@ fn resolve_package_from_toml(
         message += str_path;
         return Err(ManifestError::CyclicDependency { cycle: message });
     }
+    // Checks that the chain of dependencies leading to this package isn't too long
+    if let Some(max_depth) = max_depth {
+        if processed.len() > max_depth {
+            let mut chain = String::new();
+            for toml in processed.iter() {
+                chain += &format!("{} referencing ", toml);
+            }
+            chain += str_path;
+            return Err(ManifestError::DependencyDepthExceeded { max_depth, chain });
+        }
+    }
     // Adds the package to the set of resolved packages
     if let Some(str) = toml_path.to_str() {
         processed.push(str.to_string());
@@ -456,8 +591,8 @@ fn resolve_package_from_toml(
     let nargo_toml = read_toml(toml_path)?;
 
     let result = match nargo_toml.config {
-        Config::Package { package_config } => {
-            package_config.resolve_to_package(&nargo_toml.root_dir, processed)
+        Config::Package { package_config, .. } => {
+            package_config.resolve_to_package(&nargo_toml.root_dir, processed, max_depth)
         }
         Config::Workspace { .. } => {
             Err(ManifestError::UnexpectedWorkspace(toml_path.to_path_buf()))
@@ -469,7 +604,7 @@ fn resolve_package_from_toml(
     result
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PackageSelection {
     Selected(CrateName),
     DefaultOrAll,
@@ -482,8 +617,71 @@ pub fn resolve_workspace_from_toml(
     package_selection: PackageSelection,
     current_compiler_version: Option<String>,
 ) -> Result<Workspace, ManifestError> {
-    let nargo_toml = read_toml(toml_path)?;
-    let workspace = toml_to_workspace(nargo_toml, package_selection)?;
+    resolve_workspace_from_toml_with_max_depth(
+        toml_path,
+        package_selection,
+        current_compiler_version,
+        None,
+    )
+}
+
+/// Resolves a Nargo.toml file into a `Workspace` struct, erroring if any chain of dependencies
+/// leading from a workspace member is longer than `max_depth`.
+pub fn resolve_workspace_from_toml_with_max_depth(
+    toml_path: &Path,
+    package_selection: PackageSelection,
+    current_compiler_version: Option<String>,
+    max_depth: Option<usize>,
+) -> Result<Workspace, ManifestError> {
+    let cache_path = cache::resolve_cache_enabled()
+        .then(|| cache::cache_path(toml_path, &package_selection, max_depth));
+    let cached_workspace = cache_path.as_deref().and_then(cache::read_cached_workspace);
+    let workspace = match cached_workspace {
+        Some(workspace) => workspace,
+        None => {
+            let nargo_toml = read_toml(toml_path)?;
+            let workspace = toml_to_workspace(nargo_toml, package_selection, max_depth)?;
+            if let Some(cache_path) = &cache_path {
+                cache::write_cached_workspace(cache_path, toml_path, &workspace);
+            }
+            workspace
+        }
+    };
+    if let Some(current_compiler_version) = current_compiler_version {
+        semver::semver_check_workspace(&workspace, current_compiler_version)?;
+    }
+    Ok(workspace)
+}
+
+/// Resolves the contents of a `Nargo.toml` which doesn't exist on disk into a `Workspace` struct,
+/// for manifests which are generated by other tools.
+///
+/// The package's sources are taken from `src_dir`, which also takes the place of the manifest's directory
+/// when resolving relative paths. Unless the manifest sets an `entry`, the package's entry file is
+/// `main.nr` or `lib.nr` directly within `src_dir`.
+pub fn resolve_workspace_from_toml_str(
+    toml: &str,
+    src_dir: &Path,
+    package_selection: PackageSelection,
+    current_compiler_version: Option<String>,
+    max_depth: Option<usize>,
+) -> Result<Workspace, ManifestError> {
+    let mut config = Config::try_from(toml)?;
+    if let Config::Package { package_config, .. } = &mut config {
+        if package_config.package.entry.is_none() {
+            let entry = match package_config.package.package_type.as_deref() {
+                Some("lib") => Some("lib"),
+                Some("bin" | "contract") => Some("main"),
+                // Leave invalid or missing types to be reported when resolving the package.
+                _ => None,
+            };
+            package_config.package.entry =
+                entry.map(|entry| Path::new(entry).with_extension(FILE_EXTENSION));
+        }
+    }
+
+    let nargo_toml = NargoToml { root_dir: src_dir.normalize(), config };
+    let workspace = toml_to_workspace(nargo_toml, package_selection, max_depth)?;
     if let Some(current_compiler_version) = current_compiler_version {
         semver::semver_check_workspace(&workspace, current_compiler_version)?;
     }
@@ -559,3 +757,107 @@ fn parse_package_expression_width_toml() {
     assert!(Config::try_from(String::from(src)).is_ok());
     assert!(Config::try_from(src).is_ok());
 }
+
+#[test]
+fn parse_package_stdlib_rev_toml() {
+    let src = r#"
+    [package]
+    name = "test"
+    type = "bin"
+    authors = [""]
+    stdlib-rev = "86c7704"
+    "#;
+
+    let Config::Package { package_config, .. } = Config::try_from(src).unwrap() else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.package.stdlib_rev.as_deref(), Some("86c7704"));
+}
+
+#[test]
+fn parse_package_lints_toml() {
+    let src = r#"
+    [package]
+    name = "test"
+    type = "bin"
+    authors = [""]
+
+    [lints]
+    unused_variables = "deny"
+    dead_code = "allow"
+    "#;
+
+    let Config::Package { package_config, .. } = Config::try_from(src).unwrap() else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.lints.get("unused_variables"), Some(&LintLevel::Deny));
+    assert_eq!(package_config.lints.get("dead_code"), Some(&LintLevel::Allow));
+
+    let invalid_level = src.replace(r#""allow""#, r#""forbid""#);
+    assert!(Config::try_from(invalid_level.as_str()).is_err());
+}
+
+#[test]
+fn parse_package_prelude_toml() {
+    let src = r#"
+    [package]
+    name = "test"
+    type = "bin"
+    authors = [""]
+    prelude = "src/prelude.nr"
+    "#;
+
+    let Config::Package { package_config, .. } = Config::try_from(src).unwrap() else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.package.prelude, Some(PathBuf::from("src/prelude.nr")));
+}
+
+#[test]
+fn parse_workspace_artifacts_toml() {
+    let src = r#"
+    [workspace]
+    members = ["a", "b"]
+
+    [artifacts]
+    a = "deploy/a.json"
+    "#;
+
+    let Config::Workspace { artifacts, .. } = Config::try_from(src).unwrap() else {
+        panic!("expected a workspace config");
+    };
+    assert_eq!(artifacts.get("a"), Some(&PathBuf::from("deploy/a.json")));
+}
+
+#[test]
+fn parse_compile_settings_toml() {
+    let workspace_src = r#"
+    [workspace]
+    members = ["a"]
+
+    [workspace.compile]
+    deny_warnings = true
+    print_acir = true
+    "#;
+    let package_src = r#"
+    [package]
+    name = "a"
+    type = "bin"
+
+    [package.compile]
+    deny_warnings = false
+    "#;
+
+    let Config::Workspace { workspace_config, .. } = Config::try_from(workspace_src).unwrap()
+    else {
+        panic!("expected a workspace config");
+    };
+    let Config::Package { package_config, .. } = Config::try_from(package_src).unwrap() else {
+        panic!("expected a package config");
+    };
+
+    let settings = package_config.package.compile.or(&workspace_config.compile);
+    assert_eq!(settings.deny_warnings, Some(false));
+    assert_eq!(settings.print_acir, Some(true));
+    assert_eq!(settings.show_ssa, None);
+}
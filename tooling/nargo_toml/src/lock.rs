@@ -0,0 +1,409 @@
+//! `Nargo.lock` records exactly what each dependency of a workspace resolved to, so that everyone
+//! building the workspace compiles the same dependency sources.
+//!
+//! Git dependencies are fetched by tag, and a tag can be moved to another commit after it's first fetched.
+//! The lock file pins each git dependency to the commit it resolved to along with a checksum of its sources,
+//! and resolving a workspace whose git dependencies no longer match the lock file is an error until
+//! the lock file is regenerated with [update_lock_file].
+//!
+//! Path dependencies are recorded so that the lock file lists every package in the dependency graph,
+//! but they are never pinned since their sources are part of the workspace.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use nargo::{
+    constants::{LOCK_FILE, TARGET_DIR},
+    package::{Dependency, Package},
+    workspace::Workspace,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    git::{nargo_crates, was_fetched},
+    read_toml, toml_to_workspace, ManifestError, PackageSelection,
+};
+
+/// The version of the lock file format, which is bumped whenever it changes incompatibly.
+const LOCK_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct LockFile {
+    version: u32,
+    #[serde(default, rename = "package")]
+    packages: Vec<LockedPackage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct LockedPackage {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// Where a git dependency was fetched from, as `git+<url>?tag=<tag>#<commit>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    /// The SHA-256 digest of a git dependency's sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+    /// The names of the package's own dependencies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<String>,
+}
+
+impl LockFile {
+    /// The lock file with the sources and checksums of git dependencies left out,
+    /// which is what can be compared without reading their checkouts.
+    fn without_sources(&self) -> LockFile {
+        let packages = self
+            .packages
+            .iter()
+            .map(|package| LockedPackage { source: None, checksum: None, ..package.clone() })
+            .collect::<BTreeSet<_>>();
+        LockFile { version: self.version, packages: packages.into_iter().collect() }
+    }
+}
+
+impl LockedPackage {
+    /// The part of `source` which is taken from the manifest, without the commit it resolved to.
+    fn requested_source(&self) -> Option<&str> {
+        self.source.as_deref().map(|source| source.split_once('#').map_or(source, |(url, _)| url))
+    }
+}
+
+/// Checks that the git dependencies of `workspace` match its `Nargo.lock`, writing the lock file
+/// if there isn't one yet or if dependencies have been added or removed since it was written.
+///
+/// A dependency which is in the lock file but now resolves to a different commit or different sources
+/// is an error, as the workspace would no longer build the same way as when it was locked.
+///
+/// Checkouts only change when they're fetched, so the git dependencies are only read and hashed again
+/// if one of them was fetched while resolving the workspace or the dependency graph changed.
+pub fn check_lock_file(workspace: &Workspace) -> Result<(), ManifestError> {
+    let lock_path = workspace.root_dir.join(LOCK_FILE);
+    let locked = match std::fs::read_to_string(&lock_path) {
+        Ok(contents) => Some(
+            toml::from_str::<LockFile>(&contents)
+                .map_err(|err| ManifestError::MalformedLockFile(lock_path.clone(), err))?,
+        ),
+        Err(_) => None,
+    };
+
+    if let Some(locked) = &locked {
+        let fetched_any = remote_packages(&workspace.members)
+            .iter()
+            .any(|package| was_fetched(&package.root_dir));
+        if !fetched_any && locked.without_sources() == dependency_graph(workspace) {
+            return Ok(());
+        }
+    }
+
+    let resolved = lock_workspace(workspace)?;
+    if let Some(locked) = locked {
+        if locked == resolved {
+            return Ok(());
+        }
+
+        for package in &resolved.packages {
+            let Some(locked_package) = locked.packages.iter().find(|locked_package| {
+                locked_package.name == package.name
+                    && locked_package.requested_source() == package.requested_source()
+            }) else {
+                continue;
+            };
+            if locked_package.source != package.source
+                || locked_package.checksum != package.checksum
+            {
+                return Err(ManifestError::LockFileMismatch {
+                    lock_file: lock_path,
+                    name: package.name.clone(),
+                });
+            }
+        }
+    }
+
+    write_lock_file(&lock_path, &resolved)
+}
+
+/// Fetches the git dependencies of the workspace at `toml_path` again and overwrites its `Nargo.lock`
+/// with what they now resolve to.
+pub fn update_lock_file(toml_path: &Path) -> Result<Workspace, ManifestError> {
+    let workspace = toml_to_workspace(read_toml(toml_path)?, PackageSelection::All, None)?;
+
+    // Checkouts are reused whenever a tag has already been fetched, so they have to be removed
+    // for tags which have moved to be fetched again.
+    let mut checkouts = BTreeSet::new();
+    for package in remote_packages(&workspace.members) {
+        if let Some(checkout) = git_output(&package.root_dir, &["rev-parse", "--show-toplevel"]) {
+            let checkout = PathBuf::from(checkout);
+            if checkout.starts_with(nargo_crates()) {
+                checkouts.insert(checkout);
+            }
+        }
+    }
+    for checkout in checkouts {
+        std::fs::remove_dir_all(&checkout)
+            .map_err(|err| ManifestError::GitError(format!("{}: {err}", checkout.display())))?;
+    }
+
+    let workspace = toml_to_workspace(read_toml(toml_path)?, PackageSelection::All, None)?;
+    write_lock_file(&workspace.root_dir.join(LOCK_FILE), &lock_workspace(&workspace)?)?;
+    Ok(workspace)
+}
+
+fn write_lock_file(lock_path: &Path, lock_file: &LockFile) -> Result<(), ManifestError> {
+    let contents = toml::to_string(lock_file).expect("lock files are always serializable");
+    let contents = format!(
+        "# This file is automatically generated by nargo and is not intended for manual editing.\n{contents}"
+    );
+    std::fs::write(lock_path, contents)
+        .map_err(|err| ManifestError::LockFileWriteFailed(lock_path.to_path_buf(), err))
+}
+
+/// Same as [lock_workspace], but without reading the checkouts of git dependencies.
+fn dependency_graph(workspace: &Workspace) -> LockFile {
+    lock_packages(workspace, |_| Ok((None, None))).expect("no checkouts are read")
+}
+
+/// Records every package which the members of `workspace` depend on, sorted so that
+/// the same dependency graph always produces the same lock file.
+fn lock_workspace(workspace: &Workspace) -> Result<LockFile, ManifestError> {
+    lock_packages(workspace, |package| {
+        Ok((Some(git_source(package)?), Some(checksum_package_sources(&package.root_dir))))
+    })
+}
+
+/// Records every package which the members of `workspace` depend on, using `lock_remote`
+/// to find the source and checksum of each git dependency.
+fn lock_packages(
+    workspace: &Workspace,
+    lock_remote: impl Fn(&Package) -> Result<(Option<String>, Option<String>), ManifestError>,
+) -> Result<LockFile, ManifestError> {
+    let mut packages = BTreeSet::new();
+    let mut pending: Vec<(&Package, bool)> = workspace
+        .members
+        .iter()
+        .flat_map(|member| member.dependencies.values())
+        .map(dependency_package)
+        .collect();
+    let mut visited = BTreeSet::new();
+    while let Some((package, is_remote)) = pending.pop() {
+        if !visited.insert(package.root_dir.clone()) {
+            continue;
+        }
+
+        let (source, checksum) = if is_remote { lock_remote(package)? } else { (None, None) };
+        packages.insert(LockedPackage {
+            name: package.name.to_string(),
+            version: package.version.clone(),
+            source,
+            checksum,
+            dependencies: package.dependencies.keys().map(ToString::to_string).collect(),
+        });
+        pending.extend(package.dependencies.values().map(dependency_package));
+    }
+
+    Ok(LockFile { version: LOCK_FILE_VERSION, packages: packages.into_iter().collect() })
+}
+
+fn dependency_package(dependency: &Dependency) -> (&Package, bool) {
+    match dependency {
+        Dependency::Local { package } => (package, false),
+        Dependency::Remote { package } => (package, true),
+    }
+}
+
+fn remote_packages(members: &[Package]) -> Vec<&Package> {
+    let mut remote = Vec::new();
+    let mut pending: Vec<&Package> = members.iter().collect();
+    while let Some(package) = pending.pop() {
+        for dependency in package.dependencies.values() {
+            let (dependency, is_remote) = dependency_package(dependency);
+            if is_remote {
+                remote.push(dependency);
+            }
+            pending.push(dependency);
+        }
+    }
+    remote
+}
+
+/// Describes where a git dependency was fetched from, as `git+<url>?tag=<tag>#<commit>`.
+fn git_source(package: &Package) -> Result<String, ManifestError> {
+    let root_dir = &package.root_dir;
+    let git_error = || {
+        ManifestError::GitError(format!(
+            "Failed to read the git checkout at {}",
+            root_dir.display()
+        ))
+    };
+
+    let url = git_output(root_dir, &["remote", "get-url", "origin"]).ok_or_else(git_error)?;
+    let commit = git_output(root_dir, &["rev-parse", "HEAD"]).ok_or_else(git_error)?;
+    let source = match git_output(root_dir, &["describe", "--tags", "--exact-match", "HEAD"]) {
+        Some(tag) => format!("git+{url}?tag={tag}#{commit}"),
+        None => format!("git+{url}#{commit}"),
+    };
+    Ok(source)
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Hashes the relative path and contents of every file within `root_dir`, other than
+/// git metadata and build output.
+///
+/// This is the checksum recorded for git dependencies in `Nargo.lock`.
+pub fn checksum_package_sources(root_dir: &Path) -> String {
+    let mut files = Vec::new();
+    collect_files(root_dir, &mut files);
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative_path = file.strip_prefix(root_dir).unwrap_or(&file);
+        hasher.update(relative_path.to_string_lossy().as_bytes());
+        hasher.update(std::fs::read(&file).unwrap_or_default());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if entry.file_name() != ".git" && entry.file_name() != TARGET_DIR {
+                collect_files(&path, files);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::process::Command;
+
+    use nargo::{constants::LOCK_FILE, package::Dependency, workspace::Workspace};
+
+    use super::{check_lock_file, LockFile};
+    use crate::{
+        git::record_fetched, resolve_workspace_from_toml, ManifestError, PackageSelection,
+    };
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=nargo", "-c", "user.email=nargo@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    /// Writes a binary package depending on a library which is checked out from git at tag `v0.1.0`,
+    /// returning the workspace of the binary package.
+    fn workspace_with_git_dependency(dir: &Path) -> Workspace {
+        let files = [
+            (
+                "bin/Nargo.toml",
+                "[package]\nname = \"bin\"\ntype = \"bin\"\n\n[dependencies]\nlib = { path = \"../lib\" }\n",
+            ),
+            ("bin/src/main.nr", "fn main() {}\n"),
+            ("lib/Nargo.toml", "[package]\nname = \"lib\"\ntype = \"lib\"\n"),
+            ("lib/src/lib.nr", "pub fn foo() {}\n"),
+        ];
+        for (path, contents) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+        let lib_dir = dir.join("lib");
+        git(&lib_dir, &["init", "--quiet"]);
+        git(&lib_dir, &["add", "."]);
+        git(&lib_dir, &["commit", "--quiet", "-m", "lib"]);
+        git(&lib_dir, &["tag", "v0.1.0"]);
+        git(&lib_dir, &["remote", "add", "origin", "https://example.com/lib"]);
+
+        // The library is resolved as a path dependency, then treated as though it had been cloned.
+        let mut workspace = resolve_workspace_from_toml(
+            &dir.join("bin").join("Nargo.toml"),
+            PackageSelection::All,
+            None,
+        )
+        .unwrap();
+        for dependency in workspace.members[0].dependencies.values_mut() {
+            if let Dependency::Local { package } = dependency {
+                *dependency = Dependency::Remote { package: package.clone() };
+            }
+        }
+        workspace
+    }
+
+    fn read_lock_file(workspace: &Workspace) -> LockFile {
+        let contents = std::fs::read_to_string(workspace.root_dir.join(LOCK_FILE)).unwrap();
+        toml::from_str(&contents).unwrap()
+    }
+
+    #[test]
+    fn missing_lock_file_is_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace_with_git_dependency(dir.path());
+
+        check_lock_file(&workspace).unwrap();
+
+        let lock_file = read_lock_file(&workspace);
+        assert_eq!(lock_file.packages.len(), 1);
+        let lib = &lock_file.packages[0];
+        assert_eq!(lib.name, "lib");
+        assert!(lib
+            .source
+            .as_ref()
+            .unwrap()
+            .starts_with("git+https://example.com/lib?tag=v0.1.0#"));
+        assert!(lib.checksum.is_some());
+    }
+
+    #[test]
+    fn lock_file_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace_with_git_dependency(dir.path());
+        check_lock_file(&workspace).unwrap();
+
+        let lock_file = read_lock_file(&workspace);
+        let serialized = toml::to_string(&lock_file).unwrap();
+        assert_eq!(toml::from_str::<LockFile>(&serialized).unwrap(), lock_file);
+
+        // Checking an up to date lock file leaves it untouched.
+        let lock_path = workspace.root_dir.join(LOCK_FILE);
+        let contents = std::fs::read_to_string(&lock_path).unwrap();
+        check_lock_file(&workspace).unwrap();
+        assert_eq!(std::fs::read_to_string(&lock_path).unwrap(), contents);
+    }
+
+    #[test]
+    fn changed_dependency_sources_are_a_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = workspace_with_git_dependency(dir.path());
+        check_lock_file(&workspace).unwrap();
+
+        // As though the tag had moved and the library was fetched again with different sources.
+        let lib_dir = dir.path().join("lib");
+        std::fs::write(lib_dir.join("src").join("lib.nr"), "pub fn bar() {}\n").unwrap();
+        record_fetched(lib_dir);
+
+        let result = check_lock_file(&workspace);
+        assert!(
+            matches!(result, Err(ManifestError::LockFileMismatch { name, .. }) if name == "lib")
+        );
+    }
+}
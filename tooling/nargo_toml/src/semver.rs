@@ -89,7 +89,7 @@ fn strip_build_meta_data(version: &Version) -> String {
 mod tests {
     use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
 
-    use nargo::package::PackageType;
+    use nargo::package::{CompileSettings, PackageType};
     use noirc_frontend::graph::CrateName;
 
     use super::*;
@@ -107,6 +107,13 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            stdlib_revision: None,
+            prelude_path: None,
+            entry_function: None,
+            lints: BTreeMap::new(),
+            include: None,
+            exclude: Vec::new(),
+            compile: CompileSettings::default(),
         };
         if let Err(err) = semver_check_package(&package, &compiler_version) {
             panic!("semver check should have passed. compiler version is 0.1.0 and required version from the package is 0.1.0\n error: {err:?}")
@@ -139,6 +146,13 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            stdlib_revision: None,
+            prelude_path: None,
+            entry_function: None,
+            lints: BTreeMap::new(),
+            include: None,
+            exclude: Vec::new(),
+            compile: CompileSettings::default(),
         };
 
         let valid_dependency = Package {
@@ -150,6 +164,13 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            stdlib_revision: None,
+            prelude_path: None,
+            entry_function: None,
+            lints: BTreeMap::new(),
+            include: None,
+            exclude: Vec::new(),
+            compile: CompileSettings::default(),
         };
         let invalid_dependency = Package {
             compiler_required_version: Some("0.2.0".to_string()),
@@ -160,6 +181,13 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            stdlib_revision: None,
+            prelude_path: None,
+            entry_function: None,
+            lints: BTreeMap::new(),
+            include: None,
+            exclude: Vec::new(),
+            compile: CompileSettings::default(),
         };
 
         package.dependencies.insert(
@@ -201,6 +229,13 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            stdlib_revision: None,
+            prelude_path: None,
+            entry_function: None,
+            lints: BTreeMap::new(),
+            include: None,
+            exclude: Vec::new(),
+            compile: CompileSettings::default(),
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
@@ -221,6 +256,13 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            stdlib_revision: None,
+            prelude_path: None,
+            entry_function: None,
+            lints: BTreeMap::new(),
+            include: None,
+            exclude: Vec::new(),
+            compile: CompileSettings::default(),
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
@@ -241,6 +283,13 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            stdlib_revision: None,
+            prelude_path: None,
+            entry_function: None,
+            lints: BTreeMap::new(),
+            include: None,
+            exclude: Vec::new(),
+            compile: CompileSettings::default(),
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
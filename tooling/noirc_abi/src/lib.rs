@@ -30,6 +30,7 @@ mod arbitrary;
 
 pub mod errors;
 pub mod input_parser;
+pub mod profile;
 mod serialization;
 
 /// A map from the fields in an TOML/JSON file which correspond to some ABI to their values
@@ -0,0 +1,143 @@
+//! Profiles of the ABI types which an integration, such as a bindings generator, is able to handle.
+
+use std::str::FromStr;
+
+use crate::{Abi, AbiType, Sign};
+
+/// A subset of the ABI types which a program's inputs and outputs can be checked against.
+///
+/// Further profiles can be supported by adding a variant and describing the types it supports in [`AbiProfile::supports`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbiProfile {
+    /// Types which map directly onto a static Solidity type: fields, unsigned integers, booleans
+    /// and arrays of these.
+    Solidity,
+}
+
+const RETURN_VALUE_ITEM: &str = "return value";
+
+/// A parameter or return value whose type isn't supported by an [`AbiProfile`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedAbiType {
+    /// The name of the parameter, or `return value`.
+    pub item: String,
+    /// The unsupported type, which may be nested within the item's type.
+    pub typ: AbiType,
+}
+
+impl AbiProfile {
+    pub const ALL: [AbiProfile; 1] = [AbiProfile::Solidity];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AbiProfile::Solidity => "solidity",
+        }
+    }
+
+    /// Returns whether the profile supports `typ`, including any types nested within it.
+    pub fn supports(&self, typ: &AbiType) -> bool {
+        self.find_unsupported(typ).is_none()
+    }
+
+    /// Returns each parameter and return value of `abi` whose type isn't supported by the profile.
+    pub fn unsupported_types(&self, abi: &Abi) -> Vec<UnsupportedAbiType> {
+        let parameters =
+            abi.parameters.iter().map(|parameter| (parameter.name.as_str(), &parameter.typ));
+        let return_value =
+            abi.return_type.iter().map(|return_type| (RETURN_VALUE_ITEM, &return_type.abi_type));
+        parameters
+            .chain(return_value)
+            .filter_map(|(item, typ)| {
+                self.find_unsupported(typ)
+                    .map(|typ| UnsupportedAbiType { item: item.to_string(), typ: typ.clone() })
+            })
+            .collect()
+    }
+
+    fn find_unsupported<'a>(&self, typ: &'a AbiType) -> Option<&'a AbiType> {
+        match self {
+            AbiProfile::Solidity => match typ {
+                AbiType::Field | AbiType::Boolean => None,
+                AbiType::Integer { sign: Sign::Unsigned, .. } => None,
+                AbiType::Array { typ: element_type, .. } => self.find_unsupported(element_type),
+                AbiType::Integer { sign: Sign::Signed, .. }
+                | AbiType::Struct { .. }
+                | AbiType::Tuple { .. }
+                | AbiType::String { .. } => Some(typ),
+            },
+        }
+    }
+}
+
+impl FromStr for AbiProfile {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        AbiProfile::ALL.into_iter().find(|profile| profile.name() == input).ok_or_else(|| {
+            let names: Vec<&str> = AbiProfile::ALL.iter().map(AbiProfile::name).collect();
+            format!("unknown ABI profile `{input}`, expected one of: {}", names.join(", "))
+        })
+    }
+}
+
+impl std::fmt::Display for AbiProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Formats `typ` the way it's written in Noir source.
+pub fn abi_type_to_string(typ: &AbiType) -> String {
+    match typ {
+        AbiType::Field => "Field".to_string(),
+        AbiType::Boolean => "bool".to_string(),
+        AbiType::Integer { sign: Sign::Unsigned, width } => format!("u{width}"),
+        AbiType::Integer { sign: Sign::Signed, width } => format!("i{width}"),
+        AbiType::Array { length, typ } => format!("[{}; {length}]", abi_type_to_string(typ)),
+        AbiType::String { length } => format!("str<{length}>"),
+        AbiType::Struct { path, .. } => path.clone(),
+        AbiType::Tuple { fields } => {
+            let fields: Vec<String> = fields.iter().map(abi_type_to_string).collect();
+            format!("({})", fields.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility, Sign};
+
+    use super::{AbiProfile, UnsupportedAbiType};
+
+    #[test]
+    fn solidity_profile_rejects_nested_signed_integers() {
+        let signed = AbiType::Integer { sign: Sign::Signed, width: 32 };
+        let abi = Abi {
+            parameters: vec![
+                AbiParameter {
+                    name: "x".to_string(),
+                    typ: AbiType::Array {
+                        length: 2,
+                        typ: Box::new(AbiType::Integer { sign: Sign::Unsigned, width: 8 }),
+                    },
+                    visibility: AbiVisibility::Private,
+                },
+                AbiParameter {
+                    name: "y".to_string(),
+                    typ: AbiType::Array { length: 3, typ: Box::new(signed.clone()) },
+                    visibility: AbiVisibility::Public,
+                },
+            ],
+            return_type: Some(AbiReturnType {
+                abi_type: AbiType::Field,
+                visibility: AbiVisibility::Public,
+            }),
+            error_types: Default::default(),
+        };
+
+        assert_eq!(
+            AbiProfile::Solidity.unsupported_types(&abi),
+            vec![UnsupportedAbiType { item: "y".to_string(), typ: signed }]
+        );
+    }
+}
@@ -1,18 +1,20 @@
 use acvm::{acir::circuit::Program, FieldElement};
-use noirc_abi::{Abi, AbiType, AbiValue};
+use noirc_abi::{Abi, AbiParameter, AbiReturnType, AbiType, AbiValue};
 use noirc_driver::{CompiledContract, CompiledContractOutputs, ContractFunction};
 use serde::{Deserialize, Serialize};
 
 use noirc_driver::DebugFile;
 use noirc_errors::debug_info::ProgramDebugInfo;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
+
+use crate::program::UnoptimizedDebug;
 
 use fm::FileId;
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ContractOutputsArtifact {
-    pub structs: HashMap<String, Vec<AbiType>>,
-    pub globals: HashMap<String, Vec<AbiValue>>,
+    pub structs: BTreeMap<String, Vec<AbiType>>,
+    pub globals: BTreeMap<String, Vec<AbiValue>>,
 }
 
 impl From<CompiledContractOutputs> for ContractOutputsArtifact {
@@ -33,6 +35,13 @@ pub struct ContractArtifact {
     pub outputs: ContractOutputsArtifact,
     /// Map of file Id to the source code so locations in debug info can be mapped to source code they point to.
     pub file_map: BTreeMap<FileId, DebugFile>,
+    /// The `--opt-level` the functions were optimized with, if it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimization_level: Option<u8>,
+    /// Functions whose visibility was overridden with `--make-public` or `--make-internal`,
+    /// mapped to the visibility they were compiled with.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub visibility_overrides: BTreeMap<String, String>,
 }
 
 impl From<CompiledContract> for ContractArtifact {
@@ -43,6 +52,86 @@ impl From<CompiledContract> for ContractArtifact {
             functions: contract.functions.into_iter().map(ContractFunctionArtifact::from).collect(),
             outputs: contract.outputs.into(),
             file_map: contract.file_map,
+            optimization_level: None,
+            visibility_overrides: BTreeMap::new(),
+        }
+    }
+}
+
+impl ContractArtifact {
+    /// Replaces each function which has the same name and hash as one in `previous`
+    /// with the function from `previous`, so that unchanged functions keep the exact data they were saved with.
+    /// Functions which only exist in `previous` are dropped.
+    pub fn reuse_unchanged_functions(&mut self, previous: ContractArtifact) {
+        let mut previous_functions: BTreeMap<String, ContractFunctionArtifact> = previous
+            .functions
+            .into_iter()
+            .map(|function| (function.name.clone(), function))
+            .collect();
+        for function in &mut self.functions {
+            let unchanged = previous_functions
+                .get(&function.name)
+                .map_or(false, |previous_function| previous_function.hash == function.hash);
+            if unchanged {
+                *function = previous_functions.remove(&function.name).unwrap();
+            }
+        }
+    }
+}
+
+impl From<ContractArtifact> for CompiledContract {
+    fn from(contract: ContractArtifact) -> Self {
+        CompiledContract {
+            noir_version: contract.noir_version,
+            name: contract.name,
+            functions: contract.functions.into_iter().map(ContractFunction::from).collect(),
+            outputs: CompiledContractOutputs {
+                structs: contract.outputs.structs,
+                globals: contract.outputs.globals,
+            },
+            file_map: contract.file_map,
+            warnings: vec![],
+        }
+    }
+}
+
+/// The interface of a contract, describing the signature of each of its functions,
+/// for use by frontends which don't need the functions' bytecode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractAbiArtifact {
+    /// Version of noir used to compile this contract
+    pub noir_version: String,
+    /// The name of the contract.
+    pub name: String,
+    pub functions: Vec<ContractFunctionAbi>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractFunctionAbi {
+    pub name: String,
+    pub is_unconstrained: bool,
+    pub custom_attributes: Vec<String>,
+    /// The function's parameters, with their types and whether they're public or private.
+    pub parameters: Vec<AbiParameter>,
+    pub return_type: Option<AbiReturnType>,
+}
+
+impl From<&ContractArtifact> for ContractAbiArtifact {
+    fn from(contract: &ContractArtifact) -> Self {
+        ContractAbiArtifact {
+            noir_version: contract.noir_version.clone(),
+            name: contract.name.clone(),
+            functions: contract
+                .functions
+                .iter()
+                .map(|function| ContractFunctionAbi {
+                    name: function.name.clone(),
+                    is_unconstrained: function.is_unconstrained,
+                    custom_attributes: function.custom_attributes.clone(),
+                    parameters: function.abi.parameters.clone(),
+                    return_type: function.abi.return_type.clone(),
+                })
+                .collect(),
         }
     }
 }
@@ -55,6 +144,13 @@ impl From<CompiledContract> for ContractArtifact {
 pub struct ContractFunctionArtifact {
     pub name: String,
 
+    /// Hash of the monomorphized function from which this [`ContractFunctionArtifact`] was compiled.
+    ///
+    /// Used to skip recompiling functions whose source code hasn't changed since the last compilation.
+    /// Artifacts written before functions were hashed have no hash, so none of their functions are reused.
+    #[serde(default)]
+    pub hash: u64,
+
     pub is_unconstrained: bool,
 
     pub custom_attributes: Vec<String>,
@@ -73,19 +169,85 @@ pub struct ContractFunctionArtifact {
     )]
     pub debug_symbols: ProgramDebugInfo,
 
+    /// Names of the functions in the program, kept so that functions reused from the artifact
+    /// are still debugged and benchmarked with their names.
+    #[serde(default)]
+    pub names: Vec<String>,
+
     pub brillig_names: Vec<String>,
+
+    /// The function as it was before optimization, if it was compiled with `--keep-unoptimized-debug`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unoptimized_debug: Option<UnoptimizedDebug>,
 }
 
 impl From<ContractFunction> for ContractFunctionArtifact {
     fn from(func: ContractFunction) -> Self {
         ContractFunctionArtifact {
             name: func.name,
+            hash: func.hash,
             is_unconstrained: func.is_unconstrained,
             custom_attributes: func.custom_attributes,
             abi: func.abi,
             bytecode: func.bytecode,
+            names: func.names,
             brillig_names: func.brillig_names,
             debug_symbols: ProgramDebugInfo { debug_infos: func.debug },
+            unoptimized_debug: None,
+        }
+    }
+}
+
+impl From<ContractFunctionArtifact> for ContractFunction {
+    fn from(func: ContractFunctionArtifact) -> Self {
+        ContractFunction {
+            name: func.name,
+            hash: func.hash,
+            is_unconstrained: func.is_unconstrained,
+            custom_attributes: func.custom_attributes,
+            abi: func.abi,
+            bytecode: func.bytecode,
+            debug: func.debug_symbols.debug_infos,
+            names: func.names,
+            brillig_names: func.brillig_names,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ContractFunction, ContractFunctionArtifact};
+
+    fn function_artifact() -> ContractFunctionArtifact {
+        ContractFunctionArtifact {
+            name: "foo".to_string(),
+            hash: 1,
+            is_unconstrained: false,
+            custom_attributes: Vec::new(),
+            abi: Default::default(),
+            bytecode: Default::default(),
+            debug_symbols: Default::default(),
+            names: vec!["foo".to_string()],
+            brillig_names: Vec::new(),
+            unoptimized_debug: None,
+        }
+    }
+
+    #[test]
+    fn reads_functions_without_hash_or_names() {
+        let mut json = serde_json::to_value(function_artifact()).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("hash");
+        fields.remove("names");
+
+        let function: ContractFunctionArtifact = serde_json::from_value(json).unwrap();
+        assert_eq!(function.hash, 0);
+        assert!(function.names.is_empty());
+    }
+
+    #[test]
+    fn reused_functions_keep_their_names() {
+        let function = ContractFunction::from(function_artifact());
+        assert_eq!(function.names, vec!["foo".to_string()]);
+    }
+}
@@ -39,6 +39,43 @@ pub struct ProgramArtifact {
     pub names: Vec<String>,
     /// Names of the unconstrained functions in the program.
     pub brillig_names: Vec<String>,
+
+    /// The `--opt-level` the bytecode was optimized with, if it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimization_level: Option<u8>,
+
+    /// The function which was compiled as the entry point, if it wasn't `main`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_function: Option<String>,
+
+    /// A digest of the sources, manifests and compile options which the artifact was built from.
+    /// An artifact whose digest still matches is up to date and doesn't need to be rebuilt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_hash: Option<String>,
+
+    /// The program as it was before optimization, if it was compiled with `--keep-unoptimized-debug`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unoptimized_debug: Option<UnoptimizedDebug>,
+}
+
+/// The bytecode and debug symbols of a program before optimization.
+///
+/// Optimization merges and removes opcodes, so the debug symbols of the optimized bytecode can only
+/// point to the call stacks of the opcodes they were merged from. These debug symbols map each opcode of
+/// the unoptimized bytecode to its own call stack, so debuggers can use them for more precise locations.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct UnoptimizedDebug {
+    #[serde(
+        serialize_with = "Program::serialize_program_base64",
+        deserialize_with = "Program::deserialize_program_base64"
+    )]
+    pub bytecode: Program<FieldElement>,
+
+    #[serde(
+        serialize_with = "ProgramDebugInfo::serialize_compressed_base64_json",
+        deserialize_with = "ProgramDebugInfo::deserialize_compressed_base64_json"
+    )]
+    pub debug_symbols: ProgramDebugInfo,
 }
 
 impl From<CompiledProgram> for ProgramArtifact {
@@ -52,6 +89,10 @@ impl From<CompiledProgram> for ProgramArtifact {
             file_map: compiled_program.file_map,
             names: compiled_program.names,
             brillig_names: compiled_program.brillig_names,
+            optimization_level: None,
+            entry_function: None,
+            source_hash: None,
+            unoptimized_debug: None,
         }
     }
 }
@@ -68,6 +109,8 @@ impl From<ProgramArtifact> for CompiledProgram {
             warnings: vec![],
             names: program.names,
             brillig_names: program.brillig_names,
+            monomorphized_functions: BTreeMap::new(),
+            unreachable_functions: Vec::new(),
         }
     }
 }
@@ -183,6 +183,10 @@ mod tests {
             file_map: BTreeMap::default(),
             names: vec!["main".to_string()],
             brillig_names: Vec::new(),
+            optimization_level: None,
+            entry_function: None,
+            source_hash: None,
+            unoptimized_debug: None,
         };
 
         // Write the artifact to a file
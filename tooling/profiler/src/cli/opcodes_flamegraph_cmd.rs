@@ -192,6 +192,10 @@ mod tests {
             file_map: BTreeMap::default(),
             names: vec!["main".to_string()],
             brillig_names: Vec::new(),
+            optimization_level: None,
+            entry_function: None,
+            source_hash: None,
+            unoptimized_debug: None,
         };
 
         // Write the artifact to a file
@@ -233,6 +237,10 @@ mod tests {
             file_map: BTreeMap::default(),
             names: vec!["main".to_string()],
             brillig_names: vec!["main".to_string()],
+            optimization_level: None,
+            entry_function: None,
+            source_hash: None,
+            unoptimized_debug: None,
         };
 
         // Write the artifact to a file